@@ -0,0 +1,636 @@
+# Practice
+type-this = Type this
+all-correct = All correct, good job!
+enter-to-continue = Enter to continue
+rejected-input = Rejected input: { $reason }, it was not counted in the stats
+speed = Speed
+time = Time
+total-error-score = Total error score
+last-mistakes = Last mistakes
+error-stats = Error stats
+shift-errors = Letters typed in the wrong case: { $count }
+suspicion-paste = paste
+suspicion-synthetic = synthetic key events
+suspicion-burst = too fast to be typed by hand
+
+# Settings
+language-auto = Browser language
+
+# Speed units
+unit-wpm = WPM
+unit-actual-wpm = words/min
+unit-cpm = CPM
+unit-kpm = strokes/min
+
+# Custom text
+custom-text = Custom text
+practice-this-text = Practice this text
+charset-summary = { $count } distinct characters, mostly { $script }
+charset-off-layout = Not on the default layout, make sure your keyboard can type them: { $chars }
+charset-missing = Not in the practiced set and would be ignored: { $chars }
+charset-extend = Add them
+script-latin = Latin
+script-cyrillic = Cyrillic
+script-greek = Greek
+script-other = other scripts
+drill-this-text = Drill its sentences
+
+# Repeat-until-clean drill
+drill-start = Repeat-until-clean drill
+drill-stop = Stop drill
+drill-clean-repetitions = clean repetitions needed:
+drill-progress = Sentence { $sentence } of { $sentences }, attempt { $attempt }, clean in a row { $clean }/{ $required }
+drill-result = { $attempts } attempts: { $sentence }
+
+# Retention checks
+retention-check-prompt = Retention check: keys you mastered but have not practiced for a while
+retention-checks = Retention checks (correct/total)
+
+# Technique tips
+tip-dismiss = Got it
+tip-backspace = You correct a lot. Slow down a bit: accuracy first, speed follows.
+tip-same-finger = Transitions typed by one finger are slow. Start moving the finger to the second key while releasing the first.
+tip-left-hand = Your left hand makes most of the mistakes. Check that it rests on A S D F between keys.
+tip-right-hand = Your right hand makes most of the mistakes. Check that it rests on J K L ; between keys.
+tip-look-at-screen = Long pauses between keys often mean looking down. Keep your eyes on the screen and trust your fingers.
+
+# Stats import
+import-stats = Statistics storage and import from another device
+import-preview = Merging will change:
+import-mistake-pairs = Mistake pairs with imported counts: { $count }
+import-retention = Retention checks added: { $count }
+import-results = Results added: { $count }
+import-settings-newer = Imported settings are newer and will replace the local ones
+import-settings-kept = Local settings are newer and will be kept
+import-apply = Merge
+import-cancel = Cancel
+import-failed = Could not read the stats file: { $error }
+import-same-device = This file was exported from this device, the newer of its and the local statistics is kept
+compact-storage = Store stats in a compact binary format
+
+# Assets
+loading = Loading…
+loading-failed = Loading failed: { $error }
+
+# Startup report
+startup-report = Started in { $total } (budget { $budget })
+startup-wasm-started = WASM started
+startup-state-loaded = Stored data loaded
+startup-first-render = Prompt shown
+
+# History
+recent-results = Recent results
+result-date = Date
+result-mistakes = Mistakes
+result-note = Note
+note-placeholder = e.g. new keyboard, tired
+results = Results
+accuracy = Accuracy
+
+# Keyboards
+keyboard = Keyboard
+keyboard-unspecified = not specified
+keyboard-new = new keyboard name
+keyboard-add = Add
+keyboard-breakdown = By keyboard
+
+# Fingers
+finger-breakdown = By finger, this session
+finger = Finger
+keystrokes = Keystrokes
+latency-ms = Mean interval, ms
+finger-map-standard = Standard keyboard
+finger-map-corne = Corne
+finger-map-ergodox = Ergodox
+finger-map-moonlander = Moonlander
+finger-left-0 = left pinky
+finger-left-1 = left ring
+finger-left-2 = left middle
+finger-left-3 = left index
+finger-left-4 = left thumb
+finger-right-0 = right pinky
+finger-right-1 = right ring
+finger-right-2 = right middle
+finger-right-3 = right index
+finger-right-4 = right thumb
+
+# Layers
+layer-chars = Characters typed on a layer:
+home-row-shift = Shift is a home-row modifier
+layer-transition = Keys
+layer-base = base layer
+layer-enter = switching to a layer
+layer-within = staying on a layer
+
+# Appearance
+appearance = Appearance and accessibility
+custom-css = Custom stylesheet, see THEMING.md for the available variables and classes:
+large-text = Large text and a thick caret
+
+# Read aloud
+read-aloud = Read aloud the upcoming text with F8
+pace-caret = Race a caret moving at the pace of recent results
+pace-caret-name = Pace
+char-space = space
+char-capital = capital { $char }
+char-33 = exclamation mark
+char-34 = double quote
+char-35 = hash
+char-36 = dollar
+char-37 = percent
+char-38 = ampersand
+char-39 = apostrophe
+char-40 = left parenthesis
+char-41 = right parenthesis
+char-42 = asterisk
+char-43 = plus
+char-44 = comma
+char-45 = minus
+char-46 = dot
+char-47 = slash
+char-58 = colon
+char-59 = semicolon
+char-60 = less than
+char-61 = equals
+char-62 = greater than
+char-63 = question mark
+char-64 = at
+char-91 = left bracket
+char-92 = backslash
+char-93 = right bracket
+char-94 = caret
+char-95 = underscore
+char-96 = backtick
+char-123 = left brace
+char-124 = pipe
+char-125 = right brace
+char-126 = tilde
+
+# Auxiliary input
+aux-input = Gamepad and mouse buttons
+aux-input-hint = Bind gamepad buttons, foot pedals or extra mouse buttons to actions.
+aux-next-prompt = Next prompt
+aux-pause = Pause
+aux-toggle-zen = Zen mode, only the prompt is shown
+aux-toggle-fullscreen = Fullscreen zen mode
+aux-gamepad-button = gamepad button { $index }
+aux-mouse-button = mouse button { $index }
+aux-bind = Bind
+aux-press-button = Press a button...
+aux-clear = Clear
+paused = Paused.
+resume = Resume
+leave-zen = Leave zen mode
+
+# Data entry assessment
+data-entry-start = Data entry test
+data-entry-hint = { $fields } numeric fields, each confirmed with Enter, scored in keystrokes per hour.
+data-entry-progress = Field { $field } of { $fields }, press Enter after each one.
+data-entry-stop = Stop the test
+data-entry-summary = Data entry test result
+data-entry-fields = Fields
+data-entry-keystrokes = Keystrokes
+data-entry-errors = Fields with errors
+data-entry-accuracy = Accuracy
+data-entry-gross-kph = Gross keystrokes per hour
+data-entry-net-kph = Net keystrokes per hour
+data-entry-rules = Keystrokes include corrections and Enter. Net keystrokes per hour deduct { $penalty } keystrokes for every field with an error.
+data-entry-print = Print
+data-entry-close = Close
+
+# Exam
+exam-start = Five-minute exam
+exam-backspace-limit = Backspaces allowed:
+exam-unlimited = unlimited
+exam-progress = Exam: { $remaining } left, backspaces left: { $backspaces }.
+exam-stop = Abandon the exam
+exam-net-wpm = Net WPM
+exam-gross-wpm = Gross WPM
+exam-errors = Uncorrected errors
+exam-tampered = edited after the exam
+
+# Prompt sources
+prompt-source = Prompts:
+source-random-symbols = random symbols
+source-dictionary = common words
+source-quotes = quotes
+source-code = code
+source-online-quotes = random quotes from the internet, bundled ones offline
+source-snippets = code snippets with indentation
+uppercase-words = Capitalize half of the words
+source-url = lines of a text from a URL
+source-url-placeholder = https://example.com/text.txt
+blocklist-built-in = Leave out offensive words
+blocked-words = and these:
+blocked-words-placeholder = words, or prefixes ending with *
+
+# Metrics
+session-metrics = This session:
+metric-speed = Speed
+metric-accuracy = Accuracy
+metric-consistency = Consistency
+metric-same-finger-latency = Same-finger bigram latency, ms
+metric-backspace-rate = Backspaces
+
+# Storage backends
+storage-backend = Keep statistics in:
+backend-local-storage = browser local storage
+backend-indexed-db = browser database (IndexedDB)
+backend-memory = memory only, lost when the page is closed
+backend-file = a file
+backend-file-hint = Statistics are kept in memory until downloaded. Open the downloaded file next time to continue.
+backend-file-download = Download
+backend-file-open = Open a downloaded file:
+
+# Recompute
+recompute-stats = Recompute statistics
+recompute-stats-hint = Replays everything typed through the current scoring, so its improvements apply to old data.
+recompute-progress = Recomputing statistics: { $done } of { $total } events
+
+# Tags
+session-tags = Tags for this session:
+session-tags-placeholder = comma separated, like "morning, new switches"
+tag-filter = Only results tagged:
+
+# Saving
+saved = Saved
+saving = Saving…
+save-failed = Save failed: { $error }
+
+# Fullscreen
+fullscreen = Fullscreen (F9)
+
+# Ambient sound
+ambient = Background sound:
+ambient-none = None
+ambient-rain = Rain
+ambient-cafe = Café
+ambient-white-noise = White noise
+ambient-volume = Volume
+
+# Penalties
+penalties = Error penalties
+penalties-hint = How keystrokes change the error scores. Characters with higher scores show up in prompts more often. All statistics are recomputed with the new values.
+penalty-error = Added to the score of a mistyped character:
+penalty-pair = Added to the score of a mistaken pair of characters:
+penalty-correct = Taken from the scores on a correct keystroke:
+penalty-half-life = Days over which the scores of a character not typed halve, 0 never:
+penalty-char = Character
+penalty-share-now = Share in prompts now
+penalty-share-preview = With the new penalties
+penalties-apply = Apply
+penalties-default = Defaults
+
+# Prompt weights
+weights-title = Why this prompt
+weights-hint = Characters with a higher error score are picked more often. The weight is how many times more often than a character typed without errors. Keys typed slower than the average key are weighted up too, by their latency.
+weights-retention-hint = This prompt is a retention check: it is made of characters due for review, to see whether earlier practice stuck.
+weights-none = No character of this prompt is weighted up.
+weights-char = Character
+weights-weight = Weight
+weights-error-score = Error score
+weights-review = Review
+weights-due = due
+
+# Sloppy prompts
+personal-best = Personal best:
+min-accuracy = Prompts below this accuracy are sloppy and are no personal bests:
+min-accuracy-off = off
+sloppy = sloppy
+sloppy-prompt = Below the minimum accuracy: this prompt is no personal best. Its mistakes still count.
+
+# Auto-advance
+auto-advance = Start the next prompt automatically after
+auto-advance-off = Enter
+auto-advance-seconds = seconds
+auto-advance-in = Next prompt in { $seconds } s, type anything to stay or press Enter to continue now
+
+# Last session
+last-session = Last session: { $date }, { $duration }
+last-session-prompts = Prompts completed: { $prompts }, keystrokes: { $keystrokes }
+
+# Timer start
+timer-first-keystroke = timed from the first keystroke
+timer-prompt-shown = timed from showing the prompt
+timer-start-hint = Results are tagged with how they were timed, filter by the tag to compare like with like
+
+# Warm-up
+warm-up-prompts = Warm-up prompts at the start of a session, their mistakes do not change the statistics:
+warm-up = warm-up { $prompt } of { $prompts }
+
+# Key discovery
+discovery-start = Discover the keys of a new layout
+discovery-stop = Stop discovering
+discovery-find = Press { $char }
+discovery-delay = Reveal the key after, ms:
+discovery-revealed = revealed { $revealed } of { $shown } times
+layout-qwerty = QWERTY
+layout-dvorak = Dvorak
+layout-colemak = Colemak
+layout-workman = Workman
+
+# Key sounds
+key-sounds = Sounds while typing
+sound-latency-calibrated = (delay on this device { $ms } ms)
+sound-latency-reported = (delay reported by the browser { $ms } ms)
+sound-latency-unknown = (delay unknown, calibrate to sync the screen with the clicks)
+sound-calibrate = Calibrate the delay
+sound-calibrating = Press Space along with the clicks… { $taps } presses
+sound-theme = Theme
+sound-theme-soft-click = Soft click
+sound-theme-typewriter = Typewriter
+error-sounds = Sound on mistakes
+
+# Character classes
+class-breakdown = Accuracy by character class this session:
+class = Class
+class-lowercase = Lowercase letters
+class-uppercase = Uppercase letters
+class-digit = Digits
+class-bracket = Brackets
+class-operator = Operators
+class-whitespace = Whitespace
+class-other = Punctuation and other
+class-weakest = Practice next: { $class }
+
+# Data deletion
+deletion = Delete data
+deletion-range = Delete the data of days
+deletion-delete = Delete…
+deletion-sessions = Recent sessions:
+deletion-session = { $duration }, { $prompts } prompts
+deletion-keystroke-logs = Delete all keystroke logs, keeping the statistics…
+deletion-preview = This will be removed:
+deletion-events = Logged events: { $count }
+deletion-keystrokes = Keystrokes: { $count }
+deletion-prompts = Prompts: { $count }
+deletion-results = Prompt results: { $count }
+deletion-stats-kept = Error statistics stay as they are, only totals remain.
+deletion-stats-changed = Error statistics are recomputed without the removed keystrokes.
+deletion-copies = Backups and other devices keep their copies, merging them brings the data back.
+deletion-apply = Delete
+deletion-cancel = Cancel
+
+# File encryption
+file-password = Password of downloaded and opened files:
+file-password-hint = Files downloaded with a password are encrypted, keep the password safe: without it they cannot be opened. Leave empty for plain files.
+file-password-needed = The file is encrypted, enter its password and open it again
+encryption-failed = Could not encrypt the file: { $error }
+
+# Share codes
+share-code = Share your setup
+share-code-yours = Code of your layout, characters and prompt source:
+share-code-enter = Enter a code from a friend:
+share-code-preview = The code sets:
+share-code-chars = Extra characters: { $chars }
+share-code-apply = Use this setup
+share-code-invalid = This is not a share code
+share-code-checksum = The code has a typo, check it character by character
+share-code-version = The code is from a newer version of the app
+
+# Bigram explorer
+bigrams = Character pairs
+bigrams-search = Search pairs
+bigrams-drill = Drill selected ({ $count })
+bigram = Pair
+bigrams-error-rate = Errors
+bigrams-count = Typed
+
+# Period comparison
+comparison = Compare two periods
+comparison-before = Before:
+comparison-after = After:
+comparison-days = Days…
+comparison-key = Key
+
+# Layout migration
+migration-start = Switch from { $layout } to
+migration = Switching from { $from } to { $to }. Practicing:
+migration-stage = Stage { $stage } of { $stages }, keys:
+migration-next-stage = Add the next row
+migration-all-keys = All keys are introduced.
+migration-surpassed = The new layout is faster since { $date }!
+migration-old-speed = The new layout catches up at { $speed } { $unit }.
+migration-no-old-results = Practice the old layout too to compare the speeds.
+migration-end = End the switch
+
+# Speed chart
+speed-chart = Speed by week, { $unit }:
+speed-chart-range = From { $min } to { $max } over { $weeks } weeks with results.
+
+# Improvement trend
+migration-time-left = About { $minutes } more minutes of practice to catch up at the current rate.
+
+# Practice settings
+practice-settings = Practice settings
+prompt-len = Characters in a random symbols prompt:
+charset = Characters practiced:
+charset-ascii = Latin and ASCII symbols
+charset-cyrillic = Cyrillic
+charset-greek = Greek
+symbol-classes = Random symbols include:
+mistakes-shown = Recent mistakes listed:
+persist-stats = Save statistics
+not-saved = Statistics are not saved
+
+# Pages
+page-practice = Practice
+page-stats = Statistics
+page-lessons = Lessons
+page-settings = Settings
+
+# Warm-up routine
+warm-up-offer = Start with a short warm-up: home-row rolls, alternating hands and common trigrams. It does not change the statistics.
+warm-up-start = Warm up
+warm-up-skip = Skip the warm-up
+warm-up-progress = Warm-up, { $section }: line { $line } of { $lines }.
+warm-up-rolls = home-row rolls
+warm-up-alternating = alternating hands
+warm-up-trigrams = common trigrams
+
+# Words
+word-accuracy = Words without mistakes: { $accuracy }%.
+words-fumbled = Fumbled:
+session-words-fumbled = Words you fumbled this session:
+word-fumbled-count = { $fumbled } of { $typed } times
+
+# Tabs
+other-tab-saves = The app is open in another tab, which saves the statistics. Practice here is not saved.
+save-from-this-tab = Save from this tab
+
+# Keyboard
+show-keyboard = Keyboard under the prompt showing the next key
+keyboard-layout = Keyboard layout:
+
+# Backup reminder
+backup-reminder-days = Suggest a backup every this many days, 0 for never:
+backup-due = { $results } results are in no backup yet. A downloaded backup keeps them if the browser storage is cleared.
+backup-download = Download a backup
+backup-later = Later
+
+# Rhythm playback
+rhythm-play = Play the session's rhythm
+rhythm-stop = Stop
+rhythm-hint = Every keystroke of the session as a note at the pace it was typed, mistakes sound off.
+
+# Transitions
+weights-ngrams = Transitions you mistype or type slowly, worked into random symbols: { $ngrams }
+
+# Long texts
+book = Long text
+book-open = Open a text or EPUB file to copy-type over many sessions:
+book-open-failed = The text could not be opened: { $error }
+book-save-failed = The text could not be saved: { $error }
+book-progress = { $title }: { $chapter }, { $chapter_percent }% of the chapter, { $percent }% of the text
+book-finished = The whole text is typed.
+book-stop = Practice other prompts
+book-resume = Type from the text
+book-chapter = Chapter
+book-typed = Typed
+book-results = Results
+book-go = Go
+book-bookmark-note = Bookmark note
+book-bookmark-add = Bookmark this place
+book-bookmark-remove = Remove
+
+# Timed test
+timed = Timed test:
+timed-seconds = { $seconds } s
+timed-progress = { $remaining } left, the text keeps coming until the time is up.
+timed-stop = Stop the test
+timed-result = { $seconds }-second test: { $speed }, { $accuracy }% accuracy, { $chars } characters, { $mistakes } mistakes.
+timed-again = Again
+timed-close = Close
+timed-left = Left hand only:
+timed-right = Right hand only:
+timed-best = (best { $speed })
+
+# Course
+curriculum-start = Learn the keys one at a time
+curriculum-thresholds = A new key comes once the recent prompts reach
+curriculum-keys = Keys so far:
+curriculum-next = Next: { $key }, prompts at these keys: { $prompts } of { $needed }.
+curriculum-time-left = About { $minutes } min of practice left at your rate of progress.
+curriculum-all-keys = All keys are introduced.
+curriculum-unlocked = New key: { $key }
+curriculum-pause = Practice all keys
+curriculum-resume = Back to the course
+curriculum-reset = Start over
+
+# Text normalization
+normalize-quotes = Straighten curly quotes
+normalize-dashes = Plain dashes and dots for dashes and ellipses
+normalize-untypeable = Characters the keyboard lacks:
+untypeable-keep = keep
+untypeable-strip = leave out
+untypeable-transliterate = spell in Latin letters
+normalize-replaced = Replaced: { $substitutions }
+
+# Pasted long texts
+book-pasted = Pasted text
+book-this-text = Type over many sessions
+book-passage-len = Characters per passage, up to the end of the sentence:
+
+# Hesitations
+hesitations = Hesitations
+hesitations-percentile = Keystrokes slower than this percentage of the session's:
+hesitations-threshold = over { $ms } ms
+
+# Stats export
+export-stats = Download statistics
+export-stats-hint = Error scores, results and the keystroke log in a file to merge on another device, or here after clearing site data.
+import-stats-file = Merge a downloaded statistics file:
+
+# CapsLock
+caps-lock-guard = Ignore keystrokes while CapsLock is on
+caps-lock-on = CapsLock is on, letters come out in the wrong case.
+caps-lock-ignored = CapsLock is on: keystrokes are ignored until it is turned off.
+
+# Mode hints
+hint-dismiss = Got it
+hints-reset = Show the mode hints again
+hint-warm-up = Warm-up: a few lines of home-row rolls, alternating hands and common trigrams. Mistakes here do not change the statistics. Press Enter after each line.
+hint-drill = Drill: a sentence comes back until it is typed without mistakes enough times in a row. Any mistake, even a corrected one, restarts the count. Press Enter after each attempt.
+hint-exam = Exam: type the passage until the time is up. Backspaces may be limited, and lines can be neither skipped nor paused. Net WPM deducts a word per minute for every uncorrected error.
+hint-data-entry = Data entry: type each numeric field and confirm it with Enter. Speed is counted in keystrokes per hour, corrections and Enter included, and fields with errors are deducted from the net speed.
+hint-timed = Timed test: the clock starts with the first keystroke and the text keeps coming until the time is up. Pausing and skipping are off. The result is the speed and accuracy over that time.
+hint-book = Long text: the book is typed a passage at a time and the place is kept between sessions. Press Enter after each passage; results are tagged with the chapter.
+hint-curriculum = Course: prompts use only the keys introduced so far. A new key comes once the recent prompts are accurate and fast enough, the thresholds are under the course controls.
+hint-discovery = Key discovery: press the character shown. If you hesitate, its key is revealed on the keyboard; how often keys had to be revealed shows how well the layout is learned.
+hint-code = Code snippets: the prompts keep their line breaks and indentation. Press Enter at the end of a line, marked with ↵, and Tab to type the indentation of the next one.
+
+# Accuracy regression
+regression = Accuracy dropped from { $before }% to { $after }% since { $date }, more than day-to-day variation explains.
+regression-compare = Compare before and after
+
+# Unreadable storage
+unreadable = Some saved data could not be read. It is kept unchanged under the same name ending in "{ $suffix }", and this data starts anew:
+
+# Error heatmap
+heatmap = Error heatmap
+heatmap-hint = Keys are shaded by their error score, both characters of a key counted, relative to the weakest key.
+heatmap-key = { $key }: error score { $score }
+shift-left = Left Shift
+shift-right = Right Shift
+
+# Progress report
+export-report = Download a report
+export-report-hint = A page with the charts and tables of your progress to keep or print, it opens in any browser without the app.
+report-title = Typing progress
+report-generated = Generated on { $date }
+report-summary = Summary
+report-results = Prompts completed
+report-practice-time = Time typing
+report-hours = { $hours } h
+report-personal-best = Personal best
+report-speed = Speed by week, { $unit }
+report-range = From { $min } to { $max }.
+report-week = Week
+report-daily-accuracy = Accuracy by day
+report-day = Day
+report-weakest = Characters with the highest error scores
+report-char = Character
+report-error-score = Error score
+
+# Code snippets
+code-any = any language
+code-rust = Rust
+code-python = Python
+code-javascript = JavaScript
+
+# Idle suggestions
+suggestion-slower-key = Your { $key } keystrokes got { $percent }% slower this week. Drill it?
+suggestion-timed-test = You have not done a timed test in { $days } days.
+suggestion-first-timed-test = You have not tried a timed test yet.
+suggestion-weakest-key = { $key } has the highest error score. Drill it?
+suggestion-drill = Drill
+suggestion-take-timed-test = Take a one-minute test
+
+# Numeric keypad
+numpad = Numeric keypad
+numpad-hint = Keys typed on the keypad, shaded by their share of mistakes relative to the weakest key. In number drills and data entry they do not change the error scores.
+numpad-key = { $key }, { $finger }: { $mistakes } mistakes in { $presses } presses
+numpad-col-key = Key
+numpad-col-finger = Finger
+numpad-col-presses = Presses
+numpad-col-accuracy = Accuracy, %
+numpad-col-latency = Mean latency, ms
+
+# Session goals
+goal-start = Start a session:
+goal-minutes = { $minutes } minutes
+goal-prompts = { $prompts } prompts
+goal-course-stage = one course stage
+goal-minutes-progress = { $elapsed } of { $minutes } minutes
+goal-prompts-progress = { $done } of { $prompts } prompts
+goal-stage-progress = Until the course introduces { $key }
+goal-stop = End the session
+goal-reached = Session goal reached in { $duration }
+goal-stopped = Session ended after { $duration }
+
+# Number drills
+numbers-drill = Number drill with dates, amounts and phone numbers:
+numbers-row = number row
+numbers-numpad = numeric keypad
+numbers-row-stats = Number row in number drills
+numbers-row-stats-hint = Keys of number drills typed on the number row, kept apart from the error scores.
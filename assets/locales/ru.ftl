@@ -0,0 +1,636 @@
+# Practice
+type-this = Наберите
+all-correct = Без ошибок, отлично!
+enter-to-continue = Enter, чтобы продолжить
+rejected-input = Ввод отклонён: { $reason }, он не учтён в статистике
+speed = Скорость
+time = Время
+total-error-score = Суммарный счёт ошибок
+last-mistakes = Последние ошибки
+error-stats = Статистика ошибок
+shift-errors = Буквы, набранные не в том регистре: { $count }
+suspicion-paste = вставка из буфера обмена
+suspicion-synthetic = сгенерированные нажатия
+suspicion-burst = слишком быстро для ручного набора
+
+# Settings
+language-auto = Язык браузера
+
+# Speed units
+unit-wpm = WPM
+unit-actual-wpm = слов/мин
+unit-cpm = зн/мин
+unit-kpm = нажатий/мин
+
+# Custom text
+custom-text = Свой текст
+practice-this-text = Тренироваться на этом тексте
+charset-summary = Различных символов: { $count }, в основном { $script }
+charset-off-layout = Нет в раскладке по умолчанию, убедитесь, что их можно набрать: { $chars }
+charset-missing = Не входят в тренируемый набор и будут проигнорированы: { $chars }
+charset-extend = Добавить
+script-latin = латиница
+script-cyrillic = кириллица
+script-greek = греческий
+script-other = другие алфавиты
+drill-this-text = Отрабатывать по предложениям
+
+# Repeat-until-clean drill
+drill-start = Повторять до чистого набора
+drill-stop = Закончить
+drill-clean-repetitions = нужно чистых повторов:
+drill-progress = Предложение { $sentence } из { $sentences }, попытка { $attempt }, чисто подряд { $clean }/{ $required }
+drill-result = попыток: { $attempts }: { $sentence }
+
+# Retention checks
+retention-check-prompt = Проверка навыка: клавиши, которые вы освоили, но давно не тренировали
+retention-checks = Проверки навыка (верно/всего)
+
+# Technique tips
+tip-dismiss = Понятно
+tip-backspace = Вы часто исправляете. Чуть замедлитесь: сначала точность, скорость придёт.
+tip-same-finger = Переходы одним пальцем медленные. Начинайте движение ко второй клавише, отпуская первую.
+tip-left-hand = Большинство ошибок делает левая рука. Проверьте, что она возвращается на Ф Ы В А.
+tip-right-hand = Большинство ошибок делает правая рука. Проверьте, что она возвращается на О Л Д Ж.
+tip-look-at-screen = Долгие паузы между клавишами часто значат взгляд на клавиатуру. Смотрите на экран и доверяйте пальцам.
+
+# Stats import
+import-stats = Хранение статистики и импорт с другого устройства
+import-preview = Объединение изменит:
+import-mistake-pairs = Пар ошибок с импортируемыми счётчиками: { $count }
+import-retention = Добавится проверок навыка: { $count }
+import-results = Добавится результатов: { $count }
+import-settings-newer = Импортируемые настройки новее и заменят локальные
+import-settings-kept = Локальные настройки новее и будут сохранены
+import-apply = Объединить
+import-cancel = Отмена
+import-failed = Не удалось прочитать файл статистики: { $error }
+import-same-device = Этот файл выгружен с этого же устройства, из его и местной статистики останется более новая
+compact-storage = Хранить статистику в компактном двоичном формате
+
+# Assets
+loading = Загрузка…
+loading-failed = Не удалось загрузить: { $error }
+
+# Startup report
+startup-report = Запуск за { $total } (бюджет { $budget })
+startup-wasm-started = WASM запущен
+startup-state-loaded = Сохранённые данные загружены
+startup-first-render = Задание показано
+
+# History
+recent-results = Последние результаты
+result-date = Дата
+result-mistakes = Ошибки
+result-note = Заметка
+note-placeholder = например, новая клавиатура, устал
+results = Результатов
+accuracy = Точность
+
+# Keyboards
+keyboard = Клавиатура
+keyboard-unspecified = не указана
+keyboard-new = название новой клавиатуры
+keyboard-add = Добавить
+keyboard-breakdown = По клавиатурам
+
+# Fingers
+finger-breakdown = По пальцам, эта сессия
+finger = Палец
+keystrokes = Нажатий
+latency-ms = Средний интервал, мс
+finger-map-standard = Обычная клавиатура
+finger-map-corne = Corne
+finger-map-ergodox = Ergodox
+finger-map-moonlander = Moonlander
+finger-left-0 = левый мизинец
+finger-left-1 = левый безымянный
+finger-left-2 = левый средний
+finger-left-3 = левый указательный
+finger-left-4 = левый большой
+finger-right-0 = правый мизинец
+finger-right-1 = правый безымянный
+finger-right-2 = правый средний
+finger-right-3 = правый указательный
+finger-right-4 = правый большой
+
+# Layers
+layer-chars = Символы на слоях:
+home-row-shift = Shift как модификатор на домашнем ряду
+layer-transition = Клавиши
+layer-base = основной слой
+layer-enter = переход на слой
+layer-within = в пределах слоя
+
+# Appearance
+appearance = Внешний вид и доступность
+custom-css = Свой стиль, доступные переменные и классы описаны в THEMING.md:
+large-text = Крупный текст и толстый курсор
+
+# Read aloud
+read-aloud = Читать вслух следующий текст по F8
+pace-caret = Соревноваться с курсором, идущим в темпе последних результатов
+pace-caret-name = Темп
+char-space = пробел
+char-capital = заглавная { $char }
+char-33 = восклицательный знак
+char-34 = двойная кавычка
+char-35 = решётка
+char-36 = доллар
+char-37 = процент
+char-38 = амперсанд
+char-39 = апостроф
+char-40 = открывающая скобка
+char-41 = закрывающая скобка
+char-42 = звёздочка
+char-43 = плюс
+char-44 = запятая
+char-45 = минус
+char-46 = точка
+char-47 = слеш
+char-58 = двоеточие
+char-59 = точка с запятой
+char-60 = меньше
+char-61 = равно
+char-62 = больше
+char-63 = вопросительный знак
+char-64 = собака
+char-91 = открывающая квадратная скобка
+char-92 = обратный слеш
+char-93 = закрывающая квадратная скобка
+char-94 = крышка
+char-95 = подчёркивание
+char-96 = обратная кавычка
+char-123 = открывающая фигурная скобка
+char-124 = вертикальная черта
+char-125 = закрывающая фигурная скобка
+char-126 = тильда
+
+# Auxiliary input
+aux-input = Кнопки геймпада и мыши
+aux-input-hint = Назначьте действия кнопкам геймпада, педалям или дополнительным кнопкам мыши.
+aux-next-prompt = Следующая строка
+aux-pause = Пауза
+aux-toggle-zen = Режим дзен, видна только строка
+aux-toggle-fullscreen = Полноэкранный режим дзен
+aux-gamepad-button = кнопка геймпада { $index }
+aux-mouse-button = кнопка мыши { $index }
+aux-bind = Назначить
+aux-press-button = Нажмите кнопку...
+aux-clear = Сбросить
+paused = Пауза.
+resume = Продолжить
+leave-zen = Выйти из режима дзен
+
+# Data entry assessment
+data-entry-start = Тест ввода данных
+data-entry-hint = { $fields } числовых полей, каждое подтверждается Enter, оценка в нажатиях в час.
+data-entry-progress = Поле { $field } из { $fields }, нажимайте Enter после каждого.
+data-entry-stop = Остановить тест
+data-entry-summary = Результат теста ввода данных
+data-entry-fields = Полей
+data-entry-keystrokes = Нажатий
+data-entry-errors = Полей с ошибками
+data-entry-accuracy = Точность
+data-entry-gross-kph = Нажатий в час, всего
+data-entry-net-kph = Нажатий в час, чистых
+data-entry-rules = Нажатия включают исправления и Enter. Из чистых нажатий в час вычитается { $penalty } нажатий за каждое поле с ошибкой.
+data-entry-print = Печать
+data-entry-close = Закрыть
+
+# Exam
+exam-start = Пятиминутный экзамен
+exam-backspace-limit = Разрешено Backspace:
+exam-unlimited = без ограничений
+exam-progress = Экзамен: осталось { $remaining }, Backspace осталось: { $backspaces }.
+exam-stop = Прервать экзамен
+exam-net-wpm = Чистые слов/мин
+exam-gross-wpm = Всего слов/мин
+exam-errors = Неисправленные ошибки
+exam-tampered = изменён после экзамена
+
+# Prompt sources
+prompt-source = Строки:
+source-random-symbols = случайные символы
+source-dictionary = частые слова
+source-quotes = цитаты
+source-code = код
+source-online-quotes = случайные цитаты из интернета, встроенные без сети
+source-snippets = фрагменты кода с отступами
+uppercase-words = Писать половину слов с заглавной буквы
+source-url = строки текста по ссылке
+source-url-placeholder = https://example.com/text.txt
+blocklist-built-in = Пропускать оскорбительные слова
+blocked-words = и эти:
+blocked-words-placeholder = слова или начала слов со * в конце
+
+# Metrics
+session-metrics = Этот сеанс:
+metric-speed = Скорость
+metric-accuracy = Точность
+metric-consistency = Ровность
+metric-same-finger-latency = Задержка биграмм одним пальцем, мс
+metric-backspace-rate = Backspace
+
+# Storage backends
+storage-backend = Хранить статистику:
+backend-local-storage = в локальном хранилище браузера
+backend-indexed-db = в базе данных браузера (IndexedDB)
+backend-memory = только в памяти, пропадёт при закрытии страницы
+backend-file = в файле
+backend-file-hint = Статистика хранится в памяти, пока её не скачать. В следующий раз откройте скачанный файл, чтобы продолжить.
+backend-file-download = Скачать
+backend-file-open = Открыть скачанный файл:
+
+# Recompute
+recompute-stats = Пересчитать статистику
+recompute-stats-hint = Повторно обрабатывает всё набранное текущим алгоритмом оценки, чтобы его улучшения применились к старым данным.
+recompute-progress = Пересчёт статистики: { $done } из { $total } событий
+
+# Tags
+session-tags = Метки этого сеанса:
+session-tags-placeholder = через запятую, например «утро, новые свитчи»
+tag-filter = Только результаты с метками:
+
+# Saving
+saved = Сохранено
+saving = Сохранение…
+save-failed = Не удалось сохранить: { $error }
+
+# Fullscreen
+fullscreen = Во весь экран (F9)
+
+# Ambient sound
+ambient = Фоновый звук:
+ambient-none = Нет
+ambient-rain = Дождь
+ambient-cafe = Кафе
+ambient-white-noise = Белый шум
+ambient-volume = Громкость
+
+# Penalties
+penalties = Штрафы за ошибки
+penalties-hint = Как нажатия меняют счёт ошибок. Символы с большим счётом чаще попадаются в строках. Вся статистика пересчитывается с новыми значениями.
+penalty-error = Прибавляется к счёту символа, набранного с ошибкой:
+penalty-pair = Прибавляется к счёту перепутанной пары символов:
+penalty-correct = Вычитается из счёта при верном нажатии:
+penalty-half-life = Дней, за которые оценки ненабираемого символа уменьшаются вдвое, 0 — никогда:
+penalty-char = Символ
+penalty-share-now = Доля в строках сейчас
+penalty-share-preview = С новыми штрафами
+penalties-apply = Применить
+penalties-default = По умолчанию
+
+# Prompt weights
+weights-title = Почему эта строка
+weights-hint = Символы с большим счётом ошибок выбираются чаще. Вес показывает, во сколько раз чаще, чем символ, набираемый без ошибок. Клавиши, набираемые медленнее средней, тоже выбираются чаще, по их задержке.
+weights-retention-hint = Это проверка запоминания: строка составлена из символов, которые пора повторить, чтобы проверить, закрепились ли прошлые занятия.
+weights-none = Ни один символ этой строки не выбирается чаще других.
+weights-char = Символ
+weights-weight = Вес
+weights-error-score = Счёт ошибок
+weights-review = Повторение
+weights-due = пора
+
+# Sloppy prompts
+personal-best = Личный рекорд:
+min-accuracy = Строки с точностью ниже этой считаются небрежными и не идут в рекорды:
+min-accuracy-off = выкл.
+sloppy = небрежно
+sloppy-prompt = Точность ниже минимальной: эта строка не идёт в рекорды. Ошибки в ней всё равно учтены.
+
+# Auto-advance
+auto-advance = Начинать следующую строку автоматически через
+auto-advance-off = Enter
+auto-advance-seconds = секунд
+auto-advance-in = Следующая строка через { $seconds } с, нажмите любую клавишу, чтобы остаться, или Enter, чтобы продолжить сейчас
+
+# Last session
+last-session = Прошлый сеанс: { $date }, { $duration }
+last-session-prompts = Пройдено строк: { $prompts }, нажатий: { $keystrokes }
+
+# Timer start
+timer-first-keystroke = время с первого нажатия
+timer-prompt-shown = время с показа строки
+timer-start-hint = Результаты помечаются способом замера времени, отфильтруйте по метке, чтобы сравнивать сопоставимое
+
+# Warm-up
+warm-up-prompts = Строк разминки в начале сеанса, их ошибки не меняют статистику:
+warm-up = разминка { $prompt } из { $prompts }
+
+# Key discovery
+discovery-start = Изучить клавиши новой раскладки
+discovery-stop = Закончить изучение
+discovery-find = Нажмите { $char }
+discovery-delay = Показывать клавишу через, мс:
+discovery-revealed = показана { $revealed } из { $shown } раз
+layout-qwerty = QWERTY
+layout-dvorak = Dvorak
+layout-colemak = Colemak
+layout-workman = Workman
+
+# Key sounds
+key-sounds = Звуки при наборе
+sound-latency-calibrated = (задержка на этом устройстве { $ms } мс)
+sound-latency-reported = (задержка по данным браузера { $ms } мс)
+sound-latency-unknown = (задержка неизвестна, откалибруйте, чтобы экран совпадал со щелчками)
+sound-calibrate = Откалибровать задержку
+sound-calibrating = Нажимайте пробел вместе со щелчками… нажатий: { $taps }
+sound-theme = Тема
+sound-theme-soft-click = Мягкий щелчок
+sound-theme-typewriter = Пишущая машинка
+error-sounds = Звук при ошибке
+
+# Character classes
+class-breakdown = Точность по классам символов за сессию:
+class = Класс
+class-lowercase = Строчные буквы
+class-uppercase = Заглавные буквы
+class-digit = Цифры
+class-bracket = Скобки
+class-operator = Операторы
+class-whitespace = Пробельные символы
+class-other = Пунктуация и прочее
+class-weakest = Стоит потренировать: { $class }
+
+# Data deletion
+deletion = Удаление данных
+deletion-range = Удалить данные за дни
+deletion-delete = Удалить…
+deletion-sessions = Последние сессии:
+deletion-session = { $duration }, заданий: { $prompts }
+deletion-keystroke-logs = Удалить все журналы нажатий, сохранив статистику…
+deletion-preview = Будет удалено:
+deletion-events = Записанных событий: { $count }
+deletion-keystrokes = Нажатий: { $count }
+deletion-prompts = Заданий: { $count }
+deletion-results = Результатов заданий: { $count }
+deletion-stats-kept = Статистика ошибок не изменится, останутся только итоги.
+deletion-stats-changed = Статистика ошибок будет пересчитана без удалённых нажатий.
+deletion-copies = В резервных копиях и на других устройствах данные останутся, слияние с ними вернёт их.
+deletion-apply = Удалить
+deletion-cancel = Отмена
+
+# File encryption
+file-password = Пароль скачиваемых и открываемых файлов:
+file-password-hint = Файлы, скачанные с паролем, зашифрованы. Не теряйте пароль: без него их не открыть. Оставьте пустым для обычных файлов.
+file-password-needed = Файл зашифрован, введите его пароль и откройте файл снова
+encryption-failed = Не удалось зашифровать файл: { $error }
+
+# Share codes
+share-code = Поделиться настройкой
+share-code-yours = Код вашей раскладки, символов и источника заданий:
+share-code-enter = Введите код от друга:
+share-code-preview = Код задаёт:
+share-code-chars = Дополнительные символы: { $chars }
+share-code-apply = Использовать эту настройку
+share-code-invalid = Это не код настройки
+share-code-checksum = В коде опечатка, сверьте его посимвольно
+share-code-version = Код из более новой версии приложения
+
+# Bigram explorer
+bigrams = Пары символов
+bigrams-search = Поиск пар
+bigrams-drill = Тренировать выбранные ({ $count })
+bigram = Пара
+bigrams-error-rate = Ошибки
+bigrams-count = Набрано
+
+# Period comparison
+comparison = Сравнить два периода
+comparison-before = До:
+comparison-after = После:
+comparison-days = Дни…
+comparison-key = Клавиша
+
+# Layout migration
+migration-start = Перейти с { $layout } на
+migration = Переход с { $from } на { $to }. Тренируется:
+migration-stage = Этап { $stage } из { $stages }, клавиши:
+migration-next-stage = Добавить следующий ряд
+migration-all-keys = Все клавиши введены.
+migration-surpassed = Новая раскладка быстрее с { $date }!
+migration-old-speed = Новая раскладка догонит старую на { $speed } { $unit }.
+migration-no-old-results = Тренируйте и старую раскладку, чтобы сравнить скорости.
+migration-end = Завершить переход
+
+# Speed chart
+speed-chart = Скорость по неделям, { $unit }:
+speed-chart-range = От { $min } до { $max } за недель с результатами: { $weeks }.
+
+# Improvement trend
+migration-time-left = Осталось около { $minutes } мин. практики, чтобы догнать старую раскладку при текущем темпе.
+
+# Practice settings
+practice-settings = Настройки тренировки
+prompt-len = Символов в задании из случайных символов:
+charset = Набор символов:
+charset-ascii = Латиница и символы ASCII
+charset-cyrillic = Кириллица
+charset-greek = Греческий
+symbol-classes = Случайные символы включают:
+mistakes-shown = Последних ошибок в списке:
+persist-stats = Сохранять статистику
+not-saved = Статистика не сохраняется
+
+# Pages
+page-practice = Тренировка
+page-stats = Статистика
+page-lessons = Уроки
+page-settings = Настройки
+
+# Warm-up routine
+warm-up-offer = Начните с короткой разминки: перекаты по основному ряду, чередование рук и частые триграммы. Она не меняет статистику.
+warm-up-start = Размяться
+warm-up-skip = Пропустить разминку
+warm-up-progress = Разминка, { $section }: строка { $line } из { $lines }.
+warm-up-rolls = перекаты по основному ряду
+warm-up-alternating = чередование рук
+warm-up-trigrams = частые триграммы
+
+# Words
+word-accuracy = Слов без ошибок: { $accuracy }%.
+words-fumbled = С ошибками:
+session-words-fumbled = Слова с ошибками за эту сессию:
+word-fumbled-count = { $fumbled } из { $typed } раз
+
+# Tabs
+other-tab-saves = Приложение открыто в другой вкладке, статистику сохраняет она. Занятия здесь не сохраняются.
+save-from-this-tab = Сохранять из этой вкладки
+
+# Keyboard
+show-keyboard = Клавиатура под текстом с подсказкой следующей клавиши
+keyboard-layout = Раскладка клавиатуры:
+
+# Backup reminder
+backup-reminder-days = Напоминать о резервной копии раз в столько дней, 0 — никогда:
+backup-due = Результатов без резервной копии: { $results }. Скачанная копия сохранит их, если хранилище браузера очистят.
+backup-download = Скачать резервную копию
+backup-later = Позже
+
+# Rhythm playback
+rhythm-play = Проиграть ритм занятия
+rhythm-stop = Остановить
+rhythm-hint = Каждое нажатие за занятие звучит нотой в том темпе, в каком было набрано, ошибки звучат фальшиво.
+
+# Transitions
+weights-ngrams = Переходы, в которых вы ошибаетесь или медлите, вставляются в случайные символы: { $ngrams }
+
+# Long texts
+book = Длинный текст
+book-open = Откройте текстовый файл или EPUB, чтобы перепечатывать его несколько занятий:
+book-open-failed = Не удалось открыть текст: { $error }
+book-save-failed = Не удалось сохранить текст: { $error }
+book-progress = { $title }: { $chapter }, { $chapter_percent }% главы, { $percent }% текста
+book-finished = Весь текст набран.
+book-stop = Заниматься другими строками
+book-resume = Набирать текст
+book-chapter = Глава
+book-typed = Набрано
+book-results = Результатов
+book-go = Перейти
+book-bookmark-note = Заметка к закладке
+book-bookmark-add = Добавить закладку здесь
+book-bookmark-remove = Удалить
+
+# Timed test
+timed = Тест на время:
+timed-seconds = { $seconds } с
+timed-progress = Осталось { $remaining }, текст будет появляться, пока не выйдет время.
+timed-stop = Остановить тест
+timed-result = Тест на { $seconds } с: { $speed }, точность { $accuracy }%, символов: { $chars }, ошибок: { $mistakes }.
+timed-again = Ещё раз
+timed-close = Закрыть
+timed-left = Только левая рука:
+timed-right = Только правая рука:
+timed-best = (рекорд { $speed })
+
+# Course
+curriculum-start = Осваивать клавиши по одной
+curriculum-thresholds = Новая клавиша добавляется, когда последние строки достигают
+curriculum-keys = Освоенные клавиши:
+curriculum-next = Следующая: { $key }, строк на этих клавишах: { $prompts } из { $needed }.
+curriculum-time-left = При нынешних успехах осталось около { $minutes } мин практики.
+curriculum-all-keys = Все клавиши освоены.
+curriculum-unlocked = Новая клавиша: { $key }
+curriculum-pause = Тренировать все клавиши
+curriculum-resume = Вернуться к курсу
+curriculum-reset = Начать заново
+
+# Text normalization
+normalize-quotes = Прямые кавычки вместо фигурных
+normalize-dashes = Дефисы и точки вместо тире и многоточий
+normalize-untypeable = Символы, которых нет на клавиатуре:
+untypeable-keep = оставлять
+untypeable-strip = убирать
+untypeable-transliterate = писать латиницей
+normalize-replaced = Заменено: { $substitutions }
+
+# Pasted long texts
+book-pasted = Вставленный текст
+book-this-text = Печатать несколько занятий
+book-passage-len = Символов в отрывке, до конца предложения:
+
+# Hesitations
+hesitations = Заминки
+hesitations-percentile = Нажатия медленнее такой доли нажатий занятия, %:
+hesitations-threshold = дольше { $ms } мс
+
+# Stats export
+export-stats = Скачать статистику
+export-stats-hint = Оценки ошибок, результаты и журнал нажатий в файле, который можно объединить на другом устройстве или здесь после очистки данных сайта.
+import-stats-file = Объединить со скачанным файлом статистики:
+
+# CapsLock
+caps-lock-guard = Не принимать нажатия, пока включён CapsLock
+caps-lock-on = Включён CapsLock, буквы набираются не в том регистре.
+caps-lock-ignored = Включён CapsLock: нажатия не принимаются, пока он не выключен.
+
+# Mode hints
+hint-dismiss = Понятно
+hints-reset = Снова показать подсказки режимов
+hint-warm-up = Разминка: несколько строк с перекатами по основному ряду, чередованием рук и частыми триграммами. Ошибки здесь не меняют статистику. Нажимайте Enter после каждой строки.
+hint-drill = Повторение: предложение возвращается, пока его не наберут без ошибок нужное число раз подряд. Любая ошибка, даже исправленная, сбрасывает счёт. Нажимайте Enter после каждой попытки.
+hint-exam = Экзамен: набирайте отрывок, пока не выйдет время. Число исправлений может быть ограничено, строки нельзя пропускать и ставить на паузу. Чистая скорость вычитает слово в минуту за каждую неисправленную ошибку.
+hint-data-entry = Ввод данных: набирайте каждое числовое поле и подтверждайте его Enter. Скорость считается в нажатиях в час вместе с исправлениями и Enter, поля с ошибками вычитаются из чистой скорости.
+hint-timed = Тест на время: отсчёт начинается с первого нажатия, текст продолжается, пока не выйдет время. Пауза и пропуск отключены. Результат — скорость и точность за это время.
+hint-book = Длинный текст: книга набирается по отрывку, место сохраняется между сессиями. Нажимайте Enter после каждого отрывка; результаты отмечаются главой.
+hint-curriculum = Курс: задания состоят только из уже изученных клавиш. Новая клавиша добавляется, когда последние задания достаточно точны и быстры, пороги — под управлением курса.
+hint-discovery = Поиск клавиш: нажмите показанный символ. Если вы медлите, его клавиша подсвечивается на клавиатуре; по тому, как часто это нужно, видно, насколько освоена раскладка.
+hint-code = Фрагменты кода: строки сохраняют переносы и отступы. Нажимайте Enter в конце строки, отмеченном ↵, и Tab, чтобы набрать отступ следующей.
+
+# Accuracy regression
+regression = Точность упала с { $before }% до { $after }% начиная с { $date } — сильнее, чем обычные колебания.
+regression-compare = Сравнить до и после
+
+# Unreadable storage
+unreadable = Часть сохранённых данных не удалось прочитать. Они сохранены без изменений под тем же именем с окончанием «{ $suffix }», а эти данные начинаются заново:
+
+# Error heatmap
+heatmap = Тепловая карта ошибок
+heatmap-hint = Клавиши закрашены по оценке ошибок с учётом обоих символов клавиши, относительно самой слабой клавиши.
+heatmap-key = { $key }: оценка ошибок { $score }
+shift-left = Левый Shift
+shift-right = Правый Shift
+
+# Progress report
+export-report = Скачать отчёт
+export-report-hint = Страница с графиками и таблицами вашего прогресса, чтобы сохранить или распечатать; открывается в любом браузере без приложения.
+report-title = Прогресс в наборе
+report-generated = Создан { $date }
+report-summary = Итоги
+report-results = Набрано строк
+report-practice-time = Время набора
+report-hours = { $hours } ч
+report-personal-best = Личный рекорд
+report-speed = Скорость по неделям, { $unit }
+report-range = От { $min } до { $max }.
+report-week = Неделя
+report-daily-accuracy = Точность по дням
+report-day = День
+report-weakest = Символы с наибольшим счётом ошибок
+report-char = Символ
+report-error-score = Счёт ошибок
+
+# Code snippets
+code-any = любой язык
+code-rust = Rust
+code-python = Python
+code-javascript = JavaScript
+
+# Idle suggestions
+suggestion-slower-key = Нажатия { $key } на этой неделе стали медленнее на { $percent }%. Потренировать?
+suggestion-timed-test = Вы не проходили тест на время { $days } дн.
+suggestion-first-timed-test = Вы ещё не пробовали тест на время.
+suggestion-weakest-key = У { $key } самый высокий счёт ошибок. Потренировать?
+suggestion-drill = Тренировать
+suggestion-take-timed-test = Пройти тест на минуту
+
+# Numeric keypad
+numpad = Цифровой блок
+numpad-hint = Клавиши, набранные на цифровом блоке. Цвет показывает долю ошибок относительно самой слабой клавиши. В тренировке чисел и вводе данных они не меняют оценки ошибок.
+numpad-key = { $key }, { $finger }: ошибок { $mistakes } из { $presses } нажатий
+numpad-col-key = Клавиша
+numpad-col-finger = Палец
+numpad-col-presses = Нажатий
+numpad-col-accuracy = Точность, %
+numpad-col-latency = Средняя задержка, мс
+
+# Session goals
+goal-start = Начать сессию:
+goal-minutes = { $minutes } минут
+goal-prompts = { $prompts } заданий
+goal-course-stage = один этап курса
+goal-minutes-progress = { $elapsed } из { $minutes } минут
+goal-prompts-progress = { $done } из { $prompts } заданий
+goal-stage-progress = Пока курс не добавит { $key }
+goal-stop = Закончить сессию
+goal-reached = Цель сессии достигнута за { $duration }
+goal-stopped = Сессия закончена через { $duration }
+
+# Number drills
+numbers-drill = Тренировка чисел с датами, суммами и телефонами:
+numbers-row = цифровой ряд
+numbers-numpad = цифровой блок
+numbers-row-stats = Цифровой ряд в тренировке чисел
+numbers-row-stats-hint = Клавиши тренировки чисел, набранные на цифровом ряду, учитываются отдельно от оценок ошибок.
@@ -0,0 +1,120 @@
+//! Looping background sound to practice with. The soundscapes are noise
+//! shaped by filters, synthesized when first played, so there are no
+//! audio files to download and nothing is set up until one is picked.
+
+use serde::{Deserialize, Serialize};
+use web_sys::{AudioBufferSourceNode, AudioContext, AudioContextState, BiquadFilterType, GainNode};
+
+/// Length of the looped noise, long enough for the loop not to be heard
+const LOOP_SECONDS: f32 = 4.0;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Soundscape {
+    Rain,
+    Cafe,
+    WhiteNoise,
+}
+
+impl Soundscape {
+    pub const ALL: [Soundscape; 3] = [Soundscape::Rain, Soundscape::Cafe, Soundscape::WhiteNoise];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Soundscape::Rain => "ambient-rain",
+            Soundscape::Cafe => "ambient-cafe",
+            Soundscape::WhiteNoise => "ambient-white-noise",
+        }
+    }
+
+    /// Brown noise for the low murmur of a café, white noise otherwise
+    fn brown(self) -> bool {
+        matches!(self, Soundscape::Cafe)
+    }
+
+    /// Cutoff of the low-pass filter in Hz
+    fn cutoff(self) -> Option<f32> {
+        match self {
+            Soundscape::Rain => Some(2500.0),
+            Soundscape::Cafe => Some(600.0),
+            Soundscape::WhiteNoise => None,
+        }
+    }
+}
+
+/// A playing soundscape, stopped when dropped
+pub struct Ambient {
+    context: AudioContext,
+    source: AudioBufferSourceNode,
+    gain: GainNode,
+}
+
+fn js_error(e: wasm_bindgen::JsValue) -> String {
+    format!("{e:?}")
+}
+
+impl Ambient {
+    /// Starts playing, or waits for the first key press if the browser
+    /// does not allow sound before the user interacts with the page
+    pub fn start(soundscape: Soundscape, volume: f64) -> Result<Self, String> {
+        let context = AudioContext::new().map_err(js_error)?;
+        let rate = context.sample_rate();
+        let mut samples: Vec<f32> = (0..(rate * LOOP_SECONDS) as usize)
+            .map(|_| rand::random::<f32>() * 2.0 - 1.0)
+            .collect();
+        if soundscape.brown() {
+            let mut level = 0.0;
+            for sample in &mut samples {
+                level = (level + 0.02 * *sample) / 1.02;
+                *sample = level * 3.5;
+            }
+        }
+        let buffer = context
+            .create_buffer(1, samples.len() as u32, rate)
+            .map_err(js_error)?;
+        buffer.copy_to_channel(&samples, 0).map_err(js_error)?;
+
+        let source = context.create_buffer_source().map_err(js_error)?;
+        source.set_buffer(Some(&buffer));
+        source.set_loop(true);
+        let gain = context.create_gain().map_err(js_error)?;
+        gain.gain().set_value(volume as f32);
+        match soundscape.cutoff() {
+            Some(cutoff) => {
+                let filter = context.create_biquad_filter().map_err(js_error)?;
+                filter.set_type(BiquadFilterType::Lowpass);
+                filter.frequency().set_value(cutoff);
+                source.connect_with_audio_node(&filter).map_err(js_error)?;
+                filter.connect_with_audio_node(&gain).map_err(js_error)?;
+            }
+            None => {
+                source.connect_with_audio_node(&gain).map_err(js_error)?;
+            }
+        }
+        gain.connect_with_audio_node(&context.destination())
+            .map_err(js_error)?;
+        source.start().map_err(js_error)?;
+        Ok(Ambient {
+            context,
+            source,
+            gain,
+        })
+    }
+
+    pub fn set_volume(&self, volume: f64) {
+        self.gain.gain().set_value(volume as f32);
+    }
+
+    /// Starts the sound held back until the user interacted with the page
+    pub fn resume(&self) {
+        if self.context.state() == AudioContextState::Suspended {
+            let _ = self.context.resume();
+        }
+    }
+}
+
+impl Drop for Ambient {
+    fn drop(&mut self) {
+        let _ = self.source.stop();
+        let _ = self.context.close();
+    }
+}
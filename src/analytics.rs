@@ -0,0 +1,334 @@
+//! Aggregates over the keystrokes typed since the page was opened,
+//! and regressions of accuracy across the days of the history.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use typing_core::DAY_MS;
+
+use crate::{
+    charset::is_shifted,
+    fingers::{Finger, Hand},
+    history::History,
+    settings::Settings,
+};
+
+/// Days of practice needed on each side of a changepoint
+const MIN_CHANGEPOINT_DAYS: usize = 3;
+/// Only a regression within the latest days of practice is an alarm
+const MAX_REGRESSION_DAYS: usize = 14;
+/// Two-proportion z score a drop must exceed, about 1% one-sided
+const Z_CRITICAL: f64 = 2.33;
+
+/// A typed character of a prompt
+pub struct Keystroke {
+    pub expected: char,
+    pub typed: char,
+    /// Milliseconds since the previous keystroke of the same prompt
+    pub latency: Option<f64>,
+    /// Expected character of the previous keystroke of the same prompt
+    pub previous: Option<char>,
+}
+
+#[derive(Default)]
+pub struct SessionAnalytics {
+    pub keystrokes: usize,
+    pub backspaces: usize,
+    /// Mean latency of bigrams typed by one finger, and of all other bigrams
+    pub same_finger_latency: Option<f64>,
+    pub other_latency: Option<f64>,
+    /// (mistakes, total) per hand
+    pub left_hand: (usize, usize),
+    pub right_hand: (usize, usize),
+    pub per_finger: BTreeMap<Finger, KeyGroupStats>,
+    pub per_layer_transition: BTreeMap<LayerTransition, KeyGroupStats>,
+    pub per_class: BTreeMap<SymbolClass, KeyGroupStats>,
+    /// By expected character
+    pub per_key: BTreeMap<char, KeyGroupStats>,
+}
+
+/// How a keystroke relates to keyboard layers (or home-row modifiers)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LayerTransition {
+    Base,
+    /// The character needs a layer, the previous one did not
+    EnterLayer,
+    /// The character and the previous one both need a layer
+    WithinLayer,
+}
+
+impl LayerTransition {
+    pub fn message_key(self) -> &'static str {
+        match self {
+            LayerTransition::Base => "layer-base",
+            LayerTransition::EnterLayer => "layer-enter",
+            LayerTransition::WithinLayer => "layer-within",
+        }
+    }
+}
+
+/// Kind of character, coarser than single characters for choosing what to practice
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum SymbolClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    Bracket,
+    Operator,
+    Whitespace,
+    /// Punctuation and anything else
+    Other,
+}
+
+impl SymbolClass {
+    pub const ALL: [SymbolClass; 7] = [
+        SymbolClass::Lowercase,
+        SymbolClass::Uppercase,
+        SymbolClass::Digit,
+        SymbolClass::Bracket,
+        SymbolClass::Operator,
+        SymbolClass::Whitespace,
+        SymbolClass::Other,
+    ];
+
+    pub fn of(c: char) -> Self {
+        match c {
+            c if c.is_lowercase() => SymbolClass::Lowercase,
+            c if c.is_uppercase() => SymbolClass::Uppercase,
+            c if c.is_ascii_digit() => SymbolClass::Digit,
+            '(' | ')' | '[' | ']' | '{' | '}' | '<' | '>' => SymbolClass::Bracket,
+            '+' | '-' | '*' | '/' | '%' | '=' | '!' | '&' | '|' | '^' | '~' => {
+                SymbolClass::Operator
+            }
+            c if c.is_whitespace() => SymbolClass::Whitespace,
+            _ => SymbolClass::Other,
+        }
+    }
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            SymbolClass::Lowercase => "class-lowercase",
+            SymbolClass::Uppercase => "class-uppercase",
+            SymbolClass::Digit => "class-digit",
+            SymbolClass::Bracket => "class-bracket",
+            SymbolClass::Operator => "class-operator",
+            SymbolClass::Whitespace => "class-whitespace",
+            SymbolClass::Other => "class-other",
+        }
+    }
+}
+
+/// Whether typing `c` needs a layer key or a home-row modifier to be held
+fn on_layer(c: char, settings: &Settings) -> bool {
+    settings.layer_chars.contains(&c) || (settings.home_row_shift && is_shifted(c))
+}
+
+/// Accuracy and speed of a group of keystrokes
+#[derive(Default)]
+pub struct KeyGroupStats {
+    pub mistakes: usize,
+    pub total: usize,
+    latency_sum: f64,
+    latency_count: usize,
+}
+
+impl KeyGroupStats {
+    pub fn add(&mut self, mistake: usize, latency: Option<f64>) {
+        self.mistakes += mistake;
+        self.total += 1;
+        if let Some(latency) = latency {
+            self.latency_sum += latency;
+            self.latency_count += 1;
+        }
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        1.0 - self.mistakes as f64 / self.total.max(1) as f64
+    }
+
+    pub fn mean_latency(&self) -> Option<f64> {
+        (self.latency_count > 0).then(|| self.latency_sum / self.latency_count as f64)
+    }
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+impl SessionAnalytics {
+    pub fn compute(log: &[Keystroke], backspaces: usize, settings: &Settings) -> Self {
+        let (finger_map, layout) = (settings.finger_map, settings.typed_layout());
+        let mut same_finger = vec![];
+        let mut other = vec![];
+        let mut analytics = SessionAnalytics {
+            keystrokes: log.len() + backspaces,
+            backspaces,
+            ..Default::default()
+        };
+        for k in log {
+            let finger = finger_map.finger_for(layout, k.expected);
+            let mistake = usize::from(k.expected != k.typed);
+            if let Some(finger) = finger {
+                let hand = match finger.hand {
+                    Hand::Left => &mut analytics.left_hand,
+                    Hand::Right => &mut analytics.right_hand,
+                };
+                hand.0 += mistake;
+                hand.1 += 1;
+                analytics
+                    .per_finger
+                    .entry(finger)
+                    .or_default()
+                    .add(mistake, k.latency);
+            }
+            let transition = match (on_layer(k.expected, settings), k.previous) {
+                (false, _) => LayerTransition::Base,
+                (true, Some(prev)) if on_layer(prev, settings) => LayerTransition::WithinLayer,
+                (true, _) => LayerTransition::EnterLayer,
+            };
+            analytics
+                .per_layer_transition
+                .entry(transition)
+                .or_default()
+                .add(mistake, k.latency);
+            analytics
+                .per_key
+                .entry(k.expected)
+                .or_default()
+                .add(mistake, k.latency);
+            analytics
+                .per_class
+                .entry(SymbolClass::of(k.expected))
+                .or_default()
+                .add(mistake, k.latency);
+            if let (Some(latency), Some(prev)) = (k.latency, k.previous) {
+                if prev != k.expected
+                    && finger.is_some()
+                    && finger_map.finger_for(layout, prev) == finger
+                {
+                    same_finger.push(latency);
+                } else {
+                    other.push(latency);
+                }
+            }
+        }
+        analytics.same_finger_latency = mean(&same_finger);
+        analytics.other_latency = mean(&other);
+        analytics
+    }
+
+    pub fn backspace_rate(&self) -> f64 {
+        self.backspaces as f64 / self.keystrokes.max(1) as f64
+    }
+}
+
+/// Keystrokes and mistakes of a day of practice
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DailyAccuracy {
+    /// Unix time (ms) the day starts at, UTC
+    pub day: f64,
+    pub keystrokes: usize,
+    pub mistakes: usize,
+}
+
+/// Days with results, oldest first
+pub fn daily_accuracy(history: &History) -> Vec<DailyAccuracy> {
+    let mut days: BTreeMap<i64, (usize, usize)> = BTreeMap::new();
+    for r in &history.results {
+        let day = days
+            .entry((r.meta.created_at / DAY_MS).floor() as i64)
+            .or_default();
+        day.0 += r.keystrokes;
+        day.1 += r.mistakes.min(r.keystrokes);
+    }
+    days.into_iter()
+        .map(|(day, (keystrokes, mistakes))| DailyAccuracy {
+            day: day as f64 * DAY_MS,
+            keystrokes,
+            mistakes,
+        })
+        .collect()
+}
+
+/// Accuracy that dropped from one day on and stayed lower
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Regression {
+    /// Start of the first day of lower accuracy
+    pub since: f64,
+    /// Start of the first day the earlier accuracy is measured over
+    pub before_from: f64,
+    /// Percent
+    pub before: f64,
+    pub after: f64,
+    pub z: f64,
+}
+
+/// The changepoint where accuracy drops the most significantly, splitting the
+/// days in two, if the days after it are recent and the drop is significant
+pub fn accuracy_regression(days: &[DailyAccuracy]) -> Option<Regression> {
+    let total = |days: &[DailyAccuracy]| {
+        days.iter()
+            .fold((0, 0), |(k, m), d| (k + d.keystrokes, m + d.mistakes))
+    };
+    let first_split = MIN_CHANGEPOINT_DAYS.max(days.len().saturating_sub(MAX_REGRESSION_DAYS));
+    let last_split = days.len().checked_sub(MIN_CHANGEPOINT_DAYS)?;
+    (first_split..=last_split)
+        .filter_map(|split| {
+            let (n1, m1) = total(&days[..split]);
+            let (n2, m2) = total(&days[split..]);
+            if n1 == 0 || n2 == 0 {
+                return None;
+            }
+            let (n1, n2) = (n1 as f64, n2 as f64);
+            let p1 = 1.0 - m1 as f64 / n1;
+            let p2 = 1.0 - m2 as f64 / n2;
+            let p = 1.0 - (m1 + m2) as f64 / (n1 + n2);
+            let se = (p * (1.0 - p) * (1.0 / n1 + 1.0 / n2)).sqrt();
+            if se == 0.0 {
+                return None;
+            }
+            Some(Regression {
+                since: days[split].day,
+                before_from: days[0].day,
+                before: 100.0 * p1,
+                after: 100.0 * p2,
+                z: (p1 - p2) / se,
+            })
+        })
+        .filter(|r| r.z > Z_CRITICAL)
+        .max_by(|a, b| a.z.total_cmp(&b.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(mistakes: &[usize]) -> Vec<DailyAccuracy> {
+        mistakes
+            .iter()
+            .enumerate()
+            .map(|(i, m)| DailyAccuracy {
+                day: i as f64 * DAY_MS,
+                keystrokes: 1000,
+                mistakes: *m,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_lasting_drop_is_a_regression() {
+        let regression = accuracy_regression(&days(&[20, 25, 18, 22, 60, 55, 65])).unwrap();
+        assert_eq!(regression.since, 4.0 * DAY_MS);
+        assert_eq!(regression.before, 97.875);
+        assert_eq!(regression.after, 94.0);
+        // Noise and improvements are not
+        assert_eq!(
+            accuracy_regression(&days(&[20, 25, 18, 22, 24, 19, 23])),
+            None
+        );
+        assert_eq!(
+            accuracy_regression(&days(&[60, 55, 65, 58, 20, 25, 18])),
+            None
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Datasets fetched on demand instead of being compiled into the WASM bundle.
+//! Trunk copies the `assets` directory next to the app.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use gloo_net::http::Request;
+
+thread_local! {
+    static CACHE: RefCell<HashMap<String, String>> = Default::default();
+}
+
+/// Progress of fetching an asset
+#[derive(Clone, PartialEq, Default)]
+pub enum Loading {
+    #[default]
+    Idle,
+    InProgress,
+    Failed(String),
+}
+
+/// Fetches `assets/{name}` once, later calls are served from memory
+pub async fn fetch_text(name: &str) -> Result<String, String> {
+    if let Some(text) = CACHE.with(|c| c.borrow().get(name).cloned()) {
+        return Ok(text);
+    }
+    let response = Request::get(&format!("assets/{name}"))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.ok() {
+        return Err(format!(
+            "{name}: {} {}",
+            response.status(),
+            response.status_text()
+        ));
+    }
+    let text = response.text().await.map_err(|e| e.to_string())?;
+    CACHE.with(|c| c.borrow_mut().insert(name.to_string(), text.clone()));
+    Ok(text)
+}
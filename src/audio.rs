@@ -0,0 +1,147 @@
+//! Sounds played while typing: one on every key press and another on a
+//! mistake, in the chosen theme. The sounds are synthesized when the
+//! context is set up, so there are no audio files to download.
+
+use std::f32::consts::TAU;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+use web_sys::{AudioBuffer, AudioContext, AudioContextOptions, AudioContextState};
+
+pub fn js_error(e: JsValue) -> String {
+    format!("{e:?}")
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SoundTheme {
+    #[default]
+    SoftClick,
+    Typewriter,
+}
+
+impl SoundTheme {
+    pub const ALL: [SoundTheme; 2] = [SoundTheme::SoftClick, SoundTheme::Typewriter];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            SoundTheme::SoftClick => "sound-theme-soft-click",
+            SoundTheme::Typewriter => "sound-theme-typewriter",
+        }
+    }
+
+    /// Samples of a key press at `rate` Hz
+    fn key(self, rate: f32) -> Vec<f32> {
+        match self {
+            // A short burst of noise
+            SoundTheme::SoftClick => envelope(0.015, rate, 2, |_| noise()),
+            // A sharp clack over the thump of the type bar
+            SoundTheme::Typewriter => envelope(0.06, rate, 4, |t| {
+                0.6 * noise() * (-t * 120.0).exp() + 0.5 * (TAU * 140.0 * t).sin()
+            }),
+        }
+    }
+
+    /// Samples of a mistake at `rate` Hz
+    fn error(self, rate: f32) -> Vec<f32> {
+        match self {
+            // A low hum
+            SoundTheme::SoftClick => envelope(0.15, rate, 2, |t| 0.4 * (TAU * 220.0 * t).sin()),
+            // A dull buzz
+            SoundTheme::Typewriter => envelope(0.12, rate, 1, |t| {
+                let phase = (110.0 * t).fract();
+                0.3 * (2.0 * phase - 1.0)
+            }),
+        }
+    }
+}
+
+fn noise() -> f32 {
+    rand::random::<f32>() * 2.0 - 1.0
+}
+
+/// `seconds` of `wave`, given the time in seconds, fading out
+/// with the fade raised to `power`
+fn envelope(seconds: f32, rate: f32, power: i32, wave: impl Fn(f32) -> f32) -> Vec<f32> {
+    let len = (rate * seconds) as usize;
+    (0..len)
+        .map(|i| {
+            let fade = 1.0 - i as f32 / len as f32;
+            wave(i as f32 / rate) * fade.powi(power)
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Sound {
+    Key,
+    Error,
+}
+
+/// An audio context with the sounds of a theme, closed when dropped
+pub struct KeySound {
+    context: AudioContext,
+    key: AudioBuffer,
+    error: AudioBuffer,
+}
+
+impl KeySound {
+    pub fn new(theme: SoundTheme) -> Result<Self, String> {
+        let options = AudioContextOptions::new();
+        // Not in the bindings yet, asks for the smallest output buffer
+        js_sys::Reflect::set(&options, &"latencyHint".into(), &"interactive".into())
+            .map_err(js_error)?;
+        let context = AudioContext::new_with_context_options(&options).map_err(js_error)?;
+        let rate = context.sample_rate();
+        let buffer = |samples: Vec<f32>| {
+            let buffer = context
+                .create_buffer(1, samples.len() as u32, rate)
+                .map_err(js_error)?;
+            buffer.copy_to_channel(&samples, 0).map_err(js_error)?;
+            Ok::<_, String>(buffer)
+        };
+        let key = buffer(theme.key(rate))?;
+        let error = buffer(theme.error(rate))?;
+        Ok(KeySound {
+            context,
+            key,
+            error,
+        })
+    }
+
+    /// Plays `sound` `delay_ms` from now
+    pub fn play(&self, sound: Sound, delay_ms: f64) {
+        if self.context.state() == AudioContextState::Suspended {
+            let _ = self.context.resume();
+        }
+        let buffer = match sound {
+            Sound::Key => &self.key,
+            Sound::Error => &self.error,
+        };
+        let result = self.context.create_buffer_source().and_then(|source| {
+            source.set_buffer(Some(buffer));
+            source.connect_with_audio_node(&self.context.destination())?;
+            source.start_with_when(self.context.current_time() + delay_ms / 1000.0)
+        });
+        if let Err(e) = result {
+            tracing::warn!("Could not play a sound: {e:?}");
+        }
+    }
+
+    /// Delay in ms between playing and hearing a sound as the browser reports it,
+    /// `None` where the browser does not know
+    pub fn reported_latency(&self) -> Option<f64> {
+        let seconds = |name: &str| {
+            js_sys::Reflect::get(&self.context, &name.into())
+                .ok()
+                .and_then(|v| v.as_f64())
+        };
+        let output = seconds("outputLatency")?;
+        Some((seconds("baseLatency").unwrap_or(0.0) + output) * 1000.0)
+    }
+}
+
+impl Drop for KeySound {
+    fn drop(&mut self) {
+        let _ = self.context.close();
+    }
+}
@@ -0,0 +1,128 @@
+//! Auxiliary devices bound to app actions: gamepad buttons (foot pedals
+//! usually show up as gamepads) and the extra mouse buttons,
+//! so that navigation does not take the hands off the home row.
+
+use std::collections::HashSet;
+
+use gloo_timers::callback::Interval;
+use gloo_utils::window;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::Gamepad;
+use yew::Callback;
+
+use crate::i18n::t_args;
+
+/// Gamepads have no button events, their state is polled this often
+const POLL_INTERVAL_MS: u32 = 50;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AuxAction {
+    NextPrompt,
+    Pause,
+    ToggleZen,
+    ToggleFullscreen,
+}
+
+impl AuxAction {
+    pub const ALL: [AuxAction; 4] = [
+        AuxAction::NextPrompt,
+        AuxAction::Pause,
+        AuxAction::ToggleZen,
+        AuxAction::ToggleFullscreen,
+    ];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            AuxAction::NextPrompt => "aux-next-prompt",
+            AuxAction::Pause => "aux-pause",
+            AuxAction::ToggleZen => "aux-toggle-zen",
+            AuxAction::ToggleFullscreen => "aux-toggle-fullscreen",
+        }
+    }
+}
+
+/// A button of any connected gamepad or a mouse button, by index
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum AuxButton {
+    Gamepad(u32),
+    Mouse(i16),
+}
+
+impl AuxButton {
+    /// The primary and the context menu mouse buttons are never bound
+    pub fn from_mouse(button: i16) -> Option<Self> {
+        (button != 0 && button != 2).then_some(AuxButton::Mouse(button))
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            AuxButton::Gamepad(i) => t_args("aux-gamepad-button", &[("index", &i)]),
+            AuxButton::Mouse(i) => t_args("aux-mouse-button", &[("index", &i)]),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct AuxBinding {
+    pub button: AuxButton,
+    pub action: AuxAction,
+}
+
+/// Action bound to `button`
+pub fn action_for(bindings: &[AuxBinding], button: AuxButton) -> Option<AuxAction> {
+    bindings
+        .iter()
+        .find(|b| b.button == button)
+        .map(|b| b.action)
+}
+
+/// Binds `button` to `action`, replacing whatever it was bound to
+pub fn bind(bindings: &mut Vec<AuxBinding>, button: AuxButton, action: AuxAction) {
+    bindings.retain(|b| b.button != button);
+    bindings.push(AuxBinding { button, action });
+}
+
+/// Calls `on_press` on every gamepad button press.
+/// Polling starts with the first connected gamepad, not on page load.
+pub fn watch_gamepads(on_press: Callback<AuxButton>) {
+    let mut polling = false;
+    let on_connected: Closure<dyn FnMut()> = Closure::new(move || {
+        if std::mem::replace(&mut polling, true) {
+            return;
+        }
+        let on_press = on_press.clone();
+        let mut held = HashSet::new();
+        Interval::new(POLL_INTERVAL_MS, move || poll(&mut held, &on_press)).forget();
+    });
+    window()
+        .add_event_listener_with_callback(
+            "gamepadconnected",
+            on_connected.into_js_value().unchecked_ref(),
+        )
+        .unwrap();
+}
+
+/// Reports buttons that went down since the previous poll,
+/// `held` are the (gamepad, button) pairs that were down then
+fn poll(held: &mut HashSet<(u32, u32)>, on_press: &Callback<AuxButton>) {
+    let Ok(gamepads) = window().navigator().get_gamepads() else {
+        return;
+    };
+    let mut now_held = HashSet::new();
+    // Disconnected slots are null
+    for gamepad in gamepads.iter().filter_map(|g| g.dyn_into::<Gamepad>().ok()) {
+        for (i, button) in gamepad.buttons().iter().enumerate() {
+            let pressed = button
+                .dyn_into::<web_sys::GamepadButton>()
+                .is_ok_and(|b| b.pressed());
+            if pressed {
+                now_held.insert((gamepad.index(), i as u32));
+            }
+        }
+    }
+    for &(_gamepad, button) in now_held.difference(held) {
+        on_press.emit(AuxButton::Gamepad(button));
+    }
+    *held = now_held;
+}
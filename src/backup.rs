@@ -0,0 +1,304 @@
+//! Stats files moved between devices, merged into local data
+//! field by field rather than overwriting it. Downloading them
+//! is suggested from time to time while there is new data.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use typing_core::{errors::TypingErrors, DAY_MS};
+
+use crate::{events::EventLog, history::History, settings::Settings};
+
+#[derive(Serialize, Deserialize)]
+pub struct StatsFile {
+    pub errors: TypingErrors,
+    #[serde(default)]
+    pub settings: Option<Settings>,
+    #[serde(default)]
+    pub history: History,
+    /// Files from versions with the event log carry it
+    #[serde(default)]
+    pub events: Option<EventLog>,
+}
+
+/// Name of downloaded [`StatsFile`]s
+pub const STATS_FILE_NAME: &str = "typing-tutor-stats.json";
+/// Kept out of the stats, a backup opened on another device says nothing about this one
+const LAST_EXPORT_STORAGE_KEY: &str = "last_export";
+/// New results that make a backup worth suggesting
+pub const BACKUP_REMINDER_RESULTS: usize = 20;
+
+/// When the stats were last downloaded and how many results there were
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct LastExport {
+    pub at: f64,
+    pub results: usize,
+}
+
+impl LastExport {
+    pub fn load() -> Option<Self> {
+        LocalStorage::get(LAST_EXPORT_STORAGE_KEY).ok()
+    }
+
+    pub fn save(self) {
+        if let Err(e) = LocalStorage::set(LAST_EXPORT_STORAGE_KEY, self) {
+            tracing::warn!("Could not save the time of the export: {e}");
+        }
+    }
+}
+
+/// Results not in any backup, if it has been `days` since the last export
+/// (or the first result) and there are enough of them to suggest one
+pub fn backup_due(
+    last: Option<LastExport>,
+    history: &History,
+    days: u32,
+    now: f64,
+) -> Option<usize> {
+    let since = match last {
+        Some(last) => last.at,
+        None => history.results.first()?.meta.created_at,
+    };
+    let new = history
+        .results
+        .len()
+        .saturating_sub(last.map_or(0, |l| l.results));
+    (days > 0 && now - since >= f64::from(days) * DAY_MS && new >= BACKUP_REMINDER_RESULTS)
+        .then_some(new)
+}
+
+/// What merging a stats file would change, shown before applying it
+pub struct MergePreview {
+    /// Character, local error score, merged error score
+    pub score_changes: Vec<(char, usize, usize)>,
+    pub mistake_pairs_changed: usize,
+    pub retention_checks_added: usize,
+    /// Results not known locally
+    pub results_added: usize,
+    pub newer_settings: bool,
+    /// The file holds this device's own stats, only the newer copy is kept
+    pub same_device: bool,
+}
+
+fn sum_into<K: Eq + Hash + Clone>(target: &mut HashMap<K, usize>, other: &HashMap<K, usize>) {
+    for (k, v) in other {
+        *target.entry(k.clone()).or_default() += v;
+    }
+}
+
+/// Error deltas are summed, timestamps keep the latest value.
+/// The merged record keeps the local identity. A file of this device
+/// holds stats already counted here, the newer copy is kept instead.
+pub fn merge_errors(local: &TypingErrors, imported: &TypingErrors) -> TypingErrors {
+    if imported.meta.id == local.meta.id {
+        return if imported.meta.updated_at > local.meta.updated_at {
+            imported.clone()
+        } else {
+            local.clone()
+        };
+    }
+    let mut merged = local.clone();
+    merged.meta.updated_at = local.meta.updated_at.max(imported.meta.updated_at);
+    sum_into(&mut merged.error_score, &imported.error_score);
+    sum_into(&mut merged.error_stats, &imported.error_stats);
+    sum_into(&mut merged.shift_errors, &imported.shift_errors);
+    for (c, at) in &imported.last_practiced {
+        let last = merged.last_practiced.entry(*c).or_insert(*at);
+        *last = last.max(*at);
+    }
+    for (c, r) in &imported.retention {
+        let stats = merged.retention.entry(*c).or_default();
+        stats.correct += r.correct;
+        stats.total += r.total;
+    }
+    for (c, ms) in &imported.latency {
+        let latency = merged.latency.entry(*c).or_insert(*ms);
+        *latency = (*latency + ms) / 2.0;
+    }
+    for (ngram, n) in &imported.ngrams {
+        let stats = merged.ngrams.entry(ngram.clone()).or_default();
+        stats.score += n.score;
+        stats.latency = match (stats.latency, n.latency) {
+            (Some(a), Some(b)) => Some((a + b) / 2.0),
+            (a, b) => a.or(b),
+        };
+    }
+    merged
+}
+
+/// Merged stats and, if the file has an event log, the merged log they derive from
+pub fn merge_stats(
+    local: &TypingErrors,
+    local_events: &EventLog,
+    file: &StatsFile,
+) -> (TypingErrors, Option<EventLog>) {
+    match &file.events {
+        Some(imported) => {
+            let merged = local_events.merge(imported);
+            (merged.derive_errors(local.penalties), Some(merged))
+        }
+        None => (merge_errors(local, &file.errors), None),
+    }
+}
+
+/// Union of results by id, the most recently updated copy wins
+pub fn merge_history(local: &History, imported: &History) -> History {
+    let mut merged = local.clone();
+    for result in &imported.results {
+        match merged.get_mut(result.meta.id) {
+            Some(existing) if existing.meta.updated_at >= result.meta.updated_at => {}
+            Some(existing) => *existing = result.clone(),
+            None => merged.results.push(result.clone()),
+        }
+    }
+    merged
+        .results
+        .sort_by(|a, b| a.meta.created_at.total_cmp(&b.meta.created_at));
+    merged
+}
+
+/// Settings edited most recently win
+pub fn merge_settings(local: &Settings, imported: Option<&Settings>) -> Settings {
+    match imported {
+        Some(imported) if imported.meta.updated_at > local.meta.updated_at => imported.clone(),
+        _ => local.clone(),
+    }
+}
+
+pub fn preview(
+    local: &TypingErrors,
+    local_events: &EventLog,
+    local_settings: &Settings,
+    local_history: &History,
+    file: &StatsFile,
+) -> MergePreview {
+    let (merged, _events) = merge_stats(local, local_events, file);
+    let score_changes: BTreeMap<char, (usize, usize)> = merged
+        .error_score
+        .iter()
+        .map(|(c, new)| (*c, (local.error_score.get(c).copied().unwrap_or(0), *new)))
+        .filter(|(_c, (old, new))| old != new)
+        .collect();
+    MergePreview {
+        score_changes: score_changes
+            .into_iter()
+            .map(|(c, (old, new))| (c, old, new))
+            .collect(),
+        mistake_pairs_changed: file.errors.error_stats.values().filter(|v| **v > 0).count(),
+        retention_checks_added: file.errors.retention.values().map(|r| r.total).sum(),
+        results_added: file
+            .history
+            .results
+            .iter()
+            .filter(|r| !local_history.results.iter().any(|l| l.meta.id == r.meta.id))
+            .count(),
+        newer_settings: file
+            .settings
+            .as_ref()
+            .is_some_and(|s| s.meta.updated_at > local_settings.meta.updated_at),
+        same_device: file.errors.meta.id == local.meta.id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use typing_core::ids::RecordMeta;
+
+    use super::*;
+    use crate::history::PromptResult;
+
+    fn errors(meta: RecordMeta, score: usize) -> TypingErrors {
+        TypingErrors {
+            meta,
+            error_score: HashMap::from([('a', score)]),
+            ..Default::default()
+        }
+    }
+
+    fn result(meta: RecordMeta, note: &str) -> PromptResult {
+        PromptResult {
+            meta,
+            chars: 10,
+            words: 2,
+            keystrokes: 10,
+            millis: 2000.0,
+            mistakes: 0,
+            note: note.to_string(),
+            keyboard: None,
+            tags: Default::default(),
+            sloppy: false,
+        }
+    }
+
+    #[test]
+    fn errors_of_other_devices_are_summed() {
+        let local = errors(RecordMeta::new(1.0), 3);
+        let merged = merge_errors(&local, &errors(RecordMeta::new(2.0), 4));
+        assert_eq!(merged.error_score[&'a'], 7);
+        assert_eq!(merged.meta.id, local.meta.id);
+        assert_eq!(merged.meta.updated_at, 2.0);
+    }
+
+    #[test]
+    fn errors_of_the_same_device_keep_the_newer_copy() {
+        let local = errors(RecordMeta::new(1.0), 3);
+        let newer = errors(
+            RecordMeta {
+                updated_at: 5.0,
+                ..local.meta
+            },
+            5,
+        );
+        assert_eq!(merge_errors(&local, &newer).error_score[&'a'], 5);
+        assert_eq!(merge_errors(&newer, &local).error_score[&'a'], 5);
+    }
+
+    #[test]
+    fn history_is_a_union_where_the_newest_copy_wins() {
+        let shared = RecordMeta::new(1.0);
+        let local = History {
+            results: vec![result(shared, "old"), result(RecordMeta::new(3.0), "local")],
+        };
+        let edited = RecordMeta {
+            updated_at: 4.0,
+            ..shared
+        };
+        let imported = History {
+            results: vec![
+                result(RecordMeta::new(2.0), "imported"),
+                result(edited, "new"),
+            ],
+        };
+        let notes: Vec<_> = merge_history(&local, &imported)
+            .results
+            .into_iter()
+            .map(|r| r.note)
+            .collect();
+        assert_eq!(notes, ["new", "imported", "local"]);
+        let stale = History {
+            results: vec![result(shared, "stale")],
+        };
+        let merged = merge_history(&imported, &stale);
+        assert_eq!(merged.results.len(), 2);
+        assert_eq!(merged.results[0].note, "new");
+    }
+
+    #[test]
+    fn settings_edited_last_win() {
+        let local = Settings {
+            meta: RecordMeta::new(1.0),
+            persist_stats: false,
+            ..Default::default()
+        };
+        let newer = Settings {
+            meta: RecordMeta::new(2.0),
+            persist_stats: true,
+            ..Default::default()
+        };
+        assert!(merge_settings(&local, Some(&newer)).persist_stats);
+        assert!(merge_settings(&newer, Some(&local)).persist_stats);
+        assert!(!merge_settings(&local, None).persist_stats);
+    }
+}
@@ -0,0 +1,177 @@
+//! Explorer of the character pairs typed so far, collected from the event log,
+//! to pick the slow or error-prone ones and drill them.
+
+use std::{collections::BTreeMap, rc::Rc};
+
+use itertools::Itertools;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{
+    events::{EventKind, EventLog},
+    i18n::{t, t_args},
+    locale,
+};
+
+/// Longer gaps are pauses rather than the time it takes to type a pair
+const MAX_LATENCY_MS: f64 = 2000.0;
+const ROWS_LISTED: usize = 50;
+/// Times a pair is repeated on a line of its drill
+const DRILL_REPEATS: usize = 6;
+
+#[derive(Default, PartialEq)]
+pub struct BigramStats {
+    pub count: usize,
+    /// Mistakes on the second character
+    pub mistakes: usize,
+    latency_sum: f64,
+    latency_count: usize,
+}
+
+impl BigramStats {
+    pub fn error_rate(&self) -> f64 {
+        self.mistakes as f64 / self.count.max(1) as f64
+    }
+
+    pub fn mean_latency(&self) -> Option<f64> {
+        (self.latency_count > 0).then(|| self.latency_sum / self.latency_count as f64)
+    }
+}
+
+pub type Bigrams = BTreeMap<(char, char), BigramStats>;
+
+/// Pairs of consecutive keystrokes of each prompt, a backspace breaks the pair
+pub fn collect(log: &EventLog) -> Bigrams {
+    let mut bigrams = Bigrams::new();
+    let mut previous: Option<(char, f64)> = None;
+    for event in &log.events {
+        match &event.kind {
+            EventKind::Keystroke {
+                expected, typed, ..
+            } => {
+                if let Some((prev, at)) = previous {
+                    let stats = bigrams.entry((prev, *expected)).or_default();
+                    stats.count += 1;
+                    stats.mistakes += usize::from(expected != typed);
+                    let latency = event.at - at;
+                    if latency < MAX_LATENCY_MS {
+                        stats.latency_sum += latency;
+                        stats.latency_count += 1;
+                    }
+                }
+                previous = Some((*expected, event.at));
+            }
+            EventKind::PromptStart { .. } | EventKind::Backspace | EventKind::PromptEnd { .. } => {
+                previous = None
+            }
+            EventKind::PromptExtend { .. } | EventKind::SettingsChange(_) => {}
+        }
+    }
+    bigrams
+}
+
+/// Drill lines: each pair repeated, then all of them together
+pub fn drill_sentences(pairs: &[(char, char)]) -> Vec<String> {
+    let pair = |(a, b): &(char, char)| format!("{a}{b}");
+    pairs
+        .iter()
+        .map(|p| vec![pair(p); DRILL_REPEATS].join(" "))
+        .chain((pairs.len() > 1).then(|| pairs.iter().map(pair).join(" ")))
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SortBy {
+    Latency,
+    ErrorRate,
+    Frequency,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ExplorerProps {
+    pub bigrams: Rc<Bigrams>,
+    /// Asks to drill the given sentences until they are typed cleanly
+    pub on_drill: Callback<Vec<String>>,
+}
+
+#[function_component]
+pub fn BigramExplorer(props: &ExplorerProps) -> Html {
+    let sort = use_state(|| SortBy::Latency);
+    let search = use_state(String::new);
+    let selected = use_state(Vec::<(char, char)>::new);
+
+    let oninput = {
+        let search = search.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            search.set(input.value());
+        })
+    };
+    let sort_by = |by: SortBy| {
+        let sort = sort.clone();
+        Callback::from(move |_: MouseEvent| sort.set(by))
+    };
+    let on_drill = {
+        let selected = selected.clone();
+        props
+            .on_drill
+            .reform(move |_: MouseEvent| drill_sentences(&selected))
+    };
+
+    let rows = props
+        .bigrams
+        .iter()
+        .filter(|((a, b), _stats)| format!("{a}{b}").contains(search.as_str()))
+        .sorted_by(|(_, x), (_, y)| match *sort {
+            SortBy::Latency => y
+                .mean_latency()
+                .unwrap_or(0.0)
+                .total_cmp(&x.mean_latency().unwrap_or(0.0)),
+            SortBy::ErrorRate => y.error_rate().total_cmp(&x.error_rate()),
+            SortBy::Frequency => y.count.cmp(&x.count),
+        })
+        .take(ROWS_LISTED)
+        .map(|(pair, stats)| {
+            let checked = selected.contains(pair);
+            let onchange = {
+                let (selected, pair) = (selected.clone(), *pair);
+                Callback::from(move |_: Event| {
+                    let mut pairs = (*selected).clone();
+                    match pairs.iter().position(|p| *p == pair) {
+                        Some(i) => {
+                            pairs.remove(i);
+                        }
+                        None => pairs.push(pair),
+                    }
+                    selected.set(pairs);
+                })
+            };
+            let (a, b) = pair;
+            html!(<tr>
+                <td><input type="checkbox" {checked} {onchange} /></td>
+                <td><code>{format!("{a}{b}").replace(' ', "␣")}</code></td>
+                <td>{stats.mean_latency().map(|l| locale::format_number(l, 0))}</td>
+                <td>{locale::format_number(100.0 * stats.error_rate(), 1)}{"%"}</td>
+                <td>{stats.count}</td>
+            </tr>)
+        });
+
+    html!(<details>
+        <summary>{t("bigrams")}</summary>
+        <input type="search" placeholder={t("bigrams-search")} value={(*search).clone()} {oninput} />
+        {" "}
+        <button onclick={on_drill} disabled={selected.is_empty()}>
+            {t_args("bigrams-drill", &[("count", &selected.len())])}
+        </button>
+        <table>
+            <tr>
+                <th />
+                <th>{t("bigram")}</th>
+                <th><a onclick={sort_by(SortBy::Latency)}>{t("latency-ms")}</a></th>
+                <th><a onclick={sort_by(SortBy::ErrorRate)}>{t("bigrams-error-rate")}</a></th>
+                <th><a onclick={sort_by(SortBy::Frequency)}>{t("bigrams-count")}</a></th>
+            </tr>
+            {for rows}
+        </table>
+    </details>)
+}
@@ -0,0 +1,73 @@
+//! Words left out of the prompts of word and quote sources: a built-in list
+//! of offensive words that can be turned off, and the user's own.
+
+use std::collections::HashSet;
+
+const BUILT_IN: &str = include_str!("../assets/blocked.txt");
+
+#[derive(Default)]
+pub struct Blocklist {
+    words: HashSet<String>,
+    /// Entries ending with `*`, without it
+    prefixes: Vec<String>,
+}
+
+impl Blocklist {
+    /// The built-in list if `built_in`, and the `custom` entries.
+    /// An entry ending with `*` blocks the words starting with it.
+    pub fn new(built_in: bool, custom: &[String]) -> Self {
+        let built_in = BUILT_IN
+            .lines()
+            .filter(|l| !l.starts_with('#'))
+            .filter(|_| built_in);
+        let mut blocklist = Blocklist::default();
+        for entry in built_in.chain(custom.iter().map(String::as_str)) {
+            let entry = entry.trim().to_lowercase();
+            match entry.strip_suffix('*') {
+                Some("") => {}
+                Some(prefix) => blocklist.prefixes.push(prefix.to_string()),
+                None if entry.is_empty() => {}
+                None => {
+                    blocklist.words.insert(entry);
+                }
+            }
+        }
+        blocklist
+    }
+
+    fn blocks(&self, word: &str) -> bool {
+        self.words.contains(word) || self.prefixes.iter().any(|p| word.starts_with(p.as_str()))
+    }
+
+    /// Whether no word of `text` is blocked, ignoring case
+    pub fn allows(&self, text: &str) -> bool {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .all(|word| !self.blocks(word))
+    }
+}
+
+/// Entries of the custom list as typed in the settings, separated by commas
+/// or whitespace
+pub fn parse(text: &str) -> Vec<String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_words_and_prefixes_are_blocked() {
+        let blocklist = Blocklist::new(false, &parse("Cabbage, turn*  "));
+        assert!(blocklist.allows("The cabbages are growing."));
+        assert!(!blocklist.allows("Some CABBAGE."));
+        assert!(!blocklist.allows("It turned out fine"));
+        assert!(blocklist.allows("A return"));
+        assert!(Blocklist::new(false, &[]).allows("Damn it"));
+        assert!(!Blocklist::new(true, &[]).allows("Damn it"));
+    }
+}
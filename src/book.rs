@@ -0,0 +1,237 @@
+//! Copy-typing a long text over many sessions. The text is kept with the
+//! offset typed up to, split into chapters, and can be bookmarked;
+//! results are tagged with their chapter to compare chapters.
+
+use serde::{Deserialize, Serialize};
+use typing_core::ids::RecordMeta;
+
+use crate::{codec, store::StatsStore};
+
+/// Progress, chapters and bookmarks, saved after every passage
+pub const BOOK_STORAGE_KEY: &str = "book";
+/// The text itself, saved once when the book is opened
+pub const BOOK_TEXT_STORAGE_KEY: &str = "book_text";
+pub const CHAPTER_TAG_PREFIX: &str = "chapter:";
+/// Default characters of a passage, it ends at the first sentence end after them
+pub const PASSAGE_LEN: usize = 150;
+/// or at a space after this many times as many if a sentence runs longer
+const PASSAGE_MAX_FACTOR: usize = 2;
+/// Lines longer than this are text, not a heading
+const HEADING_MAX_CHARS: usize = 80;
+/// Line starts that make a heading, compared in lower case
+const HEADING_STARTS: [&str; 5] = ["#", "chapter ", "part ", "глава ", "часть "];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    /// Byte offset into the text
+    pub start: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Bookmark {
+    /// Byte offset into the text
+    pub offset: usize,
+    pub note: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Book {
+    pub meta: RecordMeta,
+    pub title: String,
+    /// At least one, starting at the beginning of the text
+    pub chapters: Vec<Chapter>,
+    /// Byte offset typed up to
+    pub offset: usize,
+    pub bookmarks: Vec<Bookmark>,
+    /// Prompts come from the book
+    pub reading: bool,
+    #[serde(skip)]
+    text: String,
+}
+
+fn is_heading(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    line.chars().count() <= HEADING_MAX_CHARS && HEADING_STARTS.iter().any(|s| lower.starts_with(s))
+}
+
+impl Book {
+    /// Lines of `text` joined by single spaces, chapters start at headings
+    pub fn open(title: String, text: &str, now: f64) -> Result<Self, String> {
+        let mut joined = String::new();
+        let mut chapters = vec![];
+        for line in text.lines() {
+            let mut line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            let heading = is_heading(&line);
+            if heading {
+                // Markdown marks are not typed
+                line = line.trim_start_matches('#').trim_start().to_string();
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if !joined.is_empty() {
+                joined.push(' ');
+            }
+            if heading {
+                chapters.push(Chapter {
+                    title: line.clone(),
+                    start: joined.len(),
+                });
+            }
+            joined.push_str(&line);
+        }
+        if joined.is_empty() {
+            return Err("the text is empty".to_string());
+        }
+        if chapters.first().is_none_or(|c| c.start > 0) {
+            chapters.insert(
+                0,
+                Chapter {
+                    title: title.clone(),
+                    start: 0,
+                },
+            );
+        }
+        Ok(Book {
+            meta: RecordMeta::new(now),
+            title,
+            chapters,
+            offset: 0,
+            bookmarks: vec![],
+            reading: true,
+            text: joined,
+        })
+    }
+
+    pub fn load(store: &dyn StatsStore) -> Option<Self> {
+        let book: Book = codec::load(store, BOOK_STORAGE_KEY).ok()?;
+        let text: String = codec::load(store, BOOK_TEXT_STORAGE_KEY).ok()?;
+        // Progress belongs to the stored text only
+        (book.offset <= text.len()).then_some(Book { text, ..book })
+    }
+
+    pub fn save(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String> {
+        codec::save(store, BOOK_STORAGE_KEY, self, compact)
+    }
+
+    pub fn save_text(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String> {
+        codec::save(store, BOOK_TEXT_STORAGE_KEY, &self.text, compact)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.offset >= self.text.len()
+    }
+
+    /// Byte offset the passage of about `len` characters at the current offset ends at
+    fn passage_end(&self, len: usize) -> usize {
+        let rest = &self.text[self.offset..];
+        let mut last_space = None;
+        for (count, (i, c)) in rest.char_indices().enumerate() {
+            let next = rest[i + c.len_utf8()..].chars().next();
+            if count + 1 >= len
+                && matches!(c, '.' | '!' | '?' | '…')
+                && next.is_none_or(|n| n == ' ')
+            {
+                return self.offset + i + c.len_utf8();
+            }
+            if c == ' ' {
+                last_space = Some(i);
+            }
+            if count + 1 >= len * PASSAGE_MAX_FACTOR {
+                if let Some(space) = last_space {
+                    return self.offset + space;
+                }
+            }
+        }
+        self.text.len()
+    }
+
+    /// The next passage of about `len` characters to type
+    pub fn passage(&self, len: usize) -> &str {
+        &self.text[self.offset..self.passage_end(len)]
+    }
+
+    /// Moves past the passage once it is typed
+    pub fn advance(&mut self, len: usize, now: f64) {
+        let end = self.passage_end(len);
+        self.offset = end + self.text[end..].len() - self.text[end..].trim_start().len();
+        self.meta.updated_at = now;
+    }
+
+    pub fn jump(&mut self, offset: usize, now: f64) {
+        // Offsets come from chapters and bookmarks, which are at char boundaries
+        self.offset = offset.min(self.text.len());
+        self.meta.updated_at = now;
+    }
+
+    /// Share of the text typed, from 0 to 1
+    pub fn progress(&self) -> f64 {
+        self.offset as f64 / self.text.len().max(1) as f64
+    }
+
+    /// Index of the chapter the offset is in
+    pub fn chapter(&self) -> usize {
+        self.chapters
+            .iter()
+            .rposition(|c| c.start <= self.offset)
+            .unwrap_or(0)
+    }
+
+    /// Share of the chapter `index` typed, from 0 to 1
+    pub fn chapter_progress(&self, index: usize) -> f64 {
+        let start = self.chapters[index].start;
+        let end = self
+            .chapters
+            .get(index + 1)
+            .map_or(self.text.len(), |c| c.start);
+        (self.offset.clamp(start, end) - start) as f64 / (end - start).max(1) as f64
+    }
+
+    /// Tag of results typed in the current chapter, by its number
+    pub fn chapter_tag(&self) -> String {
+        format!("{CHAPTER_TAG_PREFIX}{}", self.chapter() + 1)
+    }
+
+    pub fn add_bookmark(&mut self, note: String, now: f64) {
+        self.bookmarks.push(Bookmark {
+            offset: self.offset,
+            note,
+        });
+        self.bookmarks.sort_by_key(|b| b.offset);
+        self.meta.updated_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chapters_start_at_headings() {
+        let book = Book::open(
+            "Book".to_string(),
+            "Preface.\n\nChapter 1\nIt  begins.\n# Chapter 2\nIt ends.",
+            0.0,
+        )
+        .unwrap();
+        let titles: Vec<&str> = book.chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, ["Book", "Chapter 1", "Chapter 2"]);
+        assert_eq!(
+            &book.text[book.chapters[1].start..],
+            "Chapter 1 It begins. Chapter 2 It ends."
+        );
+    }
+
+    #[test]
+    fn passages_end_at_sentences() {
+        let sentence = "Word ".repeat(40).trim_end().to_string() + ".";
+        let mut book =
+            Book::open("Book".to_string(), &format!("{sentence} {sentence}"), 0.0).unwrap();
+        assert_eq!(book.passage(PASSAGE_LEN), sentence);
+        book.advance(PASSAGE_LEN, 1.0);
+        assert_eq!(book.passage(PASSAGE_LEN), sentence);
+        book.advance(PASSAGE_LEN, 2.0);
+        assert!(book.is_finished());
+    }
+}
@@ -0,0 +1,13 @@
+//! The loading screen of `index.html`. Its script shows troubleshooting
+//! hints and a retry button if the WebAssembly is slow or fails to start,
+//! and the app takes the screen down once it has rendered.
+
+/// Id of the screen's element in `index.html`
+pub const SCREEN_ID: &str = "bootstrap";
+
+/// Removes the loading screen, which also stops its script from showing hints
+pub fn dismiss() {
+    if let Some(screen) = gloo_utils::document().get_element_by_id(SCREEN_ID) {
+        screen.remove();
+    }
+}
@@ -0,0 +1,185 @@
+//! Character inventory of text, used to check imported text
+//! against the set of characters the user practices.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+pub use typing_core::prompts::default_symbols;
+
+/// Whether the character is typed with Shift on a US keyboard
+pub fn is_shifted(c: char) -> bool {
+    c.is_uppercase() || "~!@#$%^&*()_+{}|:\"<>?".contains(c)
+}
+
+pub fn on_default_layout(c: char) -> bool {
+    c == ' ' || c.is_ascii_graphic()
+}
+
+/// Digits and punctuation typed with every character set
+const COMMON_SYMBOLS: &str = "0123456789.,:;!?-\"'()";
+
+/// Characters practiced: what random symbols prompts are made of and what
+/// the practice accepts besides the space and the extra characters
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CharSet {
+    /// Printable ASCII
+    #[default]
+    Ascii,
+    /// Russian letters
+    Cyrillic,
+    /// Monotonic Greek letters
+    Greek,
+}
+
+impl CharSet {
+    pub const ALL: [CharSet; 3] = [CharSet::Ascii, CharSet::Cyrillic, CharSet::Greek];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            CharSet::Ascii => "charset-ascii",
+            CharSet::Cyrillic => "charset-cyrillic",
+            CharSet::Greek => "charset-greek",
+        }
+    }
+
+    pub fn chars(self) -> Vec<char> {
+        let letters: Vec<char> = match self {
+            CharSet::Ascii => return default_symbols(),
+            CharSet::Cyrillic => ('а'..='я').chain('А'..='Я').chain(['ё', 'Ё']).collect(),
+            CharSet::Greek => ('α'..='ω')
+                .chain(('Α'..='Ω').filter(|c| *c != '\u{3a2}'))
+                .chain("άέήίόύώΆΈΉΊΌΎΏϊϋΐΰ".chars())
+                .collect(),
+        };
+        letters.into_iter().chain(COMMON_SYMBOLS.chars()).collect()
+    }
+}
+
+/// Tags of results start with this and name the script of their prompt
+pub const SCRIPT_TAG_PREFIX: &str = "script:";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Other,
+}
+
+impl Script {
+    pub const ALL: [Script; 4] = [
+        Script::Latin,
+        Script::Cyrillic,
+        Script::Greek,
+        Script::Other,
+    ];
+
+    fn of(c: char) -> Option<Script> {
+        if !c.is_alphabetic() {
+            return None;
+        }
+        Some(match c {
+            'a'..='z' | 'A'..='Z' | '\u{c0}'..='\u{24f}' => Script::Latin,
+            '\u{400}'..='\u{52f}' => Script::Cyrillic,
+            '\u{370}'..='\u{3ff}' | '\u{1f00}'..='\u{1fff}' => Script::Greek,
+            _ => Script::Other,
+        })
+    }
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Script::Latin => "script-latin",
+            Script::Cyrillic => "script-cyrillic",
+            Script::Greek => "script-greek",
+            Script::Other => "script-other",
+        }
+    }
+
+    /// Tag of results whose prompt is mostly in this script
+    pub fn tag(self) -> &'static str {
+        match self {
+            Script::Latin => "script:latin",
+            Script::Cyrillic => "script:cyrillic",
+            Script::Greek => "script:greek",
+            Script::Other => "script:other",
+        }
+    }
+}
+
+/// Collapses line breaks and runs of whitespace into single spaces,
+/// only spaces can be typed inside a prompt
+pub fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub struct Inventory {
+    counts: BTreeMap<char, usize>,
+}
+
+impl Inventory {
+    pub fn of(text: &str) -> Self {
+        let mut counts = BTreeMap::new();
+        for c in normalize_whitespace(text).chars() {
+            *counts.entry(c).or_default() += 1;
+        }
+        Inventory { counts }
+    }
+
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// The script most letters of the text belong to
+    pub fn script(&self) -> Option<Script> {
+        let mut per_script = BTreeMap::new();
+        for (c, n) in &self.counts {
+            if let Some(script) = Script::of(*c) {
+                *per_script.entry(script).or_insert(0) += n;
+            }
+        }
+        per_script
+            .into_iter()
+            .max_by_key(|(_s, n)| *n)
+            .map(|(s, _n)| s)
+    }
+
+    /// Characters of the text that the practice would not accept
+    pub fn missing_from(&self, expected: &HashSet<char>) -> Vec<char> {
+        self.counts
+            .keys()
+            .copied()
+            .filter(|c| !expected.contains(c))
+            .collect()
+    }
+
+    /// Characters that the default layout has no key for
+    pub fn off_layout(&self) -> Vec<char> {
+        self.counts
+            .keys()
+            .copied()
+            .filter(|c| !on_default_layout(*c))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn char_sets_are_letters_of_their_script() {
+        for (set, script) in [
+            (CharSet::Cyrillic, Script::Cyrillic),
+            (CharSet::Greek, Script::Greek),
+        ] {
+            let chars = set.chars();
+            assert_eq!(chars.iter().collect::<HashSet<_>>().len(), chars.len());
+            assert!(chars
+                .iter()
+                .all(|c| !c.is_alphabetic() || Script::of(*c) == Some(script)));
+        }
+        assert_eq!(CharSet::Cyrillic.chars().len(), 66 + COMMON_SYMBOLS.len());
+        assert_eq!(CharSet::Ascii.chars(), default_symbols());
+    }
+}
@@ -0,0 +1,124 @@
+//! Compact storage encoding: JSON deflated and base64'd, several times
+//! smaller than plain JSON. Reading accepts both formats, and either one
+//! leaves out or defaults fields like plain JSON does.
+//! A stored value that cannot be read is set aside and reported, so that
+//! saving over it does not lose it.
+
+use std::cell::RefCell;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::store::StatsStore;
+
+/// Marks values stored in the binary format, JSON never starts like this
+const BINARY_PREFIX: &str = "bin1:";
+/// Appended to the key of a stored value that could not be read
+pub const UNREADABLE_SUFFIX: &str = ".unreadable";
+const DEFLATE_LEVEL: u8 = 9;
+
+thread_local! {
+    /// Keys whose values could not be read since the page was loaded, and why
+    static UNREADABLE: RefCell<Vec<(String, String)>> = Default::default();
+}
+
+pub fn encode<T: Serialize>(value: &T) -> Result<String, String> {
+    let bytes = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+    let compressed = miniz_oxide::deflate::compress_to_vec(&bytes, DEFLATE_LEVEL);
+    Ok(format!("{BINARY_PREFIX}{}", STANDARD.encode(compressed)))
+}
+
+pub fn decode<T: DeserializeOwned>(stored: &str) -> Result<T, String> {
+    match stored.strip_prefix(BINARY_PREFIX) {
+        Some(encoded) => {
+            let compressed = STANDARD.decode(encoded).map_err(|e| e.to_string())?;
+            let bytes = miniz_oxide::inflate::decompress_to_vec(&compressed)
+                .map_err(|e| format!("{e:?}"))?;
+            serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+        }
+        None => serde_json::from_str(stored).map_err(|e| e.to_string()),
+    }
+}
+
+/// Stores `value` as JSON or, if `compact`, in the binary format
+pub fn save<T: Serialize>(
+    store: &dyn StatsStore,
+    key: &str,
+    value: &T,
+    compact: bool,
+) -> Result<(), String> {
+    let encoded = if compact {
+        encode(value)?
+    } else {
+        serde_json::to_string(value).map_err(|e| e.to_string())?
+    };
+    store.set(key, encoded)
+}
+
+/// Loads a value stored in either format. One that is stored
+/// but cannot be read is set aside, see [`set_aside`].
+pub fn load<T: DeserializeOwned>(store: &dyn StatsStore, key: &str) -> Result<T, String> {
+    let stored = store
+        .get(key)
+        .ok_or_else(|| format!("{key} is not stored"))?;
+    decode(&stored).inspect_err(|e| set_aside(store, key, stored.clone(), e))
+}
+
+/// Keeps `stored`, the value of `key` that could not be read, under the key
+/// with [`UNREADABLE_SUFFIX`] and reports it. An earlier copy is not replaced.
+pub fn set_aside(store: &dyn StatsStore, key: &str, stored: String, error: &str) {
+    tracing::error!("Stored {key} cannot be read: {error}");
+    let aside = format!("{key}{UNREADABLE_SUFFIX}");
+    if store.get(&aside).is_none() {
+        if let Err(e) = store.set(&aside, stored) {
+            tracing::error!("Could not set aside the stored {key}: {e}");
+        }
+    }
+    UNREADABLE.with(|u| u.borrow_mut().push((key.to_string(), error.to_string())));
+}
+
+/// Keys whose stored values could not be read, and why
+pub fn unreadable() -> Vec<(String, String)> {
+    UNREADABLE.with(|u| u.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::store::MemoryStore;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Before {
+        a: u32,
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct After {
+        a: u32,
+        #[serde(default)]
+        b: Vec<u32>,
+    }
+
+    #[test]
+    fn fields_added_later_are_defaulted() {
+        let encoded = encode(&Before { a: 7 }).unwrap();
+        assert!(encoded.starts_with(BINARY_PREFIX));
+        assert_eq!(decode::<After>(&encoded), Ok(After { a: 7, b: vec![] }));
+    }
+
+    #[test]
+    fn unreadable_values_are_set_aside() {
+        let store = MemoryStore::default();
+        store.set("k", "not json".to_string()).unwrap();
+        assert!(load::<Before>(&store, "k").is_err());
+        assert_eq!(
+            store.get(&format!("k{UNREADABLE_SUFFIX}")).as_deref(),
+            Some("not json")
+        );
+        assert!(unreadable().iter().any(|(key, _)| key == "k"));
+        assert!(load::<Before>(&store, "missing").is_err());
+        assert!(!unreadable().iter().any(|(key, _)| key == "missing"));
+    }
+}
@@ -0,0 +1,60 @@
+//! Side-by-side comparison of two periods of the event log, to see what
+//! changed after switching layouts or keyboards.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    analytics::{KeyGroupStats, Keystroke, SessionAnalytics},
+    events::{EventKind, EventLog},
+    metrics::{self, Metric, MetricInput},
+    session::Period,
+    settings::Settings,
+};
+
+pub struct PeriodSummary {
+    pub keystrokes: usize,
+    pub metrics: Vec<(&'static dyn Metric, Option<f64>)>,
+    pub per_key: BTreeMap<char, KeyGroupStats>,
+}
+
+/// Keystrokes logged in `period` the way the session log records them, and backspaces
+fn keystrokes(log: &EventLog, period: Period) -> (Vec<Keystroke>, usize) {
+    let mut keystrokes = vec![];
+    let mut backspaces = 0;
+    let mut previous: Option<(char, f64)> = None;
+    for event in log.events.iter().filter(|e| period.contains(e.at)) {
+        match &event.kind {
+            EventKind::PromptStart { .. } => previous = None,
+            EventKind::Keystroke {
+                expected, typed, ..
+            } => {
+                keystrokes.push(Keystroke {
+                    expected: *expected,
+                    typed: *typed,
+                    latency: previous.map(|(_c, at)| event.at - at),
+                    previous: previous.map(|(c, _at)| c),
+                });
+                previous = Some((*expected, event.at));
+            }
+            EventKind::Backspace => backspaces += 1,
+            EventKind::PromptExtend { .. }
+            | EventKind::PromptEnd { .. }
+            | EventKind::SettingsChange(_) => {}
+        }
+    }
+    (keystrokes, backspaces)
+}
+
+pub fn summarize(log: &EventLog, period: Period, settings: &Settings) -> PeriodSummary {
+    let (log, backspaces) = keystrokes(log, period);
+    let input = MetricInput {
+        log: &log,
+        backspaces,
+        settings,
+    };
+    PeriodSummary {
+        keystrokes: log.len(),
+        metrics: metrics::compute_all(&input),
+        per_key: SessionAnalytics::compute(&log, backspaces, settings).per_key,
+    }
+}
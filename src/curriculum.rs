@@ -0,0 +1,246 @@
+//! Course that unlocks keys one at a time: prompts start with the letters of
+//! the home row, and the next key is added once the recent prompts are
+//! accurate and fast enough. Progress is kept in LocalStorage.
+
+use std::collections::HashSet;
+
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    keycaps::Layout,
+    trend::Trend,
+    units::{SpeedSample, SpeedUnit},
+};
+
+const CURRICULUM_STORAGE_KEY: &str = "curriculum";
+/// Rows of [`Layout::rows`] whose letters are introduced, in order
+const LETTER_ROWS: [usize; 3] = [2, 1, 3];
+/// Prompts at the current keys the thresholds are checked over
+pub const RECENT_PROMPTS: usize = 5;
+
+/// A completed prompt at the current keys
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct LessonSample {
+    pub chars: usize,
+    pub keystrokes: usize,
+    pub mistakes: usize,
+    pub millis: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Curriculum {
+    /// Keys introduced after the home row
+    pub unlocked: usize,
+    /// Prompts are limited to the introduced keys
+    pub active: bool,
+    /// The last prompts since the latest key was introduced
+    pub recent: Vec<LessonSample>,
+}
+
+/// Keys in the order they are introduced: letters from the home row out,
+/// then punctuation, and the number of home row letters
+pub fn key_order(layout: Layout) -> (Vec<char>, usize) {
+    let rows = layout.rows();
+    let letters = LETTER_ROWS
+        .iter()
+        .map(|row| rows[*row].chars().filter(|c| c.is_alphabetic()));
+    let home = letters.clone().next().map_or(0, Iterator::count);
+    let mut order: Vec<char> = letters.flatten().collect();
+    let rest: Vec<char> = layout
+        .chars()
+        .into_iter()
+        .filter(|c| !order.contains(c))
+        .collect();
+    order.extend(rest);
+    (order, home)
+}
+
+impl Curriculum {
+    pub fn new() -> Self {
+        Curriculum {
+            unlocked: 0,
+            active: true,
+            recent: vec![],
+        }
+    }
+
+    pub fn load() -> Option<Self> {
+        LocalStorage::get(CURRICULUM_STORAGE_KEY).ok()
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = LocalStorage::set(CURRICULUM_STORAGE_KEY, self) {
+            tracing::warn!("Could not save the course progress: {e}");
+        }
+    }
+
+    pub fn delete() {
+        LocalStorage::delete(CURRICULUM_STORAGE_KEY);
+    }
+
+    /// Keys introduced so far, `None` once all of them are
+    pub fn keys(&self, layout: Layout) -> Option<Vec<char>> {
+        let (order, home) = key_order(layout);
+        let count = home + self.unlocked;
+        (count < order.len()).then(|| order[..count].to_vec())
+    }
+
+    /// The key introduced next, `None` once all of them are
+    pub fn next_key(&self, layout: Layout) -> Option<char> {
+        let (order, home) = key_order(layout);
+        order.get(home + self.unlocked).copied()
+    }
+
+    /// What the recent prompts add up to, `None` until there are enough of them
+    pub fn recent_total(&self) -> Option<LessonSample> {
+        (self.recent.len() >= RECENT_PROMPTS).then(|| LessonSample {
+            chars: self.recent.iter().map(|s| s.chars).sum(),
+            keystrokes: self.recent.iter().map(|s| s.keystrokes).sum(),
+            mistakes: self.recent.iter().map(|s| s.mistakes).sum(),
+            millis: self.recent.iter().map(|s| s.millis).sum(),
+        })
+    }
+
+    /// Minutes of practice left until the next key, the speed of the recent
+    /// prompts improving at the rate of `trend` until it reaches `min_wpm`.
+    /// `None` without recent prompts or if the speed does not improve.
+    pub fn minutes_left(&self, trend: &Trend, min_wpm: f64) -> Option<f64> {
+        let recent = self.recent.iter().copied().reduce(|a, b| LessonSample {
+            chars: a.chars + b.chars,
+            keystrokes: a.keystrokes + b.keystrokes,
+            mistakes: a.mistakes + b.mistakes,
+            millis: a.millis + b.millis,
+        })?;
+        let stage = Trend {
+            current: wpm(&recent),
+            ..*trend
+        };
+        // Enough prompts are needed however fast they are
+        let per_prompt = recent.millis / 60_000.0 / self.recent.len() as f64;
+        let prompts = RECENT_PROMPTS.saturating_sub(self.recent.len()) as f64 * per_prompt;
+        Some(stage.minutes_to(min_wpm)?.max(prompts))
+    }
+
+    /// Records a completed prompt and introduces the next key if the recent
+    /// prompts reach `min_accuracy` percent and `min_wpm`. Returns the new key.
+    pub fn record(
+        &mut self,
+        sample: LessonSample,
+        layout: Layout,
+        min_accuracy: f64,
+        min_wpm: f64,
+    ) -> Option<char> {
+        self.recent.push(sample);
+        if self.recent.len() > RECENT_PROMPTS {
+            self.recent.remove(0);
+        }
+        let total = self.recent_total()?;
+        let passed = accuracy(&total) >= min_accuracy && wpm(&total) >= min_wpm;
+        let next = self.next_key(layout).filter(|_| passed)?;
+        self.unlocked += 1;
+        self.recent.clear();
+        Some(next)
+    }
+}
+
+pub fn accuracy(sample: &LessonSample) -> f64 {
+    crate::history::accuracy(sample.keystrokes, sample.mistakes)
+}
+
+pub fn wpm(sample: &LessonSample) -> f64 {
+    SpeedUnit::Wpm.rate(&SpeedSample {
+        chars: sample.chars,
+        words: 0,
+        keystrokes: sample.keystrokes,
+        millis: sample.millis,
+    })
+}
+
+/// Characters prompts are made of: `chars` limited to the keys of the course,
+/// with their shifted letters and the space
+pub fn practiced_chars(
+    curriculum: Option<&Curriculum>,
+    layout: Layout,
+    chars: HashSet<char>,
+) -> HashSet<char> {
+    let Some(keys) = curriculum.filter(|c| c.active).and_then(|c| c.keys(layout)) else {
+        return chars;
+    };
+    let keys: HashSet<char> = keys
+        .into_iter()
+        .flat_map(|c| [c, c.to_ascii_uppercase()])
+        .chain([' '])
+        .collect();
+    let limited: HashSet<char> = chars.intersection(&keys).copied().collect();
+    if limited.is_empty() {
+        chars
+    } else {
+        limited
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSING: LessonSample = LessonSample {
+        chars: 100,
+        keystrokes: 100,
+        mistakes: 1,
+        millis: 30_000.0,
+    };
+
+    #[test]
+    fn starts_with_the_home_row() {
+        let (order, home) = key_order(Layout::Qwerty);
+        assert_eq!(order[..home].iter().collect::<String>(), "asdfghjkl");
+        assert_eq!(order[home], 'q');
+        let curriculum = Curriculum::new();
+        assert_eq!(curriculum.keys(Layout::Qwerty).unwrap().len(), home);
+        assert_eq!(curriculum.next_key(Layout::Colemak), Some('q'));
+    }
+
+    #[test]
+    fn unlocks_after_enough_good_prompts() {
+        let mut curriculum = Curriculum::new();
+        for _ in 1..RECENT_PROMPTS {
+            assert_eq!(curriculum.record(PASSING, Layout::Qwerty, 95.0, 30.0), None);
+        }
+        // 40 WPM at 99% accuracy
+        assert_eq!(
+            curriculum.record(PASSING, Layout::Qwerty, 95.0, 30.0),
+            Some('q')
+        );
+        assert!(curriculum.recent.is_empty());
+        let slow = LessonSample {
+            millis: 60_000.0,
+            ..PASSING
+        };
+        for _ in 0..RECENT_PROMPTS {
+            assert_eq!(curriculum.record(slow, Layout::Qwerty, 95.0, 30.0), None);
+        }
+    }
+
+    #[test]
+    fn estimates_the_practice_left() {
+        let trend = Trend {
+            per_minute: 0.5,
+            current: 0.0,
+        };
+        let mut curriculum = Curriculum::new();
+        assert_eq!(curriculum.minutes_left(&trend, 30.0), None);
+        // 40 WPM, the prompts still needed take two minutes
+        curriculum.recent.push(PASSING);
+        assert_eq!(curriculum.minutes_left(&trend, 30.0), Some(2.0));
+        // 20 WPM, 10 short of the threshold
+        curriculum.recent = vec![
+            LessonSample {
+                millis: 60_000.0,
+                ..PASSING
+            };
+            RECENT_PROMPTS
+        ];
+        assert_eq!(curriculum.minutes_left(&trend, 30.0), Some(20.0));
+    }
+}
@@ -0,0 +1,84 @@
+//! Carets of other participants drawn inline in the prompt.
+//! Their positions arrive as occasional updates and are interpolated
+//! in between, so a caret glides instead of jumping a few characters at a time.
+//! There is no networked mode yet, the pace caret is the only participant.
+
+/// How often the pace caret reports its position, like a remote participant would
+const UPDATE_MS: f64 = 250.0;
+/// Carets are redrawn this often between updates
+pub const FRAME_MS: u32 = 50;
+const PACE_CARET_COLOR: &str = "#1e90ff";
+
+pub struct RemoteCursor {
+    pub name: String,
+    pub color: &'static str,
+    /// Interpolated from position `from` at `from_at` to `to` at `to_at`
+    from: f64,
+    to: f64,
+    from_at: f64,
+    to_at: f64,
+}
+
+impl RemoteCursor {
+    pub fn new(name: String, color: &'static str) -> Self {
+        RemoteCursor {
+            name,
+            color,
+            from: 0.0,
+            to: 0.0,
+            from_at: 0.0,
+            to_at: 0.0,
+        }
+    }
+
+    /// Heads to `position` from where the caret is drawn now,
+    /// arriving when the next update is due
+    pub fn update(&mut self, position: usize, now: f64) {
+        self.from = self.interpolated(now);
+        self.from_at = now;
+        self.to = position as f64;
+        self.to_at = now + UPDATE_MS;
+    }
+
+    fn interpolated(&self, now: f64) -> f64 {
+        if now >= self.to_at {
+            return self.to;
+        }
+        let progress = (now - self.from_at) / (self.to_at - self.from_at);
+        self.from + (self.to - self.from) * progress.clamp(0.0, 1.0)
+    }
+
+    /// Index of the character the caret is drawn on
+    pub fn position(&self, now: f64) -> usize {
+        self.interpolated(now) as usize
+    }
+}
+
+/// A participant typing at a steady pace, to race against
+pub struct Pacer {
+    /// Time the prompt was started, identifies the frames of this race
+    pub id: f64,
+    pub cursor: RemoteCursor,
+    chars_per_ms: f64,
+    next_update: f64,
+}
+
+impl Pacer {
+    pub fn new(name: String, chars_per_ms: f64, now: f64) -> Self {
+        Pacer {
+            id: now,
+            cursor: RemoteCursor::new(name, PACE_CARET_COLOR),
+            chars_per_ms,
+            next_update: now,
+        }
+    }
+
+    /// Reports the position reached `elapsed` ms into the prompt when an update is due
+    pub fn tick(&mut self, elapsed: f64, now: f64) {
+        if now >= self.next_update {
+            self.cursor
+                .update((elapsed * self.chars_per_ms) as usize, now);
+            self.next_update = now + UPDATE_MS;
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Data entry assessment in the format of clerical job tests:
+//! numeric fields are typed one by one, each confirmed with Enter
+//! (the numeric keypad works as is), and the speed is reported
+//! in keystrokes per hour with a penalty for every field with an error.
+
+use rand::Rng;
+
+/// Fields in one assessment
+pub const FIELDS: usize = 30;
+/// Keystrokes deducted from the net count for every field with an error
+pub const ERROR_PENALTY: usize = 10;
+
+pub struct DataEntryTest {
+    fields: Vec<String>,
+    current: usize,
+    keystrokes: usize,
+    errors: usize,
+    /// First keystroke of the first field, in event timestamp milliseconds
+    started_at: Option<f64>,
+}
+
+/// Outcome of a finished assessment
+pub struct DataEntryResult {
+    pub date: f64,
+    pub fields: usize,
+    /// Including corrections and the Enter after every field
+    pub keystrokes: usize,
+    /// Fields left with at least one wrong character
+    pub errors: usize,
+    pub millis: f64,
+}
+
+impl DataEntryTest {
+    pub fn new() -> Self {
+        let mut rng = rand::thread_rng();
+        DataEntryTest {
+            fields: (0..FIELDS).map(|_| random_field(&mut rng)).collect(),
+            current: 0,
+            keystrokes: 0,
+            errors: 0,
+            started_at: None,
+        }
+    }
+
+    pub fn prompt(&self) -> &str {
+        &self.fields[self.current]
+    }
+
+    pub fn position(&self) -> (usize, usize) {
+        (self.current + 1, self.fields.len())
+    }
+
+    /// Records a field confirmed with Enter at `now`.
+    /// Returns the result once the last field is done.
+    pub fn record_field(
+        &mut self,
+        keystrokes: usize,
+        has_error: bool,
+        started_at: Option<f64>,
+        now: f64,
+    ) -> Option<DataEntryResult> {
+        self.started_at = self.started_at.or(started_at);
+        self.keystrokes += keystrokes + 1;
+        self.errors += usize::from(has_error);
+        self.current += 1;
+        (self.current == self.fields.len()).then(|| DataEntryResult {
+            date: js_sys::Date::now(),
+            fields: self.fields.len(),
+            keystrokes: self.keystrokes,
+            errors: self.errors,
+            millis: self.started_at.map_or(0.0, |at| now - at),
+        })
+    }
+}
+
+impl DataEntryResult {
+    fn per_hour(&self, keystrokes: f64) -> f64 {
+        let hours = self.millis / 3_600_000.0;
+        if hours <= 0.0 {
+            return 0.0;
+        }
+        keystrokes / hours
+    }
+
+    pub fn gross_kph(&self) -> f64 {
+        self.per_hour(self.keystrokes as f64)
+    }
+
+    pub fn net_kph(&self) -> f64 {
+        let net = self.keystrokes.saturating_sub(self.errors * ERROR_PENALTY);
+        self.per_hour(net as f64)
+    }
+
+    /// Share of fields entered without errors, in percent
+    pub fn accuracy(&self) -> f64 {
+        100.0 * (self.fields - self.errors) as f64 / self.fields as f64
+    }
+}
+
+/// A whole number of 3 to 7 digits or an amount with cents
+fn random_field(rng: &mut impl Rng) -> String {
+    let digits = rng.gen_range(3..=7);
+    let number: String = (0..digits)
+        .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+        .collect();
+    if rng.gen_bool(0.3) {
+        format!("{number}.{:02}", rng.gen_range(0..100))
+    } else {
+        number
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1200 keystrokes in six minutes, 3 of the 30 fields wrong
+    fn result() -> DataEntryResult {
+        DataEntryResult {
+            date: 0.0,
+            fields: FIELDS,
+            keystrokes: 1200,
+            errors: 3,
+            millis: 360_000.0,
+        }
+    }
+
+    #[test]
+    fn keystrokes_are_counted_per_hour() {
+        assert_eq!(result().gross_kph(), 12_000.0);
+        let instant = DataEntryResult {
+            millis: 0.0,
+            ..result()
+        };
+        assert_eq!((instant.gross_kph(), instant.net_kph()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn every_field_with_an_error_costs_the_penalty() {
+        assert_eq!(result().net_kph(), (1200 - 3 * ERROR_PENALTY) as f64 * 10.0);
+        let hopeless = DataEntryResult {
+            keystrokes: 20,
+            ..result()
+        };
+        assert_eq!(hopeless.net_kph(), 0.0);
+    }
+
+    #[test]
+    fn accuracy_is_the_share_of_fields_without_errors() {
+        assert_eq!(result().accuracy(), 90.0);
+        let clean = DataEntryResult {
+            errors: 0,
+            ..result()
+        };
+        assert_eq!(clean.accuracy(), 100.0);
+    }
+}
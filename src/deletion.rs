@@ -0,0 +1,76 @@
+//! Removing stored practice data on request. Events and results can be removed
+//! for a time range or a session, and the keystroke-level log can be dropped
+//! as a whole, keeping the statistics it added up to.
+
+use crate::{
+    events::{EventKind, EventLog},
+    history::History,
+    session::Period,
+    settings::Penalties,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Deletion {
+    /// Events and results of a period
+    Range(Period),
+    /// All events; the statistics derived from them are kept
+    KeystrokeLogs,
+}
+
+/// What a deletion would remove
+pub struct DeletionPreview {
+    pub events: usize,
+    pub keystrokes: usize,
+    pub prompts: usize,
+    pub results: usize,
+    /// The statistics stay as they are instead of losing what the events added
+    pub keeps_stats: bool,
+}
+
+impl Deletion {
+    fn covers(self, at: f64) -> bool {
+        match self {
+            Deletion::Range(period) => period.contains(at),
+            Deletion::KeystrokeLogs => true,
+        }
+    }
+
+    pub fn preview(self, log: &EventLog, history: &History) -> DeletionPreview {
+        let events: Vec<&EventKind> = log
+            .events
+            .iter()
+            .filter(|e| self.covers(e.at))
+            .map(|e| &e.kind)
+            .collect();
+        DeletionPreview {
+            events: events.len(),
+            keystrokes: events
+                .iter()
+                .filter(|k| matches!(k, EventKind::Keystroke { .. }))
+                .count(),
+            prompts: events
+                .iter()
+                .filter(|k| matches!(k, EventKind::PromptStart { .. }))
+                .count(),
+            results: match self {
+                Deletion::Range(_) => history
+                    .results
+                    .iter()
+                    .filter(|r| self.covers(r.meta.created_at))
+                    .count(),
+                Deletion::KeystrokeLogs => 0,
+            },
+            keeps_stats: self == Deletion::KeystrokeLogs,
+        }
+    }
+
+    pub fn apply(self, log: &mut EventLog, history: &mut History, penalties: Penalties) {
+        match self {
+            Deletion::Range(_) => {
+                log.retain(|e| !self.covers(e.at));
+                history.results.retain(|r| !self.covers(r.meta.created_at));
+            }
+            Deletion::KeystrokeLogs => log.fold_all(penalties),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Finding the keys of a new layout: a character is shown, and if the user
+//! hesitates its key is revealed on an on-screen keyboard. How often each
+//! key had to be revealed tells how well the layout is learned.
+
+use std::collections::BTreeMap;
+
+use rand::{distributions::WeightedIndex, prelude::Distribution};
+use serde::{Deserialize, Serialize};
+
+use crate::{codec, keycaps::Layout, store::StatsStore};
+
+pub const DISCOVERY_STORAGE_KEY: &str = "discovery";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub struct KeyReveals {
+    pub shown: usize,
+    pub revealed: usize,
+}
+
+impl KeyReveals {
+    /// Share of times the key had to be revealed, unseen keys count as unknown
+    pub fn rate(self) -> f64 {
+        if self.shown == 0 {
+            return 1.0;
+        }
+        self.revealed as f64 / self.shown as f64
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct DiscoveryStats {
+    pub keys: BTreeMap<char, KeyReveals>,
+}
+
+impl DiscoveryStats {
+    pub fn load(store: &dyn StatsStore) -> Self {
+        codec::load(store, DISCOVERY_STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String> {
+        codec::save(store, DISCOVERY_STORAGE_KEY, self, compact)
+    }
+
+    pub fn record(&mut self, c: char, revealed: bool) {
+        let reveals = self.keys.entry(c).or_default();
+        reveals.shown += 1;
+        reveals.revealed += usize::from(revealed);
+    }
+
+    /// The next character to find, keys that needed revealing more often first
+    pub fn pick(&self, layout: Layout, previous: Option<char>) -> char {
+        let chars: Vec<char> = layout
+            .chars()
+            .into_iter()
+            .filter(|c| Some(*c) != previous)
+            .collect();
+        let weights = chars
+            .iter()
+            .map(|c| 1.0 + 4.0 * self.keys.get(c).copied().unwrap_or_default().rate());
+        let index = WeightedIndex::new(weights).unwrap();
+        chars[index.sample(&mut rand::thread_rng())]
+    }
+}
+
+/// The character being looked for
+pub struct Discovery {
+    pub target: char,
+    /// Timestamp (ms) it was shown at, identifies its reveal timer
+    pub shown_at: f64,
+    pub revealed: bool,
+}
@@ -0,0 +1,88 @@
+//! Repeat-until-clean drills: a sentence comes back
+//! until it is typed without mistakes enough times in a row.
+
+/// Sentences used when the drill is not started from custom text, one per line
+pub const SENTENCES_ASSET: &str = "sentences.txt";
+
+pub fn parse_sentences(asset: &str) -> Vec<String> {
+    asset
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Splits text after `.`, `!` and `?`, dropping empty pieces
+pub fn split_sentences(text: &str) -> Vec<String> {
+    text.split_inclusive(['.', '!', '?'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub struct SentenceResult {
+    pub sentence: String,
+    pub attempts: usize,
+}
+
+pub struct RepeatDrill {
+    sentences: Vec<String>,
+    current: usize,
+    /// Attempts on the current sentence so far
+    attempts: usize,
+    clean_streak: usize,
+    required_clean: usize,
+    /// Results of completed sentences, oldest first
+    pub results: Vec<SentenceResult>,
+    /// Sentences of numbers, see `numbers`
+    pub numbers: bool,
+}
+
+impl RepeatDrill {
+    /// `sentences` must not be empty
+    pub fn new(sentences: Vec<String>, required_clean: usize) -> Self {
+        RepeatDrill {
+            sentences,
+            current: 0,
+            attempts: 0,
+            clean_streak: 0,
+            required_clean: required_clean.max(1),
+            results: vec![],
+            numbers: false,
+        }
+    }
+
+    pub fn prompt(&self) -> &str {
+        &self.sentences[self.current]
+    }
+
+    pub fn position(&self) -> (usize, usize) {
+        (self.current + 1, self.sentences.len())
+    }
+
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    pub fn clean_streak(&self) -> (usize, usize) {
+        (self.clean_streak, self.required_clean)
+    }
+
+    /// Records a finished attempt, moving on to the next sentence
+    /// (wrapping around) once enough clean attempts were made in a row.
+    pub fn record_attempt(&mut self, clean: bool) {
+        self.attempts += 1;
+        self.clean_streak = if clean { self.clean_streak + 1 } else { 0 };
+        if self.clean_streak >= self.required_clean {
+            self.results.push(SentenceResult {
+                sentence: self.prompt().to_string(),
+                attempts: self.attempts,
+            });
+            self.current = (self.current + 1) % self.sentences.len();
+            self.attempts = 0;
+            self.clean_streak = 0;
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Password-based encryption of downloaded files, so they can be kept in
+//! places the user does not trust. Uses the browser's Web Crypto:
+//! a PBKDF2-SHA-256 derived key and AES-GCM, which also detects tampering.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AesDerivedKeyParams, AesGcmParams, CryptoKey, Pbkdf2Params, SubtleCrypto};
+
+/// Marks encrypted files, JSON never starts like this
+const ENCRYPTED_PREFIX: &str = "aes1:";
+const SALT_LEN: usize = 16;
+const IV_LEN: usize = 12;
+/// As recommended by OWASP for PBKDF2-HMAC-SHA256
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+fn js_error(e: JsValue) -> String {
+    format!("{e:?}")
+}
+
+pub fn is_encrypted(text: &str) -> bool {
+    text.starts_with(ENCRYPTED_PREFIX)
+}
+
+fn subtle() -> Result<SubtleCrypto, String> {
+    Ok(gloo_utils::window().crypto().map_err(js_error)?.subtle())
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut bytes = [0; N];
+    gloo_utils::window()
+        .crypto()
+        .map_err(js_error)?
+        .get_random_values_with_u8_array(&mut bytes)
+        .map_err(js_error)?;
+    Ok(bytes)
+}
+
+async fn derive_key(password: &str, salt: &[u8]) -> Result<CryptoKey, String> {
+    let subtle = subtle()?;
+    let usages = Array::of1(&"deriveKey".into());
+    let password_key = subtle
+        .import_key_with_str(
+            "raw",
+            &Uint8Array::from(password.as_bytes()),
+            "PBKDF2",
+            false,
+            &usages,
+        )
+        .map_err(js_error)?;
+    let password_key: CryptoKey = JsFuture::from(password_key).await.map_err(js_error)?.into();
+    let params = Pbkdf2Params::new(
+        "PBKDF2",
+        &"SHA-256".into(),
+        PBKDF2_ITERATIONS,
+        &Uint8Array::from(salt),
+    );
+    let usages = Array::of2(&"encrypt".into(), &"decrypt".into());
+    let key = subtle
+        .derive_key_with_object_and_object(
+            &params,
+            &password_key,
+            &AesDerivedKeyParams::new("AES-GCM", 256),
+            false,
+            &usages,
+        )
+        .map_err(js_error)?;
+    Ok(JsFuture::from(key).await.map_err(js_error)?.into())
+}
+
+/// `text` encrypted with `password`, salt and nonce included
+pub async fn encrypt(text: &str, password: &str) -> Result<String, String> {
+    let salt: [u8; SALT_LEN] = random_bytes()?;
+    let iv: [u8; IV_LEN] = random_bytes()?;
+    let key = derive_key(password, &salt).await?;
+    let encrypted = subtle()?
+        .encrypt_with_object_and_u8_array(
+            &AesGcmParams::new("AES-GCM", &Uint8Array::from(&iv[..])),
+            &key,
+            &mut text.as_bytes().to_vec(),
+        )
+        .map_err(js_error)?;
+    let encrypted = JsFuture::from(encrypted).await.map_err(js_error)?;
+    let mut bytes = [&salt[..], &iv[..]].concat();
+    bytes.extend(Uint8Array::new(&encrypted).to_vec());
+    Ok(format!("{ENCRYPTED_PREFIX}{}", STANDARD.encode(bytes)))
+}
+
+/// The text [`encrypt`] was given, fails on a wrong password
+pub async fn decrypt(text: &str, password: &str) -> Result<String, String> {
+    let encoded = text
+        .strip_prefix(ENCRYPTED_PREFIX)
+        .ok_or("the file is not encrypted")?;
+    let mut bytes = STANDARD.decode(encoded.trim()).map_err(|e| e.to_string())?;
+    if bytes.len() < SALT_LEN + IV_LEN {
+        return Err("the file is truncated".to_string());
+    }
+    let mut ciphertext = bytes.split_off(SALT_LEN + IV_LEN);
+    let (salt, iv) = bytes.split_at(SALT_LEN);
+    let key = derive_key(password, salt).await?;
+    let decrypted = subtle()?
+        .decrypt_with_object_and_u8_array(
+            &AesGcmParams::new("AES-GCM", &Uint8Array::from(iv)),
+            &key,
+            &mut ciphertext,
+        )
+        .map_err(js_error)?;
+    // AES-GCM rejects the data if the key is wrong
+    let decrypted = JsFuture::from(decrypted)
+        .await
+        .map_err(|_| "wrong password or damaged file".to_string())?;
+    String::from_utf8(Uint8Array::new(&decrypted).to_vec()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,308 @@
+//! Text of EPUB books for the long-text mode. An EPUB is a zip of XHTML
+//! documents; the package file lists them in reading order (the spine).
+//! Only what is needed for that is read: stored and deflated zip entries,
+//! and tags scanned as text rather than parsed as XML.
+
+use std::collections::HashMap;
+
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+const DIRECTORY_ENTRY: u32 = 0x0201_4b50;
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+/// Elements that end a line of text
+const BLOCKS: [&str; 12] = [
+    "p",
+    "div",
+    "br",
+    "li",
+    "tr",
+    "blockquote",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+];
+/// Elements that become chapter headings
+const HEADINGS: [&str; 3] = ["h1", "h2", "h3"];
+/// Elements whose contents are not text
+const SKIPPED: [&str; 3] = ["head", "script", "style"];
+
+fn u16_at(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Files of a zip archive by their paths
+struct Zip<'a> {
+    bytes: &'a [u8],
+    /// Method, compressed size and offset of the local header of each file
+    entries: HashMap<String, (u16, usize, usize)>,
+}
+
+impl<'a> Zip<'a> {
+    fn open(bytes: &'a [u8]) -> Option<Self> {
+        // The end record is last, followed by a comment of up to 64 KiB
+        let end = (0..bytes.len().saturating_sub(21))
+            .rev()
+            .take(22 + usize::from(u16::MAX))
+            .find(|at| u32_at(bytes, *at) == Some(END_OF_DIRECTORY))?;
+        let count = usize::from(u16_at(bytes, end + 10)?);
+        let mut at = u32_at(bytes, end + 16)? as usize;
+        let mut entries = HashMap::new();
+        for _ in 0..count {
+            if u32_at(bytes, at)? != DIRECTORY_ENTRY {
+                return None;
+            }
+            let method = u16_at(bytes, at + 10)?;
+            let size = u32_at(bytes, at + 20)? as usize;
+            let name_len = usize::from(u16_at(bytes, at + 28)?);
+            let extra_len = usize::from(u16_at(bytes, at + 30)?);
+            let comment_len = usize::from(u16_at(bytes, at + 32)?);
+            let header = u32_at(bytes, at + 42)? as usize;
+            let name = String::from_utf8_lossy(bytes.get(at + 46..at + 46 + name_len)?);
+            entries.insert(name.into_owned(), (method, size, header));
+            at += 46 + name_len + extra_len + comment_len;
+        }
+        Some(Zip { bytes, entries })
+    }
+
+    fn read(&self, path: &str) -> Result<String, String> {
+        let missing = || format!("{path} is missing");
+        let (method, size, header) = *self.entries.get(path).ok_or_else(missing)?;
+        let bytes = self.bytes;
+        if u32_at(bytes, header) != Some(LOCAL_HEADER) {
+            return Err(missing());
+        }
+        let name_len = usize::from(u16_at(bytes, header + 26).ok_or_else(missing)?);
+        let extra_len = usize::from(u16_at(bytes, header + 28).ok_or_else(missing)?);
+        let start = header + 30 + name_len + extra_len;
+        let data = bytes.get(start..start + size).ok_or_else(missing)?;
+        let data = match method {
+            STORED => data.to_vec(),
+            DEFLATED => miniz_oxide::inflate::decompress_to_vec(data)
+                .map_err(|e| format!("{path}: {e:?}"))?,
+            _ => return Err(format!("{path}: unsupported compression {method}")),
+        };
+        String::from_utf8(data).map_err(|e| format!("{path}: {e}"))
+    }
+}
+
+/// Value of the attribute `name` in the tag starting at `tag`
+fn attribute<'t>(tag: &'t str, name: &str) -> Option<&'t str> {
+    let tag = &tag[..tag.find('>')?];
+    let start = tag.find(&format!(" {name}="))? + name.len() + 2;
+    let quote = tag[start..].chars().next()?;
+    let value = &tag[start + 1..];
+    Some(&value[..value.find(quote)?])
+}
+
+/// Name of the tag whose text after `<` is `tag`
+fn tag_name(tag: &str) -> &str {
+    let end = tag.find(|c: char| c.is_whitespace() || c == '>' || c == '/');
+    &tag[..end.unwrap_or(tag.len())]
+}
+
+/// Starts of the tags named `name`, namespace prefixes ignored
+fn tags<'t>(xml: &'t str, name: &'t str) -> impl Iterator<Item = &'t str> {
+    xml.match_indices('<')
+        .map(|(i, _)| &xml[i..])
+        .filter(move |tag| tag_name(&tag[1..]).rsplit(':').next() == Some(name))
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let code = entity.strip_prefix('#')?;
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/// Text of an XHTML document, a line per block, headings marked with `#`
+pub fn xhtml_text(xhtml: &str) -> String {
+    let mut text = String::new();
+    let mut skipping: Option<&str> = None;
+    let mut rest = xhtml;
+    while let Some(open) = rest.find(['<', '&']) {
+        if skipping.is_none() {
+            text.push_str(&rest[..open]);
+        }
+        rest = &rest[open..];
+        if rest.starts_with('&') {
+            let entity = rest[1..].find(';').map(|end| (&rest[1..end + 1], end + 1));
+            match entity.and_then(|(e, end)| Some((decode_entity(e)?, end))) {
+                Some((c, end)) => {
+                    if skipping.is_none() {
+                        text.push(c);
+                    }
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    text.push('&');
+                    rest = &rest[1..];
+                }
+            }
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+        let closing = tag.starts_with('/');
+        let name = tag_name(tag.trim_start_matches('/')).to_ascii_lowercase();
+        if let Some(skipped) = skipping {
+            if closing && name == skipped {
+                skipping = None;
+            }
+            continue;
+        }
+        if let Some(skipped) = SKIPPED.iter().find(|s| **s == name) {
+            if !closing && !tag.ends_with('/') {
+                skipping = Some(skipped);
+            }
+        } else if BLOCKS.contains(&name.as_str()) {
+            text.push('\n');
+            if !closing && HEADINGS.contains(&name.as_str()) {
+                text.push_str("# ");
+            }
+        }
+    }
+    if skipping.is_none() {
+        text.push_str(rest);
+    }
+    text
+}
+
+/// Joins the parts of `base`'s directory and a relative `href`
+fn resolve(base: &str, href: &str) -> String {
+    let mut parts: Vec<&str> = base.split('/').collect();
+    parts.pop();
+    for part in href.split('#').next().unwrap_or_default().split('/') {
+        match part {
+            ".." => {
+                parts.pop();
+            }
+            "." | "" => {}
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Title, if the book has one, and the text of the documents in reading order
+pub fn read(bytes: &[u8]) -> Result<(Option<String>, String), String> {
+    let zip = Zip::open(bytes).ok_or("not a zip archive")?;
+    let container = zip.read("META-INF/container.xml")?;
+    let package_path = tags(&container, "rootfile")
+        .find_map(|tag| attribute(tag, "full-path"))
+        .ok_or("no package file")?;
+    let package = zip.read(package_path)?;
+    let title = tags(&package, "title").find_map(|tag| {
+        let text = &tag[tag.find('>')? + 1..];
+        Some(xhtml_text(&text[..text.find('<')?]).trim().to_string())
+    });
+    let manifest: HashMap<&str, &str> = tags(&package, "item")
+        .filter_map(|tag| Some((attribute(tag, "id")?, attribute(tag, "href")?)))
+        .collect();
+    let mut text = String::new();
+    for idref in tags(&package, "itemref").filter_map(|tag| attribute(tag, "idref")) {
+        let Some(href) = manifest.get(idref) else {
+            continue;
+        };
+        text.push_str(&xhtml_text(&zip.read(&resolve(package_path, href))?));
+        text.push('\n');
+    }
+    Ok((title.filter(|t| !t.is_empty()), text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A zip of stored files
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut bytes = vec![];
+        let mut directory = vec![];
+        for (name, contents) in files {
+            let header = bytes.len() as u32;
+            let fields = |signature: u32| {
+                let mut fields = signature.to_le_bytes().to_vec();
+                fields.resize(30, 0);
+                fields[18..22].copy_from_slice(&(contents.len() as u32).to_le_bytes());
+                fields[22..26].copy_from_slice(&(contents.len() as u32).to_le_bytes());
+                fields[26..28].copy_from_slice(&(name.len() as u16).to_le_bytes());
+                fields
+            };
+            bytes.extend(fields(LOCAL_HEADER));
+            bytes.extend(name.bytes());
+            bytes.extend(contents.bytes());
+            let mut entry = fields(DIRECTORY_ENTRY);
+            // The directory entry has the version made by before the local header fields
+            entry.splice(4..4, [0, 0]);
+            entry.resize(46, 0);
+            entry[42..46].copy_from_slice(&header.to_le_bytes());
+            directory.extend(entry);
+            directory.extend(name.bytes());
+        }
+        let directory_at = bytes.len() as u32;
+        bytes.extend(&directory);
+        let mut end = END_OF_DIRECTORY.to_le_bytes().to_vec();
+        end.resize(22, 0);
+        end[10..12].copy_from_slice(&(files.len() as u16).to_le_bytes());
+        end[16..20].copy_from_slice(&directory_at.to_le_bytes());
+        bytes.extend(end);
+        bytes
+    }
+
+    #[test]
+    fn text_of_blocks_and_headings() {
+        let text = xhtml_text(
+            "<html><head><title>x</title></head><body><h1>One</h1><p>Fish &amp; chips&#x2019;</p></body></html>",
+        );
+        assert_eq!(
+            text.lines().filter(|l| !l.is_empty()).collect::<Vec<_>>(),
+            ["# One", "Fish & chips\u{2019}"]
+        );
+    }
+
+    #[test]
+    fn documents_in_spine_order() {
+        let book = zip(&[
+            (
+                "META-INF/container.xml",
+                r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf"/></rootfiles></container>"#,
+            ),
+            (
+                "OEBPS/content.opf",
+                r#"<package><metadata><dc:title>Tale</dc:title></metadata>
+                <manifest><item id="a" href="text/a.xhtml"/><item id="b" href="text/b.xhtml"/></manifest>
+                <spine><itemref idref="b"/><itemref idref="a"/></spine></package>"#,
+            ),
+            ("OEBPS/text/a.xhtml", "<p>Second</p>"),
+            ("OEBPS/text/b.xhtml", "<p>First</p>"),
+        ]);
+        let (title, text) = read(&book).unwrap();
+        assert_eq!(title.as_deref(), Some("Tale"));
+        assert_eq!(
+            text.split_whitespace().collect::<Vec<_>>(),
+            ["First", "Second"]
+        );
+    }
+}
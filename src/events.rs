@@ -0,0 +1,470 @@
+//! The append-only event log, the source of truth for statistics.
+//! [`TypingErrors`] is derived by replaying the log, so a change to the
+//! scoring applies to old data too, and merging devices is a union of events.
+//! Stores that keep events one at a time hold the baseline alone under
+//! [`EVENTS_STORAGE_KEY`], and save only the events added since the last save.
+
+use std::{cell::Cell, collections::HashSet};
+
+use serde::{Deserialize, Serialize};
+use typing_core::{
+    errors::{TypingErrors, SCORING_VERSION},
+    ids::Ulid,
+    review,
+};
+
+use crate::{
+    backup, codec,
+    settings::{Penalties, Settings},
+    store::StatsStore,
+};
+
+pub const EVENTS_STORAGE_KEY: &str = "events";
+/// Reported in place of the events kept one at a time that cannot be read
+const EVENT_ITEMS_KEY: &str = "events.items";
+/// Older events are folded into the baseline to keep the log within storage limits
+const MAX_EVENTS: usize = 20_000;
+/// The limit where events are stored one at a time, and the log is not rewritten to add one
+const MAX_KEPT_EVENTS: usize = 500_000;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum EventKind {
+    PromptStart {
+        prompt: String,
+        retention_check: bool,
+        /// Keystrokes of warm-up prompts do not change the statistics
+        #[serde(default)]
+        warm_up: bool,
+        /// Keystrokes of number drills and data entry have statistics of their own
+        #[serde(default)]
+        kept_apart: bool,
+    },
+    Keystroke {
+        expected: char,
+        typed: char,
+        /// Keys of the numeric keypad have statistics of their own
+        #[serde(default)]
+        numpad: bool,
+    },
+    Backspace,
+    /// Text appended to the prompt being typed, as a timed test goes on
+    PromptExtend {
+        text: String,
+    },
+    PromptEnd {
+        /// Input on the prompt was rejected as not typed by hand
+        flagged: bool,
+    },
+    SettingsChange(Box<Settings>),
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Event {
+    pub id: Ulid,
+    /// Unix time in ms
+    pub at: f64,
+    pub kind: EventKind,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EventLog {
+    /// Statistics before the first event: those stored before the log existed
+    /// and the events folded in since
+    pub baseline: TypingErrors,
+    /// Oldest first
+    pub events: Vec<Event>,
+    /// Times old events were folded into the baseline since loading
+    #[serde(skip)]
+    folds: usize,
+    /// The events are stored one at a time rather than in this value
+    #[serde(default)]
+    kept_apart: bool,
+    /// Folds and the number of events when the events were last stored one at a time
+    #[serde(skip)]
+    stored: Cell<Option<(usize, usize)>>,
+}
+
+impl EventLog {
+    pub fn new(baseline: TypingErrors) -> Self {
+        EventLog {
+            baseline,
+            events: vec![],
+            folds: 0,
+            kept_apart: false,
+            stored: Cell::new(None),
+        }
+    }
+
+    pub fn load(store: &dyn StatsStore) -> Option<Self> {
+        let mut log: EventLog = codec::load(store, EVENTS_STORAGE_KEY).ok()?;
+        if !log.kept_apart {
+            // Saved whole, by another backend or into a file
+            return Some(log);
+        }
+        let mut unreadable = vec![];
+        for stored in store.events() {
+            match codec::decode(&stored) {
+                Ok(event) => log.events.push(event),
+                Err(e) => unreadable.push((stored, e)),
+            }
+        }
+        if let Some((_, error)) = unreadable.first() {
+            // All of them under one key, set aside before a fold replaces them
+            let error = format!("{} events, {error}", unreadable.len());
+            let stored = unreadable.into_iter().map(|(s, _)| s).collect::<Vec<_>>();
+            let stored = serde_json::to_string(&stored).unwrap();
+            codec::set_aside(store, EVENT_ITEMS_KEY, stored, &error);
+        }
+        log.events
+            .sort_by(|a, b| a.at.total_cmp(&b.at).then(a.id.cmp(&b.id)));
+        log.stored.set(Some((log.folds, log.events.len())));
+        // Whatever store it is saved into next
+        log.kept_apart = false;
+        Some(log)
+    }
+
+    /// Saves the log whole, or the baseline and the events added since the
+    /// last save if the store keeps events one at a time
+    pub fn save(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String> {
+        if !store.keeps_events() {
+            return codec::save(store, EVENTS_STORAGE_KEY, self, compact);
+        }
+        // Folding, removing and merging change earlier events, all are stored anew
+        let added = match self.stored.get() {
+            Some((folds, stored)) if folds == self.folds && stored <= self.events.len() => stored,
+            _ => 0,
+        };
+        let events = self.events[added..]
+            .iter()
+            .map(|e| {
+                let encoded = if compact {
+                    codec::encode(e)?
+                } else {
+                    serde_json::to_string(e).map_err(|e| e.to_string())?
+                };
+                Ok((e.id.to_string(), encoded))
+            })
+            .collect::<Result<_, String>>()?;
+        // After a failed write it is unknown which events were stored
+        store
+            .put_events(events, added == 0)
+            .inspect_err(|_| self.stored.set(None))?;
+        self.stored.set(Some((self.folds, self.events.len())));
+        let baseline = EventLog {
+            kept_apart: true,
+            ..EventLog::new(self.baseline.clone())
+        };
+        codec::save(store, EVENTS_STORAGE_KEY, &baseline, compact)
+    }
+
+    /// The next save is into another store, all events are saved there
+    pub fn store_changed(&self) {
+        self.stored.set(None);
+    }
+
+    fn max_events(&self) -> usize {
+        if self.stored.get().is_some() {
+            MAX_KEPT_EVENTS
+        } else {
+            MAX_EVENTS
+        }
+    }
+
+    pub fn push(&mut self, kind: EventKind, now: f64) {
+        self.events.push(Event {
+            id: Ulid::new(now),
+            at: now,
+            kind,
+        });
+        let max = self.max_events();
+        if self.events.len() > max {
+            let folded: Vec<Event> = self.events.drain(..max / 10).collect();
+            // Folded events keep the penalties of the baseline for good
+            let penalties = self.baseline.penalties;
+            let mut replay = Replay::new(&EventLog::new(self.baseline.clone()), penalties);
+            replay.apply(&folded);
+            self.baseline = replay.stats;
+            self.folds += 1;
+        }
+    }
+
+    /// Removes events, statistics derived from them should be derived anew
+    pub fn retain(&mut self, keep: impl FnMut(&Event) -> bool) {
+        self.events.retain(keep);
+        // Replays in progress start over
+        self.folds += 1;
+    }
+
+    /// Folds all events into the baseline, keeping the statistics they add up to
+    pub fn fold_all(&mut self, penalties: Penalties) {
+        self.baseline = self.derive_errors(penalties);
+        self.events.clear();
+        self.folds += 1;
+    }
+
+    /// Statistics as the current scoring computes them
+    pub fn derive_errors(&self, penalties: Penalties) -> TypingErrors {
+        Replay::new(self, penalties)
+            .step(self, usize::MAX)
+            .expect("the whole log is replayed at once")
+    }
+
+    /// Union of the events of both logs. Baselines are merged like stats files.
+    pub fn merge(&self, other: &EventLog) -> EventLog {
+        let known: HashSet<Ulid> = self.events.iter().map(|e| e.id).collect();
+        let mut events = self.events.clone();
+        events.extend(
+            other
+                .events
+                .iter()
+                .filter(|e| !known.contains(&e.id))
+                .cloned(),
+        );
+        events.sort_by(|a, b| a.at.total_cmp(&b.at).then(a.id.cmp(&b.id)));
+        let baseline = if self.baseline.meta.id == other.baseline.meta.id {
+            self.baseline.clone()
+        } else {
+            backup::merge_errors(&self.baseline, &other.baseline)
+        };
+        EventLog {
+            baseline,
+            events,
+            ..EventLog::new(TypingErrors::default())
+        }
+    }
+}
+
+/// Replay of the log a slice at a time, so that a long log
+/// does not freeze the page. Events logged meanwhile are replayed too.
+/// It runs between frames rather than in a worker, which would need a wasm
+/// build of its own and a copy of the log sent over on every change.
+pub struct Replay {
+    stats: TypingErrors,
+    position: usize,
+    folds: usize,
+    retention_check: bool,
+    warm_up: bool,
+    kept_apart: bool,
+    /// Time of the previous keystroke of the prompt
+    previous_at: Option<f64>,
+    /// The prompt being typed and how far, for the keys before each keystroke
+    prompt: Vec<char>,
+    typed: usize,
+}
+
+impl Replay {
+    pub fn new(log: &EventLog, penalties: Penalties) -> Self {
+        Replay {
+            stats: TypingErrors {
+                penalties,
+                ..log.baseline.clone()
+            },
+            position: 0,
+            folds: log.folds,
+            retention_check: false,
+            warm_up: false,
+            kept_apart: false,
+            previous_at: None,
+            prompt: vec![],
+            typed: 0,
+        }
+    }
+
+    /// Replays up to `slice` more events of `log`,
+    /// returns the stats once the end of the log is reached
+    pub fn step(&mut self, log: &EventLog, slice: usize) -> Option<TypingErrors> {
+        // The events replayed so far may have been folded into the baseline
+        if log.folds != self.folds {
+            *self = Replay::new(log, self.stats.penalties);
+        }
+        let end = log.events.len().min(self.position.saturating_add(slice));
+        self.apply(&log.events[self.position..end]);
+        self.position = end;
+        (end == log.events.len()).then(|| TypingErrors {
+            scoring_version: SCORING_VERSION,
+            ..self.stats.clone()
+        })
+    }
+
+    /// Events replayed and all events
+    pub fn progress(&self, log: &EventLog) -> (usize, usize) {
+        (self.position, log.events.len())
+    }
+
+    fn apply(&mut self, events: &[Event]) {
+        for event in events {
+            match &event.kind {
+                EventKind::PromptStart {
+                    prompt,
+                    retention_check,
+                    warm_up,
+                    kept_apart,
+                } => {
+                    self.retention_check = *retention_check;
+                    self.warm_up = *warm_up;
+                    self.kept_apart = *kept_apart;
+                    self.previous_at = None;
+                    self.prompt = prompt.chars().collect();
+                    self.typed = 0;
+                }
+                EventKind::Keystroke { numpad, .. }
+                    if self.warm_up || self.kept_apart || *numpad => {}
+                EventKind::Keystroke {
+                    expected, typed, ..
+                } => {
+                    let correct = expected == typed;
+                    let latency = self.previous_at.map(|previous| event.at - previous);
+                    self.stats.account(*expected, *typed, event.at);
+                    if let (true, Some(ms)) = (correct, latency) {
+                        self.stats.account_latency(*expected, ms);
+                    }
+                    let before = &self.prompt[..self.typed.min(self.prompt.len())];
+                    self.stats
+                        .account_ngrams(before, *expected, correct, latency);
+                    self.previous_at = Some(event.at);
+                    self.typed += 1;
+                    if self.retention_check {
+                        review::account(&mut self.stats.retention, *expected, expected == typed);
+                    }
+                }
+                EventKind::Backspace => self.typed = self.typed.saturating_sub(1),
+                EventKind::PromptExtend { text } => self.prompt.extend(text.chars()),
+                EventKind::PromptEnd { .. } | EventKind::SettingsChange(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::BTreeMap};
+
+    use super::*;
+    use crate::store::{Backend, MemoryStore};
+
+    /// Keeps events one at a time like IndexedDB, counting the writes
+    #[derive(Default)]
+    struct EventStore {
+        values: MemoryStore,
+        events: RefCell<BTreeMap<String, String>>,
+        written: Cell<usize>,
+    }
+
+    impl StatsStore for EventStore {
+        fn backend(&self) -> Backend {
+            Backend::IndexedDb
+        }
+
+        fn get(&self, key: &str) -> Option<String> {
+            self.values.get(key)
+        }
+
+        fn set(&self, key: &str, value: String) -> Result<(), String> {
+            self.values.set(key, value)
+        }
+
+        fn keeps_events(&self) -> bool {
+            true
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.events.borrow().values().cloned().collect()
+        }
+
+        fn put_events(&self, events: Vec<(String, String)>, replace: bool) -> Result<(), String> {
+            if replace {
+                self.events.borrow_mut().clear();
+            }
+            self.written.set(self.written.get() + events.len());
+            self.events.borrow_mut().extend(events);
+            Ok(())
+        }
+    }
+
+    fn prompt(log: &mut EventLog, at: f64, prompt: &str, warm_up: bool, kept_apart: bool) {
+        let prompt = prompt.to_string();
+        let kind = EventKind::PromptStart {
+            prompt,
+            retention_check: false,
+            warm_up,
+            kept_apart,
+        };
+        log.push(kind, at);
+    }
+
+    fn keystroke(log: &mut EventLog, at: f64, expected: char, typed: char, numpad: bool) {
+        let kind = EventKind::Keystroke {
+            expected,
+            typed,
+            numpad,
+        };
+        log.push(kind, at);
+    }
+
+    #[test]
+    fn replay_scores_like_live_typing() {
+        let penalties = Penalties::default();
+        let mut live = TypingErrors {
+            penalties,
+            ..Default::default()
+        };
+        let mut log = EventLog::new(TypingErrors::default());
+        prompt(&mut log, 0.0, "abc", false, false);
+        for (at, (expected, typed)) in [(10.0, ('a', 'a')), (20.0, ('b', 'v')), (30.0, ('c', 'c'))]
+        {
+            keystroke(&mut log, at, expected, typed, false);
+            live.account(expected, typed, at);
+        }
+        let derived = log.derive_errors(penalties);
+        assert!(!live.error_stats.is_empty());
+        assert_eq!(derived.error_score, live.error_score);
+        assert_eq!(derived.error_stats, live.error_stats);
+        assert_eq!(derived.scoring_version, SCORING_VERSION);
+    }
+
+    #[test]
+    fn merging_unions_events_by_id() {
+        let mut local = EventLog::new(TypingErrors::default());
+        local.push(EventKind::Backspace, 1.0);
+        let mut imported = local.clone();
+        imported.push(EventKind::Backspace, 3.0);
+        local.push(EventKind::Backspace, 2.0);
+        let merged = local.merge(&imported);
+        let times: Vec<f64> = merged.events.iter().map(|e| e.at).collect();
+        assert_eq!(times, [1.0, 2.0, 3.0]);
+        assert_eq!(merged.merge(&local).events.len(), 3);
+    }
+
+    #[test]
+    fn keystrokes_kept_out_of_the_stats_are_skipped() {
+        let mut log = EventLog::new(TypingErrors::default());
+        prompt(&mut log, 0.0, "a", true, false);
+        keystroke(&mut log, 1.0, 'a', 'b', false);
+        prompt(&mut log, 2.0, "1", false, true);
+        keystroke(&mut log, 3.0, '1', '2', false);
+        prompt(&mut log, 4.0, "1", false, false);
+        keystroke(&mut log, 5.0, '1', '2', true);
+        let derived = log.derive_errors(Penalties::default());
+        assert!(derived.error_score.values().all(|score| *score == 0));
+        assert!(derived.error_stats.is_empty());
+    }
+
+    #[test]
+    fn only_new_events_are_written() {
+        let store = EventStore::default();
+        let mut log = EventLog::new(TypingErrors::default());
+        log.push(EventKind::Backspace, 1.0);
+        log.push(EventKind::Backspace, 2.0);
+        log.save(&store, false).unwrap();
+        log.push(EventKind::Backspace, 3.0);
+        log.save(&store, true).unwrap();
+        assert_eq!(store.written.get(), 3);
+
+        let loaded = EventLog::load(&store).unwrap();
+        assert_eq!(loaded.events.len(), 3);
+        // Saved whole elsewhere, the loaded log holds its events
+        let memory = MemoryStore::default();
+        loaded.save(&memory, false).unwrap();
+        assert_eq!(EventLog::load(&memory).unwrap().events.len(), 3);
+    }
+}
@@ -0,0 +1,239 @@
+//! Certification-style exam: a fixed prose passage typed for five minutes
+//! under strict rules, scored with the standard net WPM formula.
+//! Results are append-only and sealed, so they cannot be edited later.
+
+use serde::{Deserialize, Serialize};
+use typing_core::{ids::RecordMeta, CHARS_PER_WORD};
+
+use crate::{codec, store::StatsStore};
+
+/// The passage, one prompt per line
+pub const PASSAGE_ASSET: &str = "exam.txt";
+pub const DURATION_MS: f64 = 5.0 * 60_000.0;
+/// The countdown is refreshed this often
+pub const TICK_MS: u32 = 1000;
+pub const EXAM_STORAGE_KEY: &str = "exam_results";
+
+pub struct Exam {
+    lines: Vec<String>,
+    current: usize,
+    /// Backspaces still allowed, `None` is unlimited
+    backspaces_left: Option<usize>,
+    backspace_limit: Option<usize>,
+    /// First keystroke, in event timestamp milliseconds
+    pub started_at: Option<f64>,
+    chars: usize,
+    errors: usize,
+}
+
+impl Exam {
+    /// `lines` must not be empty
+    pub fn new(lines: Vec<String>, backspace_limit: Option<usize>) -> Self {
+        Exam {
+            lines,
+            current: 0,
+            backspaces_left: backspace_limit,
+            backspace_limit,
+            started_at: None,
+            chars: 0,
+            errors: 0,
+        }
+    }
+
+    pub fn prompt(&self) -> &str {
+        &self.lines[self.current]
+    }
+
+    /// Milliseconds left at `now`
+    pub fn remaining(&self, now: f64) -> f64 {
+        let elapsed = self.started_at.map_or(0.0, |at| now - at);
+        (DURATION_MS - elapsed).max(0.0)
+    }
+
+    /// Uses up one backspace, returns whether it is allowed
+    pub fn take_backspace(&mut self) -> bool {
+        match &mut self.backspaces_left {
+            None => true,
+            Some(0) => false,
+            Some(left) => {
+                *left -= 1;
+                true
+            }
+        }
+    }
+
+    pub fn backspaces_left(&self) -> Option<usize> {
+        self.backspaces_left
+    }
+
+    /// Records a typed line, `errors` being its uncorrected mistakes.
+    /// Returns whether the passage is over.
+    pub fn record_line(&mut self, chars: usize, errors: usize) -> bool {
+        self.chars += chars;
+        self.errors += errors;
+        self.current += 1;
+        self.current == self.lines.len()
+    }
+
+    /// Ends the exam at `now`, `chars` and `errors` are from the unfinished line
+    pub fn finish(&self, chars: usize, errors: usize, now: f64) -> ExamResult {
+        let started_at = self.started_at.unwrap_or(now);
+        ExamResult::sealed(
+            RecordMeta::new(js_sys::Date::now()),
+            (now - started_at).min(DURATION_MS),
+            self.chars + chars,
+            self.errors + errors,
+            self.backspace_limit,
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExamResult {
+    pub meta: RecordMeta,
+    pub millis: f64,
+    pub chars: usize,
+    /// Uncorrected mistakes
+    pub errors: usize,
+    pub backspace_limit: Option<usize>,
+    /// Checksum of the fields above, a mismatch means the record was edited
+    seal: u64,
+}
+
+impl ExamResult {
+    fn sealed(
+        meta: RecordMeta,
+        millis: f64,
+        chars: usize,
+        errors: usize,
+        backspace_limit: Option<usize>,
+    ) -> Self {
+        let mut result = ExamResult {
+            meta,
+            millis,
+            chars,
+            errors,
+            backspace_limit,
+            seal: 0,
+        };
+        result.seal = result.checksum();
+        result
+    }
+
+    /// FNV-1a of the fields, enough to detect hand edits
+    fn checksum(&self) -> u64 {
+        let fields = format!(
+            "{}|{}|{}|{}|{}|{:?}",
+            self.meta.id,
+            self.meta.created_at,
+            self.millis,
+            self.chars,
+            self.errors,
+            self.backspace_limit
+        );
+        fields.bytes().fold(0xcbf29ce484222325, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x100000001b3)
+        })
+    }
+
+    pub fn is_intact(&self) -> bool {
+        self.seal == self.checksum()
+    }
+
+    fn minutes(&self) -> f64 {
+        self.millis / 60_000.0
+    }
+
+    /// Typed characters in standard words per minute
+    pub fn gross_wpm(&self) -> f64 {
+        if self.minutes() <= 0.0 {
+            return 0.0;
+        }
+        self.chars as f64 / CHARS_PER_WORD / self.minutes()
+    }
+
+    /// Gross WPM less one word per minute for every uncorrected error
+    pub fn net_wpm(&self) -> f64 {
+        if self.minutes() <= 0.0 {
+            return 0.0;
+        }
+        (self.gross_wpm() - self.errors as f64 / self.minutes()).max(0.0)
+    }
+
+    /// Share of correctly typed characters, in percent
+    pub fn accuracy(&self) -> f64 {
+        if self.chars == 0 {
+            return 0.0;
+        }
+        100.0 * self.chars.saturating_sub(self.errors) as f64 / self.chars as f64
+    }
+}
+
+/// Exam results, oldest first. Only ever appended to.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ExamResults {
+    results: Vec<ExamResult>,
+}
+
+impl ExamResults {
+    pub fn load(store: &dyn StatsStore) -> Self {
+        codec::load(store, EXAM_STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String> {
+        codec::save(store, EXAM_STORAGE_KEY, self, compact)
+    }
+
+    pub fn push(&mut self, result: ExamResult) {
+        self.results.push(result);
+    }
+
+    pub fn results(&self) -> &[ExamResult] {
+        &self.results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 500 characters, 10 of them wrong, in two minutes
+    fn result() -> ExamResult {
+        ExamResult::sealed(RecordMeta::new(1.0), 120_000.0, 500, 10, Some(3))
+    }
+
+    #[test]
+    fn net_wpm_takes_a_word_off_per_error_and_minute() {
+        let result = result();
+        assert_eq!(result.gross_wpm(), 50.0);
+        assert_eq!(result.net_wpm(), 45.0);
+        assert_eq!(result.accuracy(), 98.0);
+        let empty = ExamResult::sealed(RecordMeta::new(1.0), 0.0, 0, 0, None);
+        assert_eq!((empty.gross_wpm(), empty.net_wpm()), (0.0, 0.0));
+        let sloppy = ExamResult::sealed(RecordMeta::new(1.0), 60_000.0, 50, 20, None);
+        assert_eq!(sloppy.net_wpm(), 0.0);
+    }
+
+    #[test]
+    fn edited_results_break_the_seal() {
+        let mut result = result();
+        assert!(result.is_intact());
+        let stored: ExamResult =
+            serde_json::from_str(&serde_json::to_string(&result).unwrap()).unwrap();
+        assert!(stored.is_intact());
+        result.errors = 0;
+        assert!(!result.is_intact());
+    }
+
+    #[test]
+    fn backspaces_run_out_at_the_limit() {
+        let mut exam = Exam::new(vec!["line".to_string()], Some(2));
+        assert!(exam.take_backspace());
+        assert!(exam.take_backspace());
+        assert!(!exam.take_backspace());
+        assert_eq!(exam.backspaces_left(), Some(0));
+        let mut unlimited = Exam::new(vec!["line".to_string()], None);
+        assert!((0..100).all(|_| unlimited.take_backspace()));
+        assert_eq!(unlimited.backspaces_left(), None);
+    }
+}
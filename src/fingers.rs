@@ -0,0 +1,148 @@
+//! Which finger types a character. Row-staggered boards and split
+//! columnar boards assign some keys to different fingers,
+//! and thumbs do more work on the latter.
+
+use serde::{Deserialize, Serialize};
+
+use crate::keycaps::Layout;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Finger {
+    pub hand: Hand,
+    /// 0 is the pinky, 3 the index finger, 4 the thumb
+    pub index: u8,
+}
+
+impl Hand {
+    pub fn name(self) -> &'static str {
+        match self {
+            Hand::Left => "left",
+            Hand::Right => "right",
+        }
+    }
+
+    pub fn other(self) -> Hand {
+        match self {
+            Hand::Left => Hand::Right,
+            Hand::Right => Hand::Left,
+        }
+    }
+}
+
+impl Finger {
+    pub fn message_key(self) -> String {
+        format!("finger-{}-{}", self.hand.name(), self.index)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum FingerMap {
+    /// Row-staggered keyboard, touch typing as taught
+    #[default]
+    Standard,
+    /// Corne, 3x6 keys and 3 thumb keys per half; digits and symbols
+    /// on the lower/raise layers of the default keymap, in the columns of the letters
+    Corne,
+    /// Ergodox EZ default layout
+    Ergodox,
+    /// Moonlander default layout
+    Moonlander,
+}
+
+type FingerTable = &'static [(Hand, u8, &'static str)];
+
+/// Keys per finger by what they type on QWERTY, shifted variants included
+const STANDARD: FingerTable = &[
+    (Hand::Left, 0, "`~1!qQaAzZ"),
+    (Hand::Left, 1, "2@wWsSxX"),
+    (Hand::Left, 2, "3#eEdDcC"),
+    (Hand::Left, 3, "4$5%rRtTfFgGvVbB"),
+    (Hand::Right, 3, "6^7&yYuUhHjJnNmM"),
+    (Hand::Right, 2, "8*iIkK,<"),
+    (Hand::Right, 1, "9(oOlL.>"),
+    (Hand::Right, 0, "0)-_=+pP[{]}\\|;:'\"/?"),
+    (Hand::Right, 4, " "),
+];
+
+const CORNE: FingerTable = &[
+    (Hand::Left, 0, "1!qQaAzZ"),
+    (Hand::Left, 1, "2@wWsSxX"),
+    (Hand::Left, 2, "3#eEdDcC"),
+    (Hand::Left, 3, "4$5%rRtTfFgGvVbB"),
+    (Hand::Left, 4, " "),
+    (Hand::Right, 3, "6^7&yYuUhHjJnNmM-_=+"),
+    (Hand::Right, 2, "8*iIkK,<[{"),
+    (Hand::Right, 1, "9(oOlL.>]}"),
+    (Hand::Right, 0, "0)pP;:'\"/?\\|`~"),
+];
+
+/// Ergodox and Moonlander share the layout of the letters and thumbs,
+/// the inner columns hold brackets and the outer ones `=`, `-`, `\` and `'`
+const ERGODOX: FingerTable = &[
+    (Hand::Left, 0, "=+1!qQaAzZ`~"),
+    (Hand::Left, 1, "2@wWsSxX"),
+    (Hand::Left, 2, "3#eEdDcC"),
+    (Hand::Left, 3, "4$5%rRtTfFgGvVbB[{"),
+    (Hand::Right, 3, "6^7&yYuUhHjJnNmM]}"),
+    (Hand::Right, 2, "8*iIkK,<"),
+    (Hand::Right, 1, "9(oOlL.>"),
+    (Hand::Right, 0, "0)-_pP;:'\"/?\\|"),
+    (Hand::Right, 4, " "),
+];
+
+const MOONLANDER: FingerTable = &[
+    (Hand::Left, 0, "=+1!qQaAzZ`~"),
+    (Hand::Left, 1, "2@wWsSxX"),
+    (Hand::Left, 2, "3#eEdDcC"),
+    (Hand::Left, 3, "4$5%rRtTfFgGvVbB[{"),
+    (Hand::Right, 3, "6^7&yYuUhHjJnNmM]}"),
+    (Hand::Right, 2, "8*iIkK,<"),
+    (Hand::Right, 1, "9(oOlL.>"),
+    (Hand::Right, 0, "0)-_pP;:'\"/?\\|"),
+    (Hand::Left, 4, " "),
+];
+
+impl FingerMap {
+    pub const ALL: [FingerMap; 4] = [
+        FingerMap::Standard,
+        FingerMap::Corne,
+        FingerMap::Ergodox,
+        FingerMap::Moonlander,
+    ];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            FingerMap::Standard => "finger-map-standard",
+            FingerMap::Corne => "finger-map-corne",
+            FingerMap::Ergodox => "finger-map-ergodox",
+            FingerMap::Moonlander => "finger-map-moonlander",
+        }
+    }
+
+    fn table(self) -> FingerTable {
+        match self {
+            FingerMap::Standard => STANDARD,
+            FingerMap::Corne => CORNE,
+            FingerMap::Ergodox => ERGODOX,
+            FingerMap::Moonlander => MOONLANDER,
+        }
+    }
+
+    /// Finger pressing the key that types `c` on `layout`
+    pub fn finger_for(self, layout: Layout, c: char) -> Option<Finger> {
+        let key = if c == ' ' { c } else { layout.to_qwerty(c)? };
+        self.table()
+            .iter()
+            .find(|(_h, _i, chars)| chars.contains(key))
+            .map(|(hand, index, _chars)| Finger {
+                hand: *hand,
+                index: *index,
+            })
+    }
+}
@@ -0,0 +1,32 @@
+//! Browser fullscreen, used together with the zen layout.
+//! Browsers only allow entering it from a click or a key press.
+
+use gloo_utils::{document, document_element};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use yew::Callback;
+
+/// Key that toggles fullscreen, F11 is taken by the browser's own fullscreen
+pub const FULLSCREEN_KEY: &str = "F9";
+
+pub fn is_active() -> bool {
+    document().fullscreen_element().is_some()
+}
+
+pub fn enter() {
+    if let Err(e) = document_element().request_fullscreen() {
+        tracing::warn!("Fullscreen was refused: {e:?}");
+    }
+}
+
+pub fn exit() {
+    document().exit_fullscreen();
+}
+
+/// Calls `on_change` with whether the page is fullscreen now,
+/// also when the user leaves it with Escape
+pub fn watch(on_change: Callback<bool>) {
+    let cb: Closure<dyn Fn()> = Closure::new(move || on_change.emit(is_active()));
+    document()
+        .add_event_listener_with_callback("fullscreenchange", cb.into_js_value().unchecked_ref())
+        .unwrap();
+}
@@ -0,0 +1,87 @@
+//! Sessions started on purpose: the user picks a goal, the practice page
+//! shows how close it is, and reaching it ends the session with a summary.
+
+/// How often the progress of a session with a time goal is updated
+pub const TICK_MS: u32 = 1000;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Goal {
+    Minutes(u32),
+    Prompts(usize),
+    /// Until the course introduces its next key
+    CourseStage,
+}
+
+impl Goal {
+    pub const ALL: [Goal; 3] = [Goal::Minutes(10), Goal::Prompts(20), Goal::CourseStage];
+}
+
+pub struct GoalSession {
+    pub goal: Goal,
+    /// Unix time in ms
+    pub started_at: f64,
+    /// Prompts completed, keystrokes logged and backspaces before the session
+    pub prompts_before: usize,
+    pub log_before: usize,
+    pub backspaces_before: usize,
+    pub stage_done: bool,
+}
+
+impl GoalSession {
+    pub fn new(
+        goal: Goal,
+        now: f64,
+        prompts_done: usize,
+        log_len: usize,
+        backspaces: usize,
+    ) -> Self {
+        GoalSession {
+            goal,
+            started_at: now,
+            prompts_before: prompts_done,
+            log_before: log_len,
+            backspaces_before: backspaces,
+            stage_done: false,
+        }
+    }
+
+    pub fn prompts(&self, prompts_done: usize) -> usize {
+        prompts_done.saturating_sub(self.prompts_before)
+    }
+
+    /// Share of the goal reached, from 0 to 1. A course stage only counts
+    /// once it is passed, `course_share` is how far its check is.
+    pub fn progress(&self, now: f64, prompts_done: usize, course_share: f64) -> f64 {
+        let share = match self.goal {
+            Goal::Minutes(minutes) => (now - self.started_at) / (f64::from(minutes) * 60_000.0),
+            Goal::Prompts(prompts) => self.prompts(prompts_done) as f64 / prompts as f64,
+            Goal::CourseStage if self.stage_done => 1.0,
+            Goal::CourseStage => course_share.min(0.99),
+        };
+        share.clamp(0.0, 1.0)
+    }
+
+    pub fn reached(&self, now: f64, prompts_done: usize) -> bool {
+        self.progress(now, prompts_done, 0.0) >= 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goals_are_reached() {
+        let minutes = GoalSession::new(Goal::Minutes(10), 1000.0, 3, 0, 0);
+        assert_eq!(minutes.progress(1000.0 + 300_000.0, 3, 0.0), 0.5);
+        assert!(minutes.reached(1000.0 + 600_000.0, 3));
+        let prompts = GoalSession::new(Goal::Prompts(20), 0.0, 5, 0, 0);
+        assert_eq!(prompts.progress(0.0, 15, 0.0), 0.5);
+        assert!(prompts.reached(0.0, 25));
+        let mut stage = GoalSession::new(Goal::CourseStage, 0.0, 0, 0, 0);
+        assert_eq!(stage.progress(0.0, 0, 1.0), 0.99);
+        assert!(!stage.reached(1e12, 1000));
+        stage.stage_done = true;
+        assert!(stage.reached(0.0, 0));
+    }
+}
@@ -0,0 +1,98 @@
+//! Long hesitations in the keystrokes of the session: those slower than
+//! most, with the characters around them, to see what makes typing stop.
+
+use crate::analytics::Keystroke;
+
+/// Characters shown on each side of a hesitation
+const CONTEXT_CHARS: usize = 6;
+/// Hesitations listed, the longest first
+pub const HESITATIONS_LISTED: usize = 10;
+
+/// Latency at `percentile` of the keystrokes with one, `None` without any
+pub fn threshold(log: &[Keystroke], percentile: f64) -> Option<f64> {
+    let mut latencies: Vec<f64> = log.iter().filter_map(|k| k.latency).collect();
+    if latencies.is_empty() {
+        return None;
+    }
+    latencies.sort_by(f64::total_cmp);
+    let rank = (percentile / 100.0 * (latencies.len() - 1) as f64).round() as usize;
+    Some(latencies[rank.min(latencies.len() - 1)])
+}
+
+/// Whether the keystroke took longer than `threshold`
+pub fn is_hesitation(keystroke: &Keystroke, threshold: f64) -> bool {
+    keystroke.latency.is_some_and(|latency| latency > threshold)
+}
+
+pub struct Hesitation {
+    pub latency: f64,
+    /// Typed before it in the same prompt
+    pub before: String,
+    pub key: char,
+    /// Typed after it in the same prompt
+    pub after: String,
+}
+
+/// Keystrokes slower than `threshold` with their context, the longest first.
+/// A keystroke without a latency starts a prompt, contexts do not cross it.
+pub fn hesitations(log: &[Keystroke], threshold: f64) -> Vec<Hesitation> {
+    let mut hesitations: Vec<Hesitation> = log
+        .iter()
+        .enumerate()
+        .filter(|(_, k)| is_hesitation(k, threshold))
+        .map(|(i, k)| {
+            let start = log[..i]
+                .iter()
+                .rposition(|k| k.latency.is_none())
+                .unwrap_or(0)
+                .max(i.saturating_sub(CONTEXT_CHARS));
+            let end = log[i + 1..]
+                .iter()
+                .position(|k| k.latency.is_none())
+                .map_or(log.len(), |p| i + 1 + p)
+                .min(i + 1 + CONTEXT_CHARS);
+            Hesitation {
+                latency: k.latency.unwrap_or_default(),
+                before: log[start..i].iter().map(|k| k.expected).collect(),
+                key: k.expected,
+                after: log[i + 1..end].iter().map(|k| k.expected).collect(),
+            }
+        })
+        .collect();
+    hesitations.sort_by(|a, b| b.latency.total_cmp(&a.latency));
+    hesitations.truncate(HESITATIONS_LISTED);
+    hesitations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(text: &str, latencies: &[Option<f64>]) -> Vec<Keystroke> {
+        text.chars()
+            .zip(latencies)
+            .map(|(c, latency)| Keystroke {
+                expected: c,
+                typed: c,
+                latency: *latency,
+                previous: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn contexts_stay_within_the_prompt() {
+        let log = log(
+            "abcxy",
+            &[None, Some(100.0), Some(900.0), None, Some(100.0)],
+        );
+        assert_eq!(threshold(&log, 50.0), Some(100.0));
+        let hesitations = hesitations(&log, 100.0);
+        assert_eq!(hesitations.len(), 1);
+        let h = &hesitations[0];
+        assert_eq!(
+            (h.before.as_str(), h.key, h.after.as_str()),
+            ("ab", 'c', "")
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! A hint with the rules of a mode, shown the first time the mode is opened
+//! until dismissed. The modes whose hints were dismissed are kept in LocalStorage.
+
+use std::collections::BTreeSet;
+
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+const SEEN_HINTS_STORAGE_KEY: &str = "seen_hints";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Mode {
+    WarmUp,
+    Drill,
+    Exam,
+    DataEntry,
+    Timed,
+    Book,
+    Curriculum,
+    Discovery,
+    Code,
+}
+
+impl Mode {
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Mode::WarmUp => "hint-warm-up",
+            Mode::Drill => "hint-drill",
+            Mode::Exam => "hint-exam",
+            Mode::DataEntry => "hint-data-entry",
+            Mode::Timed => "hint-timed",
+            Mode::Book => "hint-book",
+            Mode::Curriculum => "hint-curriculum",
+            Mode::Discovery => "hint-discovery",
+            Mode::Code => "hint-code",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct SeenHints(BTreeSet<Mode>);
+
+impl SeenHints {
+    pub fn load() -> Self {
+        LocalStorage::get(SEEN_HINTS_STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn contains(&self, mode: Mode) -> bool {
+        self.0.contains(&mode)
+    }
+
+    pub fn insert(&mut self, mode: Mode) {
+        self.0.insert(mode);
+        self.save();
+    }
+
+    /// Shows every hint again
+    pub fn clear(&mut self) {
+        self.0.clear();
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(e) = LocalStorage::set(SEEN_HINTS_STORAGE_KEY, self) {
+            tracing::warn!("Could not save the dismissed hints: {e}");
+        }
+    }
+}
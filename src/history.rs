@@ -0,0 +1,165 @@
+//! Results of completed prompts, kept to follow progress over time.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use typing_core::{
+    ids::{RecordMeta, Ulid},
+    DAY_MS,
+};
+
+use crate::{
+    codec,
+    keyboards::Keyboard,
+    store::StatsStore,
+    units::{SpeedSample, SpeedUnit},
+};
+
+pub const HISTORY_STORAGE_KEY: &str = "history";
+const WEEK_MS: f64 = 7.0 * DAY_MS;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct PromptResult {
+    pub meta: RecordMeta,
+    pub chars: usize,
+    pub words: usize,
+    pub keystrokes: usize,
+    /// From the first to the last keystroke
+    pub millis: f64,
+    pub mistakes: usize,
+    /// The user's own annotation, like "new keyboard"
+    #[serde(default)]
+    pub note: String,
+    /// The keyboard that was active
+    #[serde(default)]
+    pub keyboard: Option<Ulid>,
+    /// Automatic tags like `mode:drill` and the user's session tags
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    /// Completed below the minimum accuracy, so it is no personal best
+    #[serde(default)]
+    pub sloppy: bool,
+}
+
+/// Share of keystrokes that were not mistakes, in percent
+pub fn accuracy(keystrokes: usize, mistakes: usize) -> f64 {
+    100.0 * keystrokes.saturating_sub(mistakes) as f64 / keystrokes.max(1) as f64
+}
+
+impl PromptResult {
+    pub fn speed_sample(&self) -> SpeedSample {
+        SpeedSample {
+            chars: self.chars,
+            words: self.words,
+            keystrokes: self.keystrokes,
+            millis: self.millis,
+        }
+    }
+
+    /// Stored tags and the keyboard one, which follows keyboard renames
+    pub fn all_tags(&self, keyboards: &[Keyboard]) -> BTreeSet<String> {
+        let keyboard = self
+            .keyboard
+            .and_then(|id| keyboards.iter().find(|k| k.id == id))
+            .map(|k| format!("keyboard:{}", k.name));
+        self.tags.iter().cloned().chain(keyboard).collect()
+    }
+}
+
+/// Start of a week (Unix time in ms) and the speed over its results
+pub type WeeklySpeed = Vec<(f64, f64)>;
+
+/// Results having all of `tags`, every history view goes through it
+pub struct HistoryQuery<'a> {
+    pub tags: &'a BTreeSet<String>,
+    pub keyboards: &'a [Keyboard],
+}
+
+impl HistoryQuery<'_> {
+    pub fn matches(&self, result: &PromptResult) -> bool {
+        self.tags.is_empty() || result.all_tags(self.keyboards).is_superset(self.tags)
+    }
+}
+
+/// Results, oldest first
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct History {
+    pub results: Vec<PromptResult>,
+}
+
+impl History {
+    pub fn load(store: &dyn StatsStore) -> Self {
+        codec::load(store, HISTORY_STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String> {
+        codec::save(store, HISTORY_STORAGE_KEY, self, compact)
+    }
+
+    pub fn query<'a>(
+        &'a self,
+        query: &'a HistoryQuery,
+    ) -> impl DoubleEndedIterator<Item = &'a PromptResult> {
+        self.results.iter().filter(|r| query.matches(r))
+    }
+
+    /// The fastest result that was not sloppy
+    pub fn personal_best(&self, query: &HistoryQuery, unit: SpeedUnit) -> Option<&PromptResult> {
+        self.results
+            .iter()
+            .filter(|r| query.matches(r) && !r.sloppy)
+            .max_by(|a, b| {
+                unit.rate(&a.speed_sample())
+                    .total_cmp(&unit.rate(&b.speed_sample()))
+            })
+    }
+
+    /// Speed over the results of each week that has any, oldest first
+    pub fn weekly_speed(&self, query: &HistoryQuery, unit: SpeedUnit) -> WeeklySpeed {
+        let mut weeks: BTreeMap<i64, SpeedSample> = BTreeMap::new();
+        for r in self.query(query).filter(|r| !r.sloppy) {
+            let sample = r.speed_sample();
+            let week = weeks
+                .entry((r.meta.created_at / WEEK_MS).floor() as i64)
+                .or_insert(SpeedSample {
+                    chars: 0,
+                    words: 0,
+                    keystrokes: 0,
+                    millis: 0.0,
+                });
+            week.chars += sample.chars;
+            week.words += sample.words;
+            week.keystrokes += sample.keystrokes;
+            week.millis += sample.millis;
+        }
+        weeks
+            .into_iter()
+            .map(|(week, sample)| (week as f64 * WEEK_MS, unit.rate(&sample)))
+            .collect()
+    }
+
+    /// Tags of all results, to filter by
+    pub fn known_tags(&self, keyboards: &[Keyboard]) -> BTreeSet<String> {
+        self.results
+            .iter()
+            .flat_map(|r| r.all_tags(keyboards))
+            .collect()
+    }
+
+    /// Characters per ms over the last `n` results
+    pub fn recent_pace(&self, n: usize) -> Option<f64> {
+        let (chars, millis) = self
+            .results
+            .iter()
+            .rev()
+            .take(n)
+            .fold((0, 0.0), |(chars, millis), r| {
+                (chars + r.chars, millis + r.millis)
+            });
+        (millis > 0.0).then(|| chars as f64 / millis)
+    }
+
+    pub fn get_mut(&mut self, id: Ulid) -> Option<&mut PromptResult> {
+        self.results.iter_mut().find(|r| r.meta.id == id)
+    }
+}
@@ -0,0 +1,112 @@
+//! UI strings. Messages live in Fluent-like `assets/locales/*.ftl` resources:
+//! one `key = value` per line, `{ $name }` placeholders and `#` comments.
+//! English is compiled in, other packs are fetched when selected,
+//! keys missing from them fall back to English.
+
+use std::{cell::RefCell, collections::HashMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+const EN: &str = include_str!("../assets/locales/en.ftl");
+
+thread_local! {
+    static FALLBACK: Bundle = Bundle::parse(EN);
+    static BUNDLE: RefCell<Bundle> = RefCell::new(Bundle::parse(EN));
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Language {
+    En,
+    Ru,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::En, Language::Ru];
+
+    /// Asset with the language pack, `None` for the built-in English
+    pub fn asset(self) -> Option<&'static str> {
+        match self {
+            Language::En => None,
+            Language::Ru => Some("locales/ru.ftl"),
+        }
+    }
+
+    /// BCP 47 tag of the language
+    pub fn tag(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Ru => "ru",
+        }
+    }
+
+    /// Name of the language in that language
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::En => "English",
+            Language::Ru => "Русский",
+        }
+    }
+
+    /// Picks a language pack for a BCP 47 tag like "ru-RU", if there is one
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        let primary = tag.split('-').next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Language::En),
+            "ru" => Some(Language::Ru),
+            _ => None,
+        }
+    }
+
+    /// Language of the browser UI, English if there is no pack for it
+    pub fn detect() -> Language {
+        gloo_utils::window()
+            .navigator()
+            .language()
+            .and_then(|tag| Language::from_tag(&tag))
+            .unwrap_or(Language::En)
+    }
+}
+
+struct Bundle {
+    messages: HashMap<String, String>,
+}
+
+impl Bundle {
+    fn parse(resource: &str) -> Self {
+        let messages = resource
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .filter_map(|l| l.split_once('='))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .collect();
+        Bundle { messages }
+    }
+}
+
+/// Switches all subsequent lookups to the built-in English
+pub fn set_english() {
+    set_resource(EN);
+}
+
+/// Switches all subsequent lookups to a fetched language pack
+pub fn set_resource(resource: &str) {
+    BUNDLE.with(|b| *b.borrow_mut() = Bundle::parse(resource));
+}
+
+/// Looks up a message, falling back to English and then to the key itself
+pub fn t(key: &str) -> String {
+    t_args(key, &[])
+}
+
+/// Looks up a message and substitutes its `{ $name }` placeholders
+pub fn t_args(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = BUNDLE
+        .with(|b| b.borrow().messages.get(key).cloned())
+        .or_else(|| FALLBACK.with(|f| f.messages.get(key).cloned()))
+        .unwrap_or_else(|| key.to_string());
+    for (name, value) in args {
+        message = message.replace(&format!("{{ ${name} }}"), &value.to_string());
+    }
+    message
+}
@@ -0,0 +1,113 @@
+//! Custom text import with a check of its characters
+//! against the practiced set.
+
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use crate::{
+    charset::{self, Inventory},
+    drill,
+    i18n::{t, t_args},
+    normalize::{self, Normalization, NormalizationOptions},
+};
+
+#[derive(Properties, PartialEq)]
+pub struct ImportProps {
+    pub expected_chars: HashSet<char>,
+    /// Asks to add characters to the practiced set
+    pub on_extend: Callback<Vec<char>>,
+    /// Asks to practice the given text
+    pub on_practice: Callback<String>,
+    /// Asks to drill the given sentences until they are typed cleanly
+    pub on_drill: Callback<Vec<String>>,
+    /// Asks to type the given text over many sessions, a passage at a time
+    pub on_open_book: Callback<String>,
+    pub normalization: Normalization,
+    pub on_normalize: Callback<Normalization>,
+}
+
+/// Space-separated characters, with the space itself made visible
+fn char_list(chars: &[char]) -> String {
+    chars
+        .iter()
+        .map(|c| if *c == ' ' { '␣' } else { *c })
+        .join(" ")
+}
+
+#[function_component]
+pub fn ImportText(props: &ImportProps) -> Html {
+    let text = use_state(String::new);
+    let oninput = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let area: HtmlTextAreaElement = e.target_unchecked_into();
+            text.set(area.value());
+        })
+    };
+
+    // Checked and practiced as it is once normalized
+    let (normalized, substitutions) = props.normalization.apply(&text, &props.expected_chars);
+    let inventory = Inventory::of(&normalized);
+    let missing = inventory.missing_from(&props.expected_chars);
+    let off_layout = inventory.off_layout();
+
+    let on_extend = {
+        let missing = missing.clone();
+        props.on_extend.reform(move |_: MouseEvent| missing.clone())
+    };
+    let on_practice = {
+        let text = normalized.clone();
+        props
+            .on_practice
+            .reform(move |_: MouseEvent| charset::normalize_whitespace(&text))
+    };
+    let on_open_book = {
+        let text = normalized.clone();
+        props.on_open_book.reform(move |_: MouseEvent| text.clone())
+    };
+    let on_drill = {
+        let text = normalized.clone();
+        props.on_drill.reform(move |_: MouseEvent| {
+            drill::split_sentences(&charset::normalize_whitespace(&text))
+        })
+    };
+
+    html!(
+        <details>
+            <summary>{t("custom-text")}</summary>
+            <textarea rows="6" value={(*text).clone()} {oninput} /> <br />
+            <NormalizationOptions value={props.normalization} on_change={props.on_normalize.clone()} />
+            {(!substitutions.is_empty()).then(|| html!(<p>{t_args("normalize-replaced", &[
+                ("substitutions", &normalize::describe(&substitutions)),
+            ])}</p>))}
+            {(inventory.distinct() > 0).then(|| html!(<>
+                {t_args("charset-summary", &[
+                    ("count", &inventory.distinct()),
+                    ("script", &inventory.script().map(|s| t(s.message_key())).unwrap_or_default()),
+                ])}
+                <br />
+            </>))}
+            {(!off_layout.is_empty()).then(|| html!(
+                <p class="flagged">{t_args("charset-off-layout", &[("chars", &char_list(&off_layout))])}</p>
+            ))}
+            {(!missing.is_empty()).then(|| html!(<p>
+                {t_args("charset-missing", &[("chars", &char_list(&missing))])}
+                {" "}<button onclick={on_extend}>{t("charset-extend")}</button>
+            </p>))}
+            <button onclick={on_practice} disabled={inventory.distinct() == 0}>
+                {t("practice-this-text")}
+            </button>
+            {" "}
+            <button onclick={on_drill} disabled={inventory.distinct() == 0}>
+                {t("drill-this-text")}
+            </button>
+            {" "}
+            <button onclick={on_open_book} disabled={inventory.distinct() == 0}>
+                {t("book-this-text")}
+            </button>
+        </details>
+    )
+}
@@ -0,0 +1,127 @@
+//! Debug builds expose `window.typingTutor` to drive the practice page from
+//! browser tests, such as wasm-bindgen-test ones:
+//! - `type(keys, intervalMs)` presses `keys` one at a time through the same
+//!   path as keyboard events, and resolves once the last one is sent.
+//!   Keys are characters, or key names in braces like `{Enter}` and
+//!   `{Backspace}`; `{{` is a brace.
+//! - `state()` resolves to a snapshot of the practice: the prompt, how much
+//!   of it is typed, the mode and how many prompts were completed.
+
+use gloo_timers::future::TimeoutFuture;
+use js_sys::{Function, Object, Promise, Reflect};
+use serde::Serialize;
+use wasm_bindgen::{prelude::Closure, JsValue};
+use yew::html::Scope;
+
+use crate::{key_input::KeyInput, startup, Msg, Practice};
+
+pub const GLOBAL: &str = "typingTutor";
+/// Between injected keys when the test does not say, slower than the
+/// input guard's bursts
+const DEFAULT_INTERVAL_MS: u32 = 50;
+
+#[derive(Serialize)]
+pub struct Snapshot {
+    pub route: String,
+    pub mode: Option<String>,
+    pub prompt: String,
+    /// Characters of the prompt typed
+    pub typed: usize,
+    pub mistakes: usize,
+    pub keystrokes: usize,
+    pub prompts_done: usize,
+    pub results: usize,
+}
+
+impl Snapshot {
+    /// Resolves a `state()` promise with the snapshot as an object
+    pub fn resolve(&self, resolve: &Function) {
+        let state = serde_json::to_string(self)
+            .map_err(|e| JsValue::from(e.to_string()))
+            .and_then(|json| js_sys::JSON::parse(&json));
+        match state {
+            Ok(state) => drop(resolve.call1(&JsValue::NULL, &state)),
+            Err(e) => tracing::warn!("Could not take a snapshot: {e:?}"),
+        }
+    }
+}
+
+/// Key names of a `type` sequence
+pub fn parse_keys(keys: &str) -> Result<Vec<String>, String> {
+    let mut parsed = vec![];
+    let mut chars = keys.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' || chars.next_if_eq(&'{').is_some() {
+            parsed.push(c.to_string());
+            continue;
+        }
+        let mut name = String::new();
+        loop {
+            match chars.next() {
+                Some('}') => break,
+                Some(c) => name.push(c),
+                None => return Err(format!("unclosed key name in {keys:?}")),
+            }
+        }
+        if name.is_empty() {
+            return Err(format!("empty key name in {keys:?}"));
+        }
+        parsed.push(name);
+    }
+    Ok(parsed)
+}
+
+/// Sets up `window.typingTutor` for the component of `link`
+pub fn install(link: Scope<Practice>) {
+    let api = Object::new();
+    let type_link = link.clone();
+    let type_keys = Closure::<dyn Fn(String, JsValue) -> Promise>::new(
+        move |keys: String, interval: JsValue| {
+            let keys = match parse_keys(&keys) {
+                Ok(keys) => keys,
+                Err(e) => return Promise::reject(&e.into()),
+            };
+            let interval = interval
+                .as_f64()
+                .map_or(DEFAULT_INTERVAL_MS, |ms| ms as u32);
+            let link = type_link.clone();
+            wasm_bindgen_futures::future_to_promise(async move {
+                for key in keys {
+                    TimeoutFuture::new(interval).await;
+                    link.send_message(Msg::KeyPress(KeyInput::injected(key, startup::now())));
+                }
+                Ok(JsValue::UNDEFINED)
+            })
+        },
+    );
+    let state = Closure::<dyn Fn() -> Promise>::new(move || {
+        Promise::new(&mut |resolve: Function, _reject| {
+            link.send_message(Msg::InjectionState(resolve));
+        })
+    });
+    let set = |name: &str, value: JsValue| {
+        if let Err(e) = Reflect::set(&api, &name.into(), &value) {
+            tracing::warn!("Could not set up the injection API: {e:?}");
+        }
+    };
+    set("type", type_keys.into_js_value());
+    set("state", state.into_js_value());
+    if let Err(e) = Reflect::set(&gloo_utils::window(), &GLOBAL.into(), &api) {
+        tracing::warn!("Could not set up the injection API: {e:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_names_in_braces() {
+        assert_eq!(
+            parse_keys("a {Backspace}{{b{Enter}").unwrap(),
+            vec!["a", " ", "Backspace", "{", "b", "Enter"]
+        );
+        assert!(parse_keys("a{}").is_err());
+        assert!(parse_keys("a{Enter").is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Detection of input that was not typed by hand: clipboard pastes,
+//! script-dispatched key events and bursts of keys too fast for a human.
+
+/// Keys closer together than this (in milliseconds) count as "too fast".
+const MIN_HUMAN_INTERVAL_MS: f64 = 8.0;
+/// How many too-fast keys in a row make a burst.
+/// Single near-simultaneous keydowns happen with key rollover, long runs do not.
+const BURST_LEN: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Suspicion {
+    Paste,
+    Synthetic,
+    Burst,
+}
+
+impl Suspicion {
+    /// Message key of the human-readable reason
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Suspicion::Paste => "suspicion-paste",
+            Suspicion::Synthetic => "suspicion-synthetic",
+            Suspicion::Burst => "suspicion-burst",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InputGuard {
+    last_key_at: Option<f64>,
+    fast_streak: usize,
+}
+
+impl InputGuard {
+    /// Inspects a keystroke, `timestamp` is in milliseconds.
+    /// Returns a reason if the keystroke should not be scored.
+    pub fn check_key(&mut self, is_trusted: bool, timestamp: f64) -> Option<Suspicion> {
+        if !is_trusted {
+            return Some(Suspicion::Synthetic);
+        }
+        let fast = self
+            .last_key_at
+            .is_some_and(|last| timestamp - last < MIN_HUMAN_INTERVAL_MS);
+        self.last_key_at = Some(timestamp);
+        if fast {
+            self.fast_streak += 1;
+        } else {
+            self.fast_streak = 0;
+        }
+        (self.fast_streak + 1 >= BURST_LEN).then_some(Suspicion::Burst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untrusted_events_are_synthetic() {
+        let mut guard = InputGuard::default();
+        assert_eq!(guard.check_key(false, 0.0), Some(Suspicion::Synthetic));
+        assert_eq!(guard.check_key(true, 1000.0), None);
+    }
+
+    #[test]
+    fn only_runs_of_fast_keys_are_bursts() {
+        let mut guard = InputGuard::default();
+        // Rollover: a single key right after another
+        assert_eq!(guard.check_key(true, 0.0), None);
+        assert_eq!(guard.check_key(true, 2.0), None);
+        assert_eq!(guard.check_key(true, 200.0), None);
+        let burst: Vec<_> = (1..=4)
+            .map(|i| guard.check_key(true, 200.0 + f64::from(i)))
+            .collect();
+        assert_eq!(
+            burst,
+            [None, None, Some(Suspicion::Burst), Some(Suspicion::Burst)]
+        );
+        // A human pause ends the burst
+        assert_eq!(guard.check_key(true, 400.0), None);
+    }
+}
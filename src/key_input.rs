@@ -0,0 +1,75 @@
+//! A key press as the practice page handles it. Keyboard events and the
+//! keys injected in debug builds (see `injection`) take the same path
+//! through `update`.
+
+use web_sys::KeyboardEvent;
+
+use crate::numpad;
+
+pub struct KeyInput {
+    key: String,
+    /// Milliseconds since the page was loaded
+    time_stamp: f64,
+    trusted: bool,
+    caps_lock: bool,
+    /// `KeyboardEvent.location`
+    location: u32,
+    /// `None` for an injected key, which has no default action to prevent
+    event: Option<KeyboardEvent>,
+}
+
+impl KeyInput {
+    /// `key` as named by `KeyboardEvent.key`, pressed at `time_stamp` with
+    /// CapsLock off. It passes the input guard like a key typed by hand.
+    #[cfg(debug_assertions)]
+    pub fn injected(key: String, time_stamp: f64) -> Self {
+        KeyInput {
+            key,
+            time_stamp,
+            trusted: true,
+            caps_lock: false,
+            location: 0,
+            event: None,
+        }
+    }
+
+    pub fn key(&self) -> String {
+        self.key.clone()
+    }
+
+    pub fn time_stamp(&self) -> f64 {
+        self.time_stamp
+    }
+
+    pub fn is_trusted(&self) -> bool {
+        self.trusted
+    }
+
+    pub fn caps_lock(&self) -> bool {
+        self.caps_lock
+    }
+
+    /// Whether the key is on the numeric keypad
+    pub fn on_numpad(&self) -> bool {
+        self.location == numpad::LOCATION_NUMPAD
+    }
+
+    pub fn prevent_default(&self) {
+        if let Some(event) = &self.event {
+            event.prevent_default();
+        }
+    }
+}
+
+impl From<KeyboardEvent> for KeyInput {
+    fn from(event: KeyboardEvent) -> Self {
+        KeyInput {
+            key: event.key(),
+            time_stamp: event.time_stamp(),
+            trusted: event.is_trusted(),
+            caps_lock: event.get_modifier_state("CapsLock"),
+            location: event.location(),
+            event: Some(event),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Physical keyboards the user practices on. Results are tagged with
+//! the active one, since speed differs a lot between boards.
+
+use serde::{Deserialize, Serialize};
+use typing_core::ids::Ulid;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::{history::PromptResult, i18n::t, units::SpeedSample};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Keyboard {
+    pub id: Ulid,
+    pub name: String,
+}
+
+/// Totals over the results typed on one keyboard
+pub struct KeyboardStats {
+    pub results: usize,
+    pub sample: SpeedSample,
+    pub accuracy: f64,
+}
+
+/// Sums up results typed on `keyboard`, `None` meaning untagged results
+pub fn stats_for<'a>(
+    results: impl Iterator<Item = &'a PromptResult>,
+    keyboard: Option<Ulid>,
+) -> KeyboardStats {
+    let mut stats = KeyboardStats {
+        results: 0,
+        sample: SpeedSample {
+            chars: 0,
+            words: 0,
+            keystrokes: 0,
+            millis: 0.0,
+        },
+        accuracy: 0.0,
+    };
+    let mut mistakes = 0;
+    for r in results.filter(|r| r.keyboard == keyboard) {
+        stats.results += 1;
+        stats.sample.chars += r.chars;
+        stats.sample.words += r.words;
+        stats.sample.keystrokes += r.keystrokes;
+        stats.sample.millis += r.millis;
+        mistakes += r.mistakes;
+    }
+    stats.accuracy = stats.sample.chars as f64 / (stats.sample.chars + mistakes).max(1) as f64;
+    stats
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PickerProps {
+    pub keyboards: Vec<Keyboard>,
+    pub active: Option<Ulid>,
+    pub on_select: Callback<Option<Ulid>>,
+    /// Registers a keyboard with the given name
+    pub on_add: Callback<String>,
+}
+
+#[function_component]
+pub fn KeyboardPicker(props: &PickerProps) -> Html {
+    let name = use_state(String::new);
+    let onchange = {
+        let keyboards = props.keyboards.clone();
+        props.on_select.reform(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            // The first option is "not specified"
+            (select.selected_index() as usize)
+                .checked_sub(1)
+                .and_then(|i| keyboards.get(i))
+                .map(|k| k.id)
+        })
+    };
+    let oninput = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+    let onclick = {
+        let name = name.clone();
+        let on_add = props.on_add.clone();
+        Callback::from(move |_| {
+            if !name.trim().is_empty() {
+                on_add.emit(name.trim().to_string());
+                name.set(String::new());
+            }
+        })
+    };
+    html!(<span>
+        {t("keyboard")}{" "}
+        <select {onchange}>
+            <option selected={props.active.is_none()}>{t("keyboard-unspecified")}</option>
+            {for props.keyboards.iter().map(|k| html!(
+                <option selected={Some(k.id) == props.active}>{&k.name}</option>
+            ))}
+        </select>
+        {" "}
+        <input type="text" size="12" value={(*name).clone()} placeholder={t("keyboard-new")} {oninput} />
+        <button {onclick}>{t("keyboard-add")}</button>
+    </span>)
+}
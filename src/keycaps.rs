@@ -0,0 +1,172 @@
+//! Keyboard layouts as rows of keycaps, for everything that deals with
+//! physical keys: the on-screen keyboards, finger assignments and key discovery.
+
+use serde::{Deserialize, Serialize};
+
+/// Number, top, home and bottom rows
+pub const ROWS: usize = 4;
+
+/// A key and the characters it types
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Keycap {
+    pub base: char,
+    /// With shift held
+    pub shifted: char,
+}
+
+/// Layouts by the unshifted characters on the number,
+/// top, home and bottom rows of an ANSI board
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Layout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+    Workman,
+}
+
+impl Layout {
+    pub const ALL: [Layout; 4] = [
+        Layout::Qwerty,
+        Layout::Dvorak,
+        Layout::Colemak,
+        Layout::Workman,
+    ];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Layout::Qwerty => "layout-qwerty",
+            Layout::Dvorak => "layout-dvorak",
+            Layout::Colemak => "layout-colemak",
+            Layout::Workman => "layout-workman",
+        }
+    }
+
+    /// Tag of results typed on the layout
+    pub fn tag(self) -> &'static str {
+        match self {
+            Layout::Qwerty => "layout:qwerty",
+            Layout::Dvorak => "layout:dvorak",
+            Layout::Colemak => "layout:colemak",
+            Layout::Workman => "layout:workman",
+        }
+    }
+
+    /// Unshifted characters of each row, [`Layout::keycaps`] adds the shifted ones
+    pub fn rows(self) -> [&'static str; ROWS] {
+        match self {
+            Layout::Qwerty => [
+                "`1234567890-=",
+                "qwertyuiop[]\\",
+                "asdfghjkl;'",
+                "zxcvbnm,./",
+            ],
+            Layout::Dvorak => [
+                "`1234567890[]",
+                "',.pyfgcrl/=\\",
+                "aoeuidhtns-",
+                ";qjkxbmwvz",
+            ],
+            Layout::Colemak => [
+                "`1234567890-=",
+                "qwfpgjluy;[]\\",
+                "arstdhneio'",
+                "zxcvbkm,./",
+            ],
+            Layout::Workman => [
+                "`1234567890-=",
+                "qdrwbjfup;[]\\",
+                "ashtgyneoi'",
+                "zxmcvkl,./",
+            ],
+        }
+    }
+
+    /// Characters to discover: letters and punctuation, digits stay where they were
+    pub fn chars(self) -> Vec<char> {
+        self.rows()
+            .iter()
+            .flat_map(|row| row.chars())
+            .filter(|c| !c.is_ascii_digit())
+            .collect()
+    }
+
+    pub fn keycaps(self) -> [Vec<Keycap>; ROWS] {
+        self.rows().map(|row| {
+            row.chars()
+                .map(|base| Keycap {
+                    base,
+                    shifted: shifted(base),
+                })
+                .collect()
+        })
+    }
+
+    /// Row and column of the key typing `c`, and whether it needs shift
+    pub fn key_of(self, c: char) -> Option<(usize, usize, bool)> {
+        let (unshifted, shift) = match SHIFTED.iter().find(|(_, s)| *s == c) {
+            Some((u, _)) => (*u, true),
+            None if c.is_ascii_uppercase() => (c.to_ascii_lowercase(), true),
+            None => (c, false),
+        };
+        self.rows().iter().enumerate().find_map(|(row, keys)| {
+            let column = keys.chars().position(|k| k == unshifted)?;
+            Some((row, column, shift))
+        })
+    }
+
+    /// The character typed by the same key with the same shift on QWERTY,
+    /// tables of physical keys are written for it
+    pub fn to_qwerty(self, c: char) -> Option<char> {
+        let (row, column, shift) = self.key_of(c)?;
+        let cap = Layout::Qwerty.keycaps()[row][column];
+        Some(if shift { cap.shifted } else { cap.base })
+    }
+}
+
+/// The character the key of `c` types with shift held
+pub fn shifted(c: char) -> char {
+    SHIFTED
+        .iter()
+        .find(|(u, _)| *u == c)
+        .map_or(c.to_ascii_uppercase(), |(_, s)| *s)
+}
+
+/// Punctuation and digits with shift held on an ANSI board
+const SHIFTED: [(char, char); 21] = [
+    ('`', '~'),
+    ('1', '!'),
+    ('2', '@'),
+    ('3', '#'),
+    ('4', '$'),
+    ('5', '%'),
+    ('6', '^'),
+    ('7', '&'),
+    ('8', '*'),
+    ('9', '('),
+    ('0', ')'),
+    ('-', '_'),
+    ('=', '+'),
+    ('[', '{'),
+    (']', '}'),
+    ('\\', '|'),
+    (';', ':'),
+    ('\'', '"'),
+    (',', '<'),
+    ('.', '>'),
+    ('/', '?'),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_key_on_qwerty() {
+        assert_eq!(Layout::Dvorak.to_qwerty('o'), Some('s'));
+        assert_eq!(Layout::Dvorak.to_qwerty('O'), Some('S'));
+        assert_eq!(Layout::Colemak.to_qwerty(':'), Some('P'));
+        assert_eq!(Layout::Workman.to_qwerty('!'), Some('!'));
+        assert_eq!(Layout::Qwerty.to_qwerty('é'), None);
+    }
+}
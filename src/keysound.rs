@@ -0,0 +1,79 @@
+//! Delay of the sounds played on key presses. Sound reaches the ears later
+//! than the screen updates, by up to a few hundred ms over bluetooth, so the
+//! typing feedback on screen is held back by the same amount. The delay is what the
+//! browser reports, or what a calibration measured on this device.
+
+use gloo_storage::{LocalStorage, Storage};
+
+use crate::audio::{KeySound, Sound};
+
+/// Kept out of the settings since it belongs to the device, not the user
+const LATENCY_STORAGE_KEY: &str = "sound_latency";
+/// Clicks played during the calibration, the first two are for getting the beat
+pub const CALIBRATION_CLICKS: usize = 8;
+const CALIBRATION_WARM_UP: usize = 2;
+pub const CALIBRATION_INTERVAL_MS: f64 = 600.0;
+/// Time to get ready before the first click
+pub const CALIBRATION_LEAD_MS: f64 = 1000.0;
+
+pub fn load_latency() -> Option<f64> {
+    LocalStorage::get(LATENCY_STORAGE_KEY).ok()
+}
+
+pub fn save_latency(ms: f64) {
+    if let Err(e) = LocalStorage::set(LATENCY_STORAGE_KEY, ms) {
+        tracing::warn!("Could not save the sound latency: {e}");
+    }
+}
+
+/// Clicks played at a steady beat, the user presses a key along with what
+/// they hear. How late the presses come after the clicks were played is the latency.
+pub struct Calibration {
+    /// Timestamp (ms) of the first click as played, identifies the calibration
+    pub first_click_at: f64,
+    offsets: Vec<f64>,
+}
+
+impl Calibration {
+    /// Schedules the clicks on `sound`
+    pub fn start(sound: &KeySound, now: f64) -> Self {
+        for i in 0..CALIBRATION_CLICKS {
+            sound.play(
+                Sound::Key,
+                CALIBRATION_LEAD_MS + i as f64 * CALIBRATION_INTERVAL_MS,
+            );
+        }
+        Calibration {
+            first_click_at: now + CALIBRATION_LEAD_MS,
+            offsets: vec![],
+        }
+    }
+
+    /// Timestamp (ms) after which no more presses are expected
+    pub fn ends_at(&self) -> f64 {
+        self.first_click_at + CALIBRATION_CLICKS as f64 * CALIBRATION_INTERVAL_MS
+    }
+
+    pub fn tap(&mut self, now: f64) {
+        let beats = (now - self.first_click_at) / CALIBRATION_INTERVAL_MS;
+        // Presses are late, so each belongs to the last click played before it
+        let click = beats.floor();
+        if click < CALIBRATION_WARM_UP as f64 || click >= CALIBRATION_CLICKS as f64 {
+            return;
+        }
+        self.offsets.push((beats - click) * CALIBRATION_INTERVAL_MS);
+    }
+
+    pub fn taps(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Median delay of the presses in ms, `None` if too few clicks were answered
+    pub fn latency(mut self) -> Option<f64> {
+        if self.offsets.len() < (CALIBRATION_CLICKS - CALIBRATION_WARM_UP) / 2 {
+            return None;
+        }
+        self.offsets.sort_by(f64::total_cmp);
+        Some(self.offsets[self.offsets.len() / 2])
+    }
+}
@@ -0,0 +1,65 @@
+//! Splitting the prompt into visual lines that fit the prompt area,
+//! so that the line with the caret can be kept in place on any screen width.
+
+use std::ops::Range;
+
+/// Lines shown at once when the prompt is longer, the caret line is in the middle
+pub const VISIBLE_LINES: usize = 5;
+
+/// Ranges of char indices of `prompt` in lines of at most `width` chars.
+/// Lines break after line breaks, which end their line, and after spaces;
+/// words longer than a line are split.
+pub fn wrap(prompt: &str, width: usize) -> Vec<Range<usize>> {
+    let chars: Vec<char> = prompt.chars().collect();
+    let mut lines = vec![];
+    let mut start = 0;
+    for (i, c) in chars.iter().enumerate() {
+        if *c == '\n' {
+            wrap_line(&chars[..=i], start, width, &mut lines);
+            start = i + 1;
+        }
+    }
+    wrap_line(&chars, start, width, &mut lines);
+    lines
+}
+
+/// Wraps `chars` from `start` on, a line without line breaks
+fn wrap_line(chars: &[char], mut start: usize, width: usize, lines: &mut Vec<Range<usize>>) {
+    let width = width.max(1);
+    while chars.len() - start > width {
+        let end = start + width;
+        // Break after the last space that fits, keeping trailing spaces on the line
+        let end = match chars[start..=end].iter().rposition(|c| *c == ' ') {
+            Some(space) if space > 0 => start + space + 1,
+            _ => end,
+        };
+        lines.push(start..end);
+        start = end;
+    }
+    lines.push(start..chars.len());
+}
+
+/// Lines to show so that `caret_line` stays in the middle,
+/// `None` stands for an empty line before the first or after the last one
+pub fn visible_lines(lines: usize, caret_line: usize) -> Vec<Option<usize>> {
+    if lines <= VISIBLE_LINES {
+        return (0..lines).map(Some).collect();
+    }
+    let above = VISIBLE_LINES / 2;
+    (0..VISIBLE_LINES)
+        .map(|i| (caret_line + i).checked_sub(above))
+        .map(|line| line.filter(|l| *l < lines))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_breaks_end_lines() {
+        assert_eq!(wrap("ab cd ef", 5), vec![0..6, 6..8]);
+        assert_eq!(wrap("a {\n  b\n}", 10), vec![0..4, 4..8, 8..9]);
+        assert_eq!(wrap("ab cd\nef", 3), vec![0..3, 3..6, 6..8]);
+    }
+}
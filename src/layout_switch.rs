@@ -0,0 +1,163 @@
+//! Switching to a new layout in stages: prompts are made of the home row of
+//! the new layout first, then the top and bottom rows are added, then the rest.
+//! Results are tagged with the layout they were typed on, so the speed on both
+//! layouts is tracked side by side until the new one catches up.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    history::History,
+    keycaps::Layout,
+    trend::Trend,
+    units::{SpeedSample, SpeedUnit},
+};
+
+/// Rows of [`Layout::rows`] in the order they are introduced
+const STAGE_ROWS: [usize; 4] = [2, 1, 3, 0];
+pub const STAGES: usize = STAGE_ROWS.len();
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Migration {
+    pub from: Layout,
+    pub to: Layout,
+    /// Index into the stages of the last row introduced
+    pub stage: usize,
+    /// Practicing the new layout rather than keeping the old one in shape
+    pub practicing_new: bool,
+}
+
+impl Migration {
+    pub fn new(from: Layout, to: Layout) -> Self {
+        Migration {
+            from,
+            to,
+            stage: 0,
+            practicing_new: true,
+        }
+    }
+
+    /// Layout the current prompts are typed on
+    pub fn typed_layout(&self) -> Layout {
+        if self.practicing_new {
+            self.to
+        } else {
+            self.from
+        }
+    }
+
+    /// Keys introduced so far, `None` once all of them are
+    pub fn stage_chars(&self) -> Option<HashSet<char>> {
+        if !self.practicing_new || self.stage + 1 >= STAGES {
+            return None;
+        }
+        let rows = self.to.rows();
+        let keys = STAGE_ROWS[..=self.stage]
+            .iter()
+            .flat_map(|row| rows[*row].chars())
+            // Shifted letters are on the same keys
+            .flat_map(|c| [c, c.to_ascii_uppercase()]);
+        Some(keys.chain([' ']).collect())
+    }
+}
+
+/// Characters prompts are made of: `expected` ones limited to the keys introduced so far
+pub fn practiced_chars(migration: Option<&Migration>, expected: &HashSet<char>) -> HashSet<char> {
+    let Some(stage) = migration.and_then(Migration::stage_chars) else {
+        return expected.clone();
+    };
+    let chars: HashSet<char> = expected.intersection(&stage).copied().collect();
+    if chars.is_empty() {
+        expected.clone()
+    } else {
+        chars
+    }
+}
+
+/// Speed on both layouts of a day with results
+pub struct MigrationDay {
+    /// Unix time in ms of a result of the day
+    pub at: f64,
+    pub old: Option<f64>,
+    pub new: Option<f64>,
+}
+
+pub struct MigrationProgress {
+    pub days: Vec<MigrationDay>,
+    /// Speed on the old layout over all its results
+    pub old: Option<f64>,
+    /// First day the new layout was faster than the old one
+    pub surpassed_at: Option<f64>,
+    /// Minutes of practice on the new layout until it catches up
+    pub minutes_left: Option<f64>,
+}
+
+fn add(total: &mut Option<SpeedSample>, sample: SpeedSample) {
+    let total = total.get_or_insert(SpeedSample {
+        chars: 0,
+        words: 0,
+        keystrokes: 0,
+        millis: 0.0,
+    });
+    total.chars += sample.chars;
+    total.words += sample.words;
+    total.keystrokes += sample.keystrokes;
+    total.millis += sample.millis;
+}
+
+/// Local midnight before `at`, to group results by day
+fn day_of(at: f64) -> i64 {
+    let date = js_sys::Date::new(&at.into());
+    date.set_hours(0);
+    date.set_minutes(0);
+    date.set_seconds(0);
+    date.set_milliseconds(0);
+    date.get_time() as i64
+}
+
+pub fn progress(history: &History, migration: &Migration, unit: SpeedUnit) -> MigrationProgress {
+    let (old_tag, new_tag) = (migration.from.tag(), migration.to.tag());
+    let mut old = None;
+    // Minutes practiced on the new layout and the speed of each result
+    let (mut practiced, mut new_points) = (0.0, vec![]);
+    let mut days: BTreeMap<i64, (f64, Option<SpeedSample>, Option<SpeedSample>)> = BTreeMap::new();
+    for result in history.results.iter().filter(|r| !r.sloppy) {
+        let at = result.meta.created_at;
+        let day = days.entry(day_of(at)).or_insert((at, None, None));
+        // Results from before the switch were typed on the old layout
+        let untagged = !result.tags.iter().any(|t| t.starts_with("layout:"));
+        if untagged || result.tags.contains(old_tag) {
+            add(&mut old, result.speed_sample());
+            add(&mut day.1, result.speed_sample());
+        } else if result.tags.contains(new_tag) {
+            add(&mut day.2, result.speed_sample());
+            practiced += result.millis / 60_000.0;
+            new_points.push((practiced, unit.rate(&result.speed_sample())));
+        }
+    }
+    let old = old.map(|s| unit.rate(&s));
+    let days: Vec<MigrationDay> = days
+        .into_values()
+        .filter(|(_at, old, new)| old.is_some() || new.is_some())
+        .map(|(at, old, new)| MigrationDay {
+            at,
+            old: old.map(|s| unit.rate(&s)),
+            new: new.map(|s| unit.rate(&s)),
+        })
+        .collect();
+    let surpassed_at = days
+        .iter()
+        .find(|d| matches!((d.new, old), (Some(new), Some(old)) if new > old))
+        .map(|d| d.at);
+    let minutes_left = match (surpassed_at, old) {
+        (None, Some(old)) => Trend::fit(&new_points).and_then(|t| t.minutes_to(old)),
+        _ => None,
+    };
+    MigrationProgress {
+        days,
+        old,
+        surpassed_at,
+        minutes_left,
+    }
+}
@@ -0,0 +1,3264 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
+    ops::Range,
+    rc::Rc,
+};
+
+use gloo_file::callbacks::FileReader;
+use gloo_timers::future::TimeoutFuture;
+use gloo_utils::body;
+use typing_core::{
+    errors::{TypingErrors, SCORING_VERSION},
+    ids::{RecordMeta, Ulid},
+    review,
+};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{Element, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::{
+    prelude::{Link, LocationHandle, RouterScopeExt},
+    HashRouter,
+};
+
+use ambient::{Ambient, Soundscape};
+use analytics::{Keystroke, SessionAnalytics, SymbolClass};
+use assets::Loading;
+use audio::{KeySound, Sound, SoundTheme};
+use aux_input::{AuxAction, AuxButton};
+use backup::{LastExport, StatsFile};
+use book::Book;
+use charset::{CharSet, Inventory};
+use curriculum::{Curriculum, LessonSample};
+use cursors::{Pacer, RemoteCursor};
+use data_entry::{DataEntryResult, DataEntryTest};
+use deletion::Deletion;
+use discovery::{Discovery, DiscoveryStats};
+use drill::RepeatDrill;
+use events::{EventKind, EventLog, Replay};
+use exam::{Exam, ExamResults};
+use fingers::{FingerMap, Hand};
+use goal::{Goal, GoalSession};
+use hints::SeenHints;
+use history::{History, HistoryQuery, PromptResult};
+use i18n::{t, t_args, Language};
+use input_guard::{InputGuard, Suspicion};
+use key_input::KeyInput;
+use keyboards::{Keyboard, KeyboardPicker};
+use keycaps::{Keycap, Layout};
+use keysound::Calibration;
+use layout_switch::Migration;
+use normalize::Normalization;
+use numbers::{DigitStats, NumberKeys};
+use pages::{LessonsPage, PracticePage, SettingsPage, StatsPage, TipState};
+use rhythm::Rhythm;
+use route::Route;
+use session::{Period, SessionSummary};
+use settings::{Penalties, Settings};
+use share_code::Preset;
+use snippets::CodeLanguage;
+use sources::{CharWeights, PromptSource};
+use store::{Backend, MemoryStore, StatsStore};
+use suggestions::Suggestion;
+use timed::{TimedResult, TimedTest};
+use units::{SpeedSample, SpeedUnit, TimerStart};
+use warm_up::WarmUp;
+use words::WordStats;
+
+mod ambient;
+mod analytics;
+mod assets;
+mod audio;
+mod aux_input;
+mod backup;
+mod bigrams;
+mod blocklist;
+mod book;
+mod bootstrap;
+mod charset;
+mod codec;
+mod comparison;
+mod curriculum;
+mod cursors;
+mod data_entry;
+mod deletion;
+mod discovery;
+mod drill;
+mod encryption;
+mod epub;
+mod events;
+mod exam;
+mod fingers;
+mod fullscreen;
+mod goal;
+mod hesitation;
+mod hints;
+mod history;
+mod i18n;
+mod import;
+#[cfg(debug_assertions)]
+mod injection;
+mod input_guard;
+mod key_input;
+mod keyboards;
+mod keycaps;
+mod keysound;
+mod layout;
+mod layout_switch;
+mod locale;
+mod metrics;
+mod migrations;
+mod normalize;
+mod numbers;
+mod numpad;
+mod pages;
+mod report;
+mod rhythm;
+mod route;
+mod session;
+mod settings;
+mod share_code;
+mod snippets;
+mod sources;
+mod speech;
+mod startup;
+mod store;
+mod stream;
+mod suggestions;
+mod tabs;
+mod timed;
+mod tips;
+mod trend;
+mod units;
+mod warm_up;
+mod words;
+
+struct Practice {
+    store: Rc<dyn StatsStore>,
+    prompt: String,
+    correctness: Vec<bool>,
+    expected_chars: HashSet<char>,
+    /// Expected and typed characters with the number of times in a row, newest last
+    mistyped: VecDeque<(char, char, usize)>,
+    /// Derived from `events`, kept up to date as keys are typed
+    error_stats: TypingErrors,
+    events: EventLog,
+    /// Recomputing `error_stats` from the event log
+    recompute: Option<Replay>,
+    /// Mistakes made on this line, corrected or not
+    mistakes_on_this_line: usize,
+    input_guard: InputGuard,
+    /// Set when input on this line was rejected as not typed by hand
+    flagged: Option<Suspicion>,
+    /// As of the last key press
+    caps_lock: bool,
+    settings: Settings,
+    /// Timestamp (ms) the prompt was shown at
+    shown_at: f64,
+    /// Timestamps (ms) of the first and the last keystroke of the line
+    started_at: Option<f64>,
+    finished_at: Option<f64>,
+    /// All keystrokes on this line, including corrections
+    keystrokes: usize,
+    drill: Option<RepeatDrill>,
+    data_entry: Option<DataEntryTest>,
+    exam: Option<Exam>,
+    exam_loading: Loading,
+    exam_results: ExamResults,
+    timed: Option<TimedTest>,
+    /// Keys unlocked one at a time, `None` until the course is started
+    curriculum: Option<Curriculum>,
+    /// Key the course introduced with the last prompt, until the next one
+    unlocked_key: Option<char>,
+    /// Summary of the last timed test, until closed
+    timed_result: Option<TimedResult>,
+    /// Summary of the last data entry assessment, until closed
+    data_entry_result: Option<DataEntryResult>,
+    /// Prompts completed since the page was opened
+    prompts_done: usize,
+    /// Whether the current prompt is a retention check
+    retention_check: bool,
+    /// Everything typed since the page was opened
+    log: Vec<Keystroke>,
+    backspaces: usize,
+    /// Expected character and time of the previous keystroke on this line
+    previous_key: Option<(char, f64)>,
+    /// Keeps a stats file being read alive
+    file_reader: Option<FileReader>,
+    drill_loading: Loading,
+    language_loading: Loading,
+    /// Keeps the router reporting pages opened by links or the back button
+    _location: Option<LocationHandle>,
+    /// The warm-up routine being typed
+    warm_up: Option<WarmUp>,
+    read_ahead: ReadAhead,
+    /// Fetching prompts from the selected source
+    source_loading: Loading,
+    history: History,
+    /// Tags given to results of this session
+    session_tags: BTreeSet<String>,
+    /// When the pause started, typing is ignored while paused
+    paused_at: Option<f64>,
+    /// Everything but the prompt is hidden
+    zen: bool,
+    /// The pace caret of the current prompt, once typing started
+    pacer: Option<Pacer>,
+    save_state: SaveState,
+    prompt_ref: NodeRef,
+    /// A single prompt character, to measure its width
+    char_ref: NodeRef,
+    /// Visual lines the prompt is split into, 0 until measured
+    chars_per_line: usize,
+    ambient: Option<Ambient>,
+    key_sound: Option<KeySound>,
+    /// Sound latency in ms measured on this device
+    sound_latency: Option<f64>,
+    calibration: Option<Calibration>,
+    /// Looking for keys of a new layout instead of typing prompts
+    discovery: Option<Discovery>,
+    discovery_stats: DiscoveryStats,
+    /// Keystrokes typed on the numeric keypad
+    numpad_stats: DigitStats,
+    /// Keystrokes of number drills typed on the number row, kept apart from `error_stats`
+    number_row_stats: DigitStats,
+    /// Unix time (ms) the page was opened
+    session_started_at: f64,
+    /// Session started with a goal, until it is reached or stopped
+    goal: Option<GoalSession>,
+    /// Summary of the last goal session and whether its goal was reached, until dismissed
+    goal_summary: Option<(bool, SessionSummary)>,
+    /// Char indices of the prompt mistyped at least once, corrections do not clear them
+    fumbled_at: BTreeSet<usize>,
+    /// Words of this session's prompts and how often they had a mistake
+    word_stats: BTreeMap<String, WordStats>,
+    last_export: Option<LastExport>,
+    /// The session's keystrokes playing as notes, and when they started (ms)
+    rhythm: Option<(f64, Rhythm)>,
+    /// A long text copy-typed over many sessions
+    book: Option<Book>,
+    /// When the completed prompt is followed by the next one
+    advance_at: Option<f64>,
+    /// Prompts of the source buffered for the growing prompt of a timed test
+    stream: stream::Buffer,
+    seen_hints: SeenHints,
+    /// A recent drop of accuracy across days, found when results are added
+    regression: Option<analytics::Regression>,
+    regression_dismissed: bool,
+    /// Unix time (ms) of the last key press, or of opening the app
+    active_at: f64,
+    stats_page: StatsPage,
+    settings_page: SettingsPage,
+    lessons_page: LessonsPage,
+    practice_page: PracticePage,
+}
+
+/// The next prompt of the selected source, generated while the current one
+/// is typed so that Enter shows it instantly. It is based on the stats as
+/// they were when it was generated, a few keystrokes of lag do not matter.
+enum ReadAhead {
+    Empty,
+    Scheduled,
+    Ready(String),
+}
+
+/// Statistics are saved between prompts, or once typing pauses for
+/// [`SAVE_DELAY_MS`], not on every keystroke
+enum SaveState {
+    Saved,
+    /// Changed since the last save, no save is scheduled yet
+    Unsaved,
+    Scheduled,
+    Failed(String),
+    /// Statistics are not saved by choice
+    Disabled,
+    /// A tab opened later saves the statistics
+    OtherTab,
+}
+
+/// Persistence of the error stats, which come from the core crate
+trait StoredErrors: Sized {
+    fn load(store: &dyn StatsStore) -> Self;
+    /// `compact` picks the binary format over JSON
+    fn save(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String>;
+}
+
+impl StoredErrors for TypingErrors {
+    fn load(store: &dyn StatsStore) -> Self {
+        // Unreadable stats are set aside by the codec before being replaced
+        codec::load(store, ERROR_STORAGE_KEY).unwrap_or_else(|_| TypingErrors {
+            // Unreadable stats are outdated, so that they are rebuilt from the event log
+            scoring_version: if store.get(ERROR_STORAGE_KEY).is_some() {
+                0
+            } else {
+                SCORING_VERSION
+            },
+            meta: RecordMeta::new(js_sys::Date::now()),
+            ..Default::default()
+        })
+    }
+
+    fn save(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String> {
+        codec::save(store, ERROR_STORAGE_KEY, self, compact)
+    }
+}
+
+/// Events replayed between two frames
+const REPLAY_SLICE: usize = 2000;
+/// Results shown in the statistics, the rest stays in the history
+const RECENT_RESULTS: usize = 10;
+/// Changes made within this time are saved together
+const SAVE_DELAY_MS: u32 = 2000;
+const ERROR_STORAGE_KEY: &str = "typing_errors";
+/// Everything kept in the stats store
+const STATS_KEYS: [&str; 10] = [
+    ERROR_STORAGE_KEY,
+    events::EVENTS_STORAGE_KEY,
+    history::HISTORY_STORAGE_KEY,
+    exam::EXAM_STORAGE_KEY,
+    session::SESSION_STORAGE_KEY,
+    discovery::DISCOVERY_STORAGE_KEY,
+    numpad::NUMPAD_STORAGE_KEY,
+    numbers::NUMBER_ROW_STORAGE_KEY,
+    book::BOOK_STORAGE_KEY,
+    book::BOOK_TEXT_STORAGE_KEY,
+];
+
+#[derive(Properties)]
+struct PracticeProps {
+    store: Rc<dyn StatsStore>,
+}
+
+impl PartialEq for PracticeProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.store, &other.store)
+    }
+}
+
+enum Msg {
+    KeyPress(KeyInput),
+    /// Resolves the promise of `window.typingTutor.state()` with a snapshot
+    #[cfg(debug_assertions)]
+    InjectionState(js_sys::Function),
+    Paste(Event),
+    PageHide,
+    DismissLastSession,
+    Resize,
+    SetLineWidth(usize),
+    SaveDue,
+    StartRecompute,
+    RecomputeStep,
+    AuxMouse(MouseEvent),
+    AuxPress(AuxButton),
+    RunAction(AuxAction),
+    SetAmbient(Option<Soundscape>),
+    SetAmbientVolume(f64),
+    PreviewPenalties(Penalties),
+    ApplyPenalties,
+    FullscreenChanged(bool),
+    TakenOver,
+    StartBinding(AuxAction),
+    ClearBindings(AuxAction),
+    SetSpeedUnit(SpeedUnit),
+    SetLanguage(Option<Language>),
+    ExtendExpectedChars(Vec<char>),
+    PracticeText(String),
+    /// The router opened another page
+    Navigate,
+    StartWarmUp,
+    SkipWarmUp,
+    SetNote(Ulid, String),
+    SetSessionTags(String),
+    ToggleTagFilter(String),
+    SelectKeyboard(Option<Ulid>),
+    AddKeyboard(String),
+    SetFingerMap(FingerMap),
+    SetLayerChars(String),
+    SetCustomCss(String),
+    SetReadAloud(bool),
+    SetPaceCaret(bool),
+    SetKeySounds(bool),
+    SetSoundTheme(SoundTheme),
+    SetErrorSounds(bool),
+    StartCalibration,
+    /// Ends the calibration whose first click was played at the timestamp
+    CalibrationDone(f64),
+    CursorFrame(f64),
+    SetLargeText(bool),
+    SetShowKeyboard(bool),
+    SetHomeRowShift(bool),
+    ReadAhead(Result<String, String>),
+    StreamPrompt(Result<String, String>),
+    SetPromptSource(String),
+    SetSourceUrl(String),
+    SetSnippetLanguage(Option<CodeLanguage>),
+    SetUppercaseWords(bool),
+    SetBuiltInBlocklist(bool),
+    SetBlockedWords(Vec<String>),
+    SourcePrompt(Result<String, String>),
+    StartBuiltinDrill,
+    StartDrill(Vec<String>),
+    StartNumberDrill(NumberKeys),
+    DrillFailed(String),
+    LanguageLoaded(Language, Result<String, String>),
+    StopDrill,
+    StartDataEntry,
+    StartDiscovery,
+    StartMigration(Layout),
+    NextMigrationStage,
+    SetPracticingNewLayout(bool),
+    EndMigration,
+    StopDiscovery,
+    BookFileChosen(Option<web_sys::File>),
+    /// Title and text of an opened long text
+    BookFileRead(String, Result<String, String>),
+    SetReadingBook(bool),
+    /// Byte offset into the book
+    JumpInBook(usize),
+    SetBookmarkNote(String),
+    AddBookmark,
+    SetNormalization(Normalization),
+    SetPassageLen(usize),
+    SetHesitationPercentile(f64),
+    RemoveBookmark(usize),
+    PlayRhythm,
+    StopRhythm,
+    /// Timestamp (ms) the playback started at
+    RhythmEnded(f64),
+    RevealKey(f64),
+    SetLayout(Layout),
+    SetCharSet(CharSet),
+    SetDiscoveryDelay(u32),
+    StopDataEntry,
+    PrintDataEntryResult,
+    CloseDataEntryResult,
+    StartExam,
+    BeginExam(Vec<String>),
+    ExamFailed(String),
+    ExamTick(f64),
+    StopExam,
+    SetExamBackspaceLimit(Option<usize>),
+    /// Seconds, and the hand if only its keys are typed
+    StartTimedTest(u32, Option<Hand>),
+    StartGoal(Goal),
+    StopGoal,
+    GoalTick,
+    DismissGoalSummary,
+    StartCurriculum,
+    SetCurriculumActive(bool),
+    ResetCurriculum,
+    SetCurriculumAccuracy(f64),
+    SetCurriculumWpm(f64),
+    Tick(f64),
+    StopTimedTest,
+    CloseTimedResult,
+    SetMinAccuracy(Option<f64>),
+    SetAutoAdvance(Option<u32>),
+    SetTimerStart(TimerStart),
+    SetWarmUpPrompts(usize),
+    AdvanceTick(f64),
+    CompletePrompt(f64),
+    SetCleanRepetitions(usize),
+    DismissTip,
+    DismissHint(hints::Mode),
+    CompareRegression,
+    DismissRegression,
+    DismissUnreadable,
+    IdleCheck,
+    ActOnSuggestion(Suggestion),
+    DismissSuggestions,
+    ResetHints,
+    SetCompactStorage(bool),
+    StatsFileChosen(Option<web_sys::File>),
+    SetStorageBackend(Backend),
+    StoreOpened(Rc<dyn StatsStore>),
+    DownloadStore,
+    ExportStats,
+    ExportReport,
+    /// File name and contents
+    EncryptedDownload(&'static str, Result<String, String>),
+    SetFilePassword(String),
+    StoreFileChosen(Option<web_sys::File>),
+    StoreFileRead(Result<String, String>),
+    StatsFileRead(Result<String, String>),
+    ApplyImport,
+    CancelImport,
+    EnterShareCode(String),
+    ApplySharedPreset,
+    /// Start and end of one of the compared periods
+    SetComparisonPeriod(usize, (Option<f64>, Option<f64>)),
+    SetDeletionFrom(Option<f64>),
+    SetDeletionTo(Option<f64>),
+    PreviewDeletion(Deletion),
+    ApplyDeletion,
+    CancelDeletion,
+    SetPromptLen(usize),
+    SetSymbolClass(SymbolClass, bool),
+    SetMistakesShown(usize),
+    SetPersistStats(bool),
+    SetCapsLockGuard(bool),
+    SetBackupReminderDays(u32),
+    PostponeBackup,
+}
+
+impl Practice {
+    fn render_chars(&self) -> Html {
+        let now = startup::now();
+        let last = self.prompt.chars().count().saturating_sub(1);
+        let remote: Vec<(usize, &RemoteCursor)> = self
+            .pacer
+            .iter()
+            .map(|p| (p.cursor.position(now).min(last), &p.cursor))
+            .collect();
+        let spans: Vec<Html> = self
+            .prompt
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                // A line break shows where Enter is pressed
+                let c = match c {
+                    '\n' => "↵\n".to_string(),
+                    c => c.to_string(),
+                };
+                let class = match (i, self.correctness.get(i)) {
+                    (i, _) if self.correctness.len() == i => "cursor",
+                    (_, Some(true)) => "correct",
+                    (_, Some(false)) => "incorrect",
+                    (_, None) => "",
+                };
+                let here: Vec<&RemoteCursor> = remote
+                    .iter()
+                    .filter(|(at, _)| *at == i)
+                    .map(|(_, cursor)| *cursor)
+                    .collect();
+                match here.first() {
+                    None => html!(<span class = {class}>{c}</span>),
+                    Some(first) => html!(
+                        <span class={classes!(class, "remote-cursor")}
+                            style={format!("--remote-color: {}", first.color)}>
+                            {c}
+                            <span class="nametag">{for here.iter().map(|r| r.name.clone())}</span>
+                        </span>
+                    ),
+                }
+            })
+            .collect();
+        let words = words::segment(&self.prompt);
+        let current = words::current(&words, self.correctness.len());
+        // Characters of each word are grouped, a word split over lines in each line
+        let group = |range: Range<usize>| -> Html {
+            let mut grouped = vec![];
+            let mut i = range.start;
+            while i < range.end {
+                match words.iter().position(|w| w.contains(&i)) {
+                    Some(word) => {
+                        let end = words[word].end.min(range.end);
+                        let class =
+                            classes!("word", (Some(word) == current).then_some("current-word"));
+                        grouped
+                            .push(html!(<span {class}>{for spans[i..end].iter().cloned()}</span>));
+                        i = end;
+                    }
+                    None => {
+                        grouped.push(spans[i].clone());
+                        i += 1;
+                    }
+                }
+            }
+            grouped.into_iter().collect()
+        };
+        // Not measured yet, the browser wraps the prompt
+        if self.chars_per_line == 0 {
+            return group(0..spans.len());
+        }
+        let lines = layout::wrap(&self.prompt, self.chars_per_line);
+        let caret = self.correctness.len().min(last);
+        let caret_line = lines.iter().position(|l| l.contains(&caret)).unwrap_or(0);
+        layout::visible_lines(lines.len(), caret_line)
+            .into_iter()
+            .map(|line| match line {
+                Some(line) => html!(<div class="prompt-line">{group(lines[line].clone())}</div>),
+                None => html!(<div class="prompt-line">{"\u{a0}"}</div>),
+            })
+            .collect()
+    }
+
+    /// Characters that fit on a line of the prompt area
+    fn measure_line_width(&self) -> Option<usize> {
+        let prompt = self.prompt_ref.cast::<Element>()?;
+        let char_width = self
+            .char_ref
+            .cast::<Element>()?
+            .get_bounding_client_rect()
+            .width();
+        (char_width > 0.0).then(|| (prompt.client_width() as f64 / char_width) as usize)
+    }
+
+    fn speed_sample(&self) -> Option<SpeedSample> {
+        Some(SpeedSample {
+            chars: self.prompt.chars().count(),
+            words: self.prompt.split_whitespace().count(),
+            keystrokes: self.keystrokes,
+            millis: self.finished_at?
+                - match self.settings.timer_start {
+                    TimerStart::FirstKeystroke => self.started_at?,
+                    TimerStart::PromptShown => self.shown_at,
+                },
+        })
+    }
+
+    /// Speed over the characters typed so far while the prompt is being typed
+    fn live_speed_sample(&self) -> Option<SpeedSample> {
+        if self.finished_at.is_some() {
+            return self.speed_sample();
+        }
+        let started_at = match self.settings.timer_start {
+            TimerStart::FirstKeystroke => self.started_at?,
+            TimerStart::PromptShown => self.shown_at,
+        };
+        let typed: String = self.prompt.chars().take(self.correctness.len()).collect();
+        Some(SpeedSample {
+            chars: typed.chars().count(),
+            words: typed.split_whitespace().count(),
+            keystrokes: self.keystrokes,
+            millis: self.paused_at.unwrap_or_else(startup::now) - started_at,
+        })
+    }
+
+    fn format_speed(&self, sample: &SpeedSample) -> String {
+        let unit = self.settings.speed_unit;
+        format!(
+            "{} {}",
+            locale::format_number(unit.rate(sample), 1),
+            t(unit.message_key())
+        )
+    }
+
+    /// Whether the current prompt is one of the first of the session, which do not
+    /// change the error scores. Assessments are never warm-ups.
+    fn is_warm_up(&self) -> bool {
+        self.exam.is_none()
+            && self.data_entry.is_none()
+            && self.timed.is_none()
+            && (self.warm_up.is_some() || self.prompts_done < self.settings.warm_up_prompts)
+    }
+
+    /// Whether the current prompt is below the minimum accuracy so far.
+    /// Its mistakes count towards the error stats all the same.
+    fn is_sloppy(&self) -> bool {
+        let accuracy = history::accuracy(self.keystrokes, self.mistakes_on_this_line);
+        self.settings.min_accuracy.is_some_and(|min| accuracy < min)
+    }
+
+    /// Records the finished prompt unless its input was rejected
+    fn record_result(&mut self) {
+        if let Some(sample) = self.speed_sample() {
+            self.record_sample(&sample);
+        }
+    }
+
+    /// Adds the current prompt typed as in `sample` to the history
+    fn record_sample(&mut self, sample: &SpeedSample) {
+        if self.flagged.is_some() {
+            return;
+        }
+        self.history.results.push(PromptResult {
+            meta: RecordMeta::new(js_sys::Date::now()),
+            chars: sample.chars,
+            words: sample.words,
+            keystrokes: sample.keystrokes,
+            millis: sample.millis,
+            mistakes: self.mistakes_on_this_line,
+            note: String::new(),
+            keyboard: self.settings.active_keyboard,
+            tags: self.result_tags(),
+            sloppy: self.is_sloppy(),
+        });
+        self.history_changed();
+        self.mark_unsaved();
+    }
+
+    /// Looks for a regression of accuracy among the changed results
+    fn history_changed(&mut self) {
+        self.regression = analytics::accuracy_regression(&analytics::daily_accuracy(&self.history));
+    }
+
+    /// Counts the completed prompt towards the next key of the course
+    /// if it is a regular one made of the course's keys
+    /// Number drills and data entry leave the error scores alone
+    fn keeps_stats_apart(&self) -> bool {
+        self.data_entry.is_some() || self.drill.as_ref().is_some_and(|d| d.numbers)
+    }
+
+    /// Ends the goal session with a summary of what was typed in it
+    fn finish_goal(&mut self, reached: bool) {
+        let Some(goal) = self.goal.take() else {
+            return;
+        };
+        let input = metrics::MetricInput {
+            log: &self.log[goal.log_before.min(self.log.len())..],
+            backspaces: self.backspaces.saturating_sub(goal.backspaces_before),
+            settings: &self.settings,
+        };
+        let summary = SessionSummary::new(
+            goal.started_at,
+            js_sys::Date::now(),
+            goal.prompts(self.prompts_done),
+            &input,
+        );
+        self.goal_summary = Some((reached, summary));
+    }
+
+    fn record_lesson(&mut self) {
+        let regular = self.drill.is_none()
+            && self.warm_up.is_none()
+            && !self.retention_check
+            && !self.typing_book()
+            && !self.is_warm_up()
+            && self.flagged.is_none();
+        self.unlocked_key = None;
+        let Some(sample) = self.speed_sample().filter(|_| regular) else {
+            return;
+        };
+        let lesson = LessonSample {
+            chars: sample.chars,
+            keystrokes: sample.keystrokes,
+            mistakes: self.mistakes_on_this_line,
+            millis: sample.millis,
+        };
+        let Some(curriculum) = self.curriculum.as_mut().filter(|c| c.active) else {
+            return;
+        };
+        self.unlocked_key = curriculum.record(
+            lesson,
+            self.settings.typed_layout(),
+            self.settings.curriculum_accuracy,
+            self.settings.curriculum_wpm,
+        );
+        curriculum.save();
+        if self.unlocked_key.is_some() {
+            // Prompts read ahead are made of the keys before
+            self.read_ahead = ReadAhead::Empty;
+        }
+    }
+
+    /// Automatic and session tags of the current prompt's result
+    fn result_tags(&self) -> BTreeSet<String> {
+        let mode = match () {
+            _ if self.timed.is_some() => "timed",
+            _ if self.drill.is_some() => "drill",
+            _ if self.warm_up.is_some() => "warm-up",
+            _ if self.retention_check => "retention",
+            _ if self.typing_book() => "book",
+            _ => "practice",
+        };
+        let source =
+            (mode == "practice").then(|| format!("source:{}", self.settings.prompt_source));
+        let book = self
+            .book
+            .as_ref()
+            .filter(|_| mode == "book")
+            .map(|b| [format!("book:{}", b.title), b.chapter_tag()]);
+        let timed = self.timed.iter().flat_map(TimedTest::tags);
+        [
+            format!("mode:{mode}"),
+            self.settings.timer_start.tag().to_string(),
+        ]
+        .into_iter()
+        .chain(source)
+        .chain(book.into_iter().flatten())
+        .chain(timed)
+        .chain(
+            Inventory::of(&self.prompt)
+                .script()
+                .map(|s| s.tag().to_string()),
+        )
+        .chain(
+            self.settings
+                .migration
+                .as_ref()
+                .map(|m| m.typed_layout().tag().to_string()),
+        )
+        .chain(self.session_tags.iter().cloned())
+        .collect()
+    }
+
+    /// The prompt is the next passage of the book
+    fn typing_book(&self) -> bool {
+        self.book_passage() == Some(self.prompt.as_str())
+    }
+
+    fn book_passage(&self) -> Option<&str> {
+        self.book
+            .as_ref()
+            .filter(|b| b.reading && !b.is_finished())
+            .map(|b| b.passage(self.settings.passage_len))
+    }
+
+    fn history_query(&self) -> HistoryQuery<'_> {
+        HistoryQuery {
+            tags: &self.stats_page.tag_filter,
+            keyboards: &self.settings.keyboards,
+        }
+    }
+
+    /// Words of the completed prompt that had a mistake
+    fn render_word_accuracy(&self) -> Html {
+        let Some(accuracy) = words::accuracy(&self.prompt, &self.fumbled_at) else {
+            return html!();
+        };
+        let fumbled = words::fumbled(&self.prompt, &self.fumbled_at);
+        html!(<p>
+            {t_args("word-accuracy", &[("accuracy", &locale::format_number(accuracy, 1))])}
+            {(!fumbled.is_empty()).then(|| html!(<>
+                {" "}{t("words-fumbled")}{" "}<code>{fumbled.join(" ")}</code>
+            </>))}
+        </p>)
+    }
+
+    fn prompt_source(&self) -> Rc<dyn PromptSource> {
+        sources::find(&self.settings.prompt_source, &self.settings)
+    }
+
+    fn char_weights(&self) -> CharWeights {
+        CharWeights::new(
+            &self.error_stats,
+            &practiced_chars(
+                &self.settings,
+                self.curriculum.as_ref(),
+                &self.expected_chars,
+            ),
+            js_sys::Date::now(),
+        )
+    }
+
+    fn random_symbols(&self) -> String {
+        let mut chars = self.settings.symbol_chars();
+        if let Some(hand) = self.timed.as_ref().and_then(|t| t.hand) {
+            chars = timed::hand_chars(
+                &chars,
+                self.settings.finger_map,
+                self.settings.typed_layout(),
+                hand,
+            );
+        }
+        sources::symbols(&chars, &self.char_weights(), self.settings.prompt_len)
+    }
+
+    fn reported_latency(&self) -> Option<f64> {
+        self.key_sound.as_ref()?.reported_latency()
+    }
+
+    /// Holds back typing feedback on screen until the click is heard
+    fn feedback_delay_style(&self) -> Option<String> {
+        if !self.settings.key_sounds {
+            return None;
+        }
+        let ms = self.sound_latency.or(self.reported_latency())?;
+        Some(format!("--feedback-delay: {}ms", ms.round()))
+    }
+
+    /// The mode being practiced, the most specific one if several are on
+    fn mode(&self) -> Option<hints::Mode> {
+        match () {
+            _ if self.discovery.is_some() => Some(hints::Mode::Discovery),
+            _ if self.exam.is_some() => Some(hints::Mode::Exam),
+            _ if self.data_entry.is_some() => Some(hints::Mode::DataEntry),
+            _ if self.timed.is_some() => Some(hints::Mode::Timed),
+            _ if self.drill.is_some() => Some(hints::Mode::Drill),
+            _ if self.warm_up.is_some() => Some(hints::Mode::WarmUp),
+            _ if self.typing_book() => Some(hints::Mode::Book),
+            _ if self.curriculum.as_ref().is_some_and(|c| c.active) => {
+                Some(hints::Mode::Curriculum)
+            }
+            _ if self.settings.prompt_source == sources::SNIPPETS_SOURCE => Some(hints::Mode::Code),
+            _ => None,
+        }
+    }
+
+    fn save_settings(&mut self) {
+        if let Err(e) = self.settings.save(&*self.store) {
+            tracing::warn!("Saving settings failed: {e}");
+            self.save_state = SaveState::Failed(e);
+        }
+        self.events.push(
+            EventKind::SettingsChange(Box::new(self.settings.clone())),
+            js_sys::Date::now(),
+        );
+    }
+
+    /// Statistics changed, they are saved by a save scheduled after the next render
+    fn mark_unsaved(&mut self) {
+        if !matches!(
+            self.save_state,
+            SaveState::Scheduled | SaveState::Disabled | SaveState::OtherTab
+        ) {
+            self.save_state = SaveState::Unsaved;
+        }
+    }
+
+    fn save_stats(&mut self) {
+        if let SaveState::OtherTab = self.save_state {
+            return;
+        }
+        if !self.settings.persist_stats {
+            self.save_state = SaveState::Disabled;
+            return;
+        }
+        let compact = self.settings.compact_storage;
+        let store = &*self.store;
+        let saved = self
+            .events
+            .save(store, compact)
+            .and(self.error_stats.save(store, compact))
+            .and(self.history.save(store, compact))
+            .and(self.exam_results.save(store, compact))
+            .and(self.discovery_stats.save(store, compact))
+            .and(
+                self.numpad_stats
+                    .save(store, numpad::NUMPAD_STORAGE_KEY, compact),
+            )
+            .and(
+                self.number_row_stats
+                    .save(store, numbers::NUMBER_ROW_STORAGE_KEY, compact),
+            )
+            .and(
+                self.book
+                    .as_ref()
+                    .map_or(Ok(()), |b| b.save(store, compact)),
+            );
+        self.save_state = match saved {
+            Ok(()) => SaveState::Saved,
+            Err(e) => {
+                tracing::warn!("Saving statistics failed: {e}");
+                SaveState::Failed(e)
+            }
+        };
+    }
+
+    fn render_save_state(&self) -> Html {
+        let (class, text) = match &self.save_state {
+            SaveState::Saved => ("save-state", t("saved")),
+            SaveState::Unsaved | SaveState::Scheduled => ("save-state", t("saving")),
+            SaveState::Failed(e) => ("save-state flagged", t_args("save-failed", &[("error", e)])),
+            SaveState::Disabled => ("save-state", t("not-saved")),
+            SaveState::OtherTab => {
+                return html!(<div class="save-state flagged">
+                    {t("other-tab-saves")}{" "}
+                    <button onclick={Callback::from(|_| tabs::take_over())}>
+                        {t("save-from-this-tab")}
+                    </button>
+                </div>)
+            }
+        };
+        html!(<div class={class}>{text}</div>)
+    }
+
+    /// Downloads `contents` as `name`, encrypted if there is a password,
+    /// and remembers it as the latest backup
+    fn download_backup(&mut self, ctx: &Context<Self>, contents: String, name: &'static str) {
+        let export = LastExport {
+            at: js_sys::Date::now(),
+            results: self.history.results.len(),
+        };
+        export.save();
+        self.last_export = Some(export);
+        if self.settings_page.file_password.is_empty() {
+            store::download(&contents, name, "application/json");
+            return;
+        }
+        let password = self.settings_page.file_password.clone();
+        ctx.link().send_future(async move {
+            Msg::EncryptedDownload(name, encryption::encrypt(&contents, &password).await)
+        });
+    }
+
+    /// Decrypts an opened file with the entered password and reads it again
+    fn decrypt_file(
+        &mut self,
+        ctx: &Context<Self>,
+        text: String,
+        read: fn(Result<String, String>) -> Msg,
+    ) {
+        self.file_reader = None;
+        if self.settings_page.file_password.is_empty() {
+            self.settings_page.import_error = Some(t("file-password-needed"));
+            return;
+        }
+        let password = self.settings_page.file_password.clone();
+        ctx.link()
+            .send_future(async move { read(encryption::decrypt(&text, &password).await) });
+    }
+
+    /// Ends the running exam at `now`, counting the unfinished line
+    fn finish_exam(&mut self, now: f64) {
+        let Some(exam) = self.exam.take() else {
+            return;
+        };
+        let errors = self.correctness.iter().filter(|c| !**c).count();
+        self.exam_results
+            .push(exam.finish(self.correctness.len(), errors, now));
+        self.mark_unsaved();
+    }
+
+    /// Ends the running timed test at `now`, recording what was typed in time
+    fn finish_timed_test(&mut self, now: f64) {
+        let Some(test) = &self.timed else {
+            return;
+        };
+        let typed: String = self.prompt.chars().take(self.correctness.len()).collect();
+        let sample = SpeedSample {
+            chars: typed.chars().count(),
+            words: typed.split_whitespace().count(),
+            keystrokes: self.keystrokes,
+            millis: test.duration_ms() - test.remaining(now),
+        };
+        let (seconds, hand) = (test.seconds, test.hand);
+        self.events.push(
+            EventKind::PromptEnd {
+                flagged: self.flagged.is_some(),
+            },
+            js_sys::Date::now(),
+        );
+        self.prompts_done += 1;
+        self.record_sample(&sample);
+        self.timed_result = Some(TimedResult {
+            seconds,
+            hand,
+            sample,
+            mistakes: self.mistakes_on_this_line,
+        });
+        self.timed = None;
+        self.mark_unsaved();
+    }
+
+    /// Appends the next prompt of the source to the one being typed
+    fn extend_prompt(&mut self) {
+        let one_hand = self.timed.as_ref().is_some_and(|t| t.hand.is_some());
+        let buffered = self.stream.pop().filter(|_| !one_hand);
+        let next = match (
+            buffered,
+            std::mem::replace(&mut self.read_ahead, ReadAhead::Empty),
+        ) {
+            (Some(next), pending) => {
+                self.read_ahead = pending;
+                next
+            }
+            (None, ReadAhead::Ready(prompt)) if !one_hand => prompt,
+            (None, pending) => {
+                self.read_ahead = pending;
+                self.random_symbols()
+            }
+        };
+        let text = format!(" {next}");
+        self.prompt.push_str(&text);
+        self.events
+            .push(EventKind::PromptExtend { text }, js_sys::Date::now());
+    }
+
+    /// Prompts read ahead were made of the keys of the previous stage
+    fn migration_changed(&mut self) {
+        self.save_settings();
+        self.read_ahead = ReadAhead::Empty;
+    }
+
+    /// Shows the next character to find and starts its reveal timer
+    fn next_discovery(&mut self, ctx: &Context<Self>, previous: Option<char>) {
+        let shown_at = startup::now();
+        self.discovery = Some(Discovery {
+            target: self.discovery_stats.pick(self.settings.layout, previous),
+            shown_at,
+            revealed: false,
+        });
+        let delay = self.settings.discovery_delay_ms;
+        ctx.link().send_future(async move {
+            TimeoutFuture::new(delay).await;
+            Msg::RevealKey(shown_at)
+        });
+    }
+
+    /// Keyboard under the prompt with the next key lit, keys are shaded by their error scores
+    fn render_keyboard(&self) -> Html {
+        self.render_error_keyboard(self.prompt.chars().nth(self.correctness.len()))
+    }
+
+    /// Keys shaded by their error scores, with the key typing `next` marked
+    fn render_error_keyboard(&self, next: Option<char>) -> Html {
+        let keycaps = self.settings.typed_layout().keycaps();
+        let next_key = next.and_then(|c| self.settings.typed_layout().key_of(c));
+        let score = |c: char| self.error_stats.error_score.get(&c).copied().unwrap_or(0);
+        // Both characters of a key count towards it
+        let key_score = |cap: &Keycap| score(cap.base) + score(cap.shifted);
+        let max_score = keycaps
+            .iter()
+            .flatten()
+            .map(key_score)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        // Shift is pressed by the other hand than the key, both are lit if the finger is unknown
+        let shift_hand = matches!(next_key, Some((_, _, true))).then(|| {
+            next.and_then(|c| {
+                self.settings
+                    .finger_map
+                    .finger_for(self.settings.typed_layout(), c)
+            })
+            .map(|f| f.hand.other())
+        });
+        let shift_key = |hand: Hand| {
+            let lit = matches!(shift_hand, Some(h) if h.is_none() || h == Some(hand));
+            let title = t(&format!("shift-{}", hand.name()));
+            html!(<span class={classes!("key", "wide", lit.then_some("next"))} {title}>{"⇧"}</span>)
+        };
+        let rows = keycaps.iter().enumerate().map(|(i, row)| {
+            let keys = row.iter().enumerate().map(|(column, cap)| {
+                let next = matches!(next_key, Some((r, col, _)) if (r, col) == (i, column));
+                let error = key_score(cap) as f64 / max_score as f64;
+                let title = t_args("heatmap-key", &[("key", &cap.base), ("score", &key_score(cap))]);
+                html!(<span class={classes!("key", next.then_some("next"))} style={format!("--error: {error:.2}")} {title}>
+                    {cap.base}
+                </span>)
+            });
+            let bottom = i + 1 == keycaps::ROWS;
+            html!(<div class="keyboard-row" style={format!("--row: {i}")}>
+                {bottom.then(|| shift_key(Hand::Left))}{for keys}{bottom.then(|| shift_key(Hand::Right))}
+            </div>)
+        });
+        let space = next == Some(' ');
+        let space_error = score(' ') as f64 / max_score as f64;
+        html!(<div class="keyboard">
+            {for rows}
+            <div class="keyboard-row" style={format!("--row: {}", keycaps::ROWS)}>
+                <span class={classes!("key", "space", space.then_some("next"))}
+                    style={format!("--error: {:.2}", space_error.min(1.0))}></span>
+            </div>
+        </div>)
+    }
+
+    /// Keypad under a data entry field with the next key lit
+    fn render_numpad(&self) -> Html {
+        // Enter confirms a typed field
+        let next = match self.prompt.chars().nth(self.correctness.len()) {
+            Some(c) => numpad::KEYS.iter().position(|k| k.char == Some(c)),
+            None => numpad::KEYS.iter().position(|k| k.label == "Enter"),
+        };
+        self.render_numpad_heatmap(next)
+    }
+
+    /// Keys of the keypad shaded by their error rates there, with the key at `next` marked
+    fn render_numpad_heatmap(&self, next: Option<usize>) -> Html {
+        let rate = |key: &numpad::NumpadKey| {
+            key.char
+                .map_or(0.0, |c| self.numpad_stats.get(c).error_rate())
+        };
+        let max_rate = numpad::KEYS
+            .iter()
+            .map(rate)
+            .fold(0.0, f64::max)
+            .max(f64::EPSILON);
+        let keys = numpad::KEYS.iter().enumerate().map(|(i, key)| {
+            let stats = key
+                .char
+                .map(|c| self.numpad_stats.get(c))
+                .unwrap_or_default();
+            let title = t_args(
+                "numpad-key",
+                &[
+                    ("key", &key.label),
+                    ("finger", &t(&key.finger.message_key())),
+                    ("mistakes", &stats.mistakes),
+                    ("presses", &stats.presses),
+                ],
+            );
+            let class = classes!(
+                "key",
+                key.tall.then_some("tall"),
+                key.wide.then_some("wide"),
+                (next == Some(i)).then_some("next"),
+            );
+            html!(<span {class} style={format!("--error: {:.2}", rate(key) / max_rate)} {title}>
+                {key.label}
+            </span>)
+        });
+        let columns = format!("grid-template-columns: repeat({}, auto)", numpad::COLUMNS);
+        html!(<div class="keyboard numpad" style={columns}>{for keys}</div>)
+    }
+
+    fn render_discovery(&self, ctx: &Context<Self>, discovery: &Discovery) -> Html {
+        let rows = self
+            .settings
+            .layout
+            .keycaps()
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let keys = row.into_iter().map(|cap| {
+                    let revealed = discovery.revealed && cap.base == discovery.target;
+                    html!(<span class={classes!("key", revealed.then_some("revealed"))}>
+                    {revealed.then_some(cap.base)}
+                </span>)
+                });
+                html!(<div class="keyboard-row" style={format!("--row: {i}")}>{for keys}</div>)
+            });
+        html!(<>
+            <div class="prompt">{t_args("discovery-find", &[("char", &discovery.target.to_string())])}</div>
+            <div class="keyboard">{for rows}</div>
+            <button onclick={ctx.link().callback(|_| Msg::StopDiscovery)}>{t("discovery-stop")}</button>
+        </>)
+    }
+
+    fn render_language_select(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let index = select.selected_index() as usize;
+            // The first option follows the browser
+            Msg::SetLanguage(index.checked_sub(1).map(|i| Language::ALL[i]))
+        });
+        html!(
+            <select {onchange}>
+                <option selected={self.settings.language.is_none()}>{t("language-auto")}</option>
+                {for Language::ALL.iter().map(|l| html!(
+                    <option selected={Some(*l) == self.settings.language}>{l.native_name()}</option>
+                ))}
+            </select>
+        )
+    }
+
+    fn render_nav(&self, ctx: &Context<Self>) -> Html {
+        let current = Route::current(ctx.link());
+        let links = Route::ALL.into_iter().map(|route| {
+            let classes = classes!((route == current).then_some("current"));
+            html!(<><Link<Route> to={route} {classes}>{t(route.message_key())}</Link<Route>>{" "}</>)
+        });
+        html!(<nav class="pages">{for links}</nav>)
+    }
+}
+
+impl yew::Component for Practice {
+    type Message = Msg;
+    type Properties = PracticeProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let cb: Closure<dyn Fn(Event)> = Closure::new(move |e: Event| {
+            if targets_text_field(&e) {
+                return;
+            }
+            let e = e.dyn_into::<KeyboardEvent>().unwrap();
+            link.send_message(Msg::KeyPress(e.into()));
+        });
+        #[cfg(debug_assertions)]
+        injection::install(ctx.link().clone());
+
+        body()
+            .add_event_listener_with_callback("keydown", cb.into_js_value().unchecked_ref())
+            .unwrap();
+
+        let link = ctx.link().clone();
+        let resize_cb: Closure<dyn Fn()> = Closure::new(move || link.send_message(Msg::Resize));
+        gloo_utils::window()
+            .add_event_listener_with_callback("resize", resize_cb.into_js_value().unchecked_ref())
+            .unwrap();
+
+        let link = ctx.link().clone();
+        let paste_cb: Closure<dyn Fn(Event)> = Closure::new(move |e: Event| {
+            if targets_text_field(&e) {
+                return;
+            }
+            link.send_message(Msg::Paste(e));
+        });
+        body()
+            .add_event_listener_with_callback("paste", paste_cb.into_js_value().unchecked_ref())
+            .unwrap();
+
+        let link = ctx.link().clone();
+        // Saves are otherwise delayed and the page may be gone by then.
+        // A hidden tab may be closed without any other event.
+        let hide_cb: Closure<dyn Fn()> = Closure::new(move || link.send_message(Msg::PageHide));
+        let hide_cb = hide_cb.into_js_value();
+        for event in ["beforeunload", "pagehide"] {
+            gloo_utils::window()
+                .add_event_listener_with_callback(event, hide_cb.unchecked_ref())
+                .unwrap();
+        }
+        let link = ctx.link().clone();
+        let visibility_cb: Closure<dyn Fn()> = Closure::new(move || {
+            if gloo_utils::document().hidden() {
+                link.send_message(Msg::PageHide);
+            }
+        });
+        gloo_utils::document()
+            .add_event_listener_with_callback(
+                "visibilitychange",
+                visibility_cb.into_js_value().unchecked_ref(),
+            )
+            .unwrap();
+
+        let link = ctx.link().clone();
+        let mouse_cb: Closure<dyn Fn(Event)> = Closure::new(move |e: Event| {
+            if targets_text_field(&e) {
+                return;
+            }
+            link.send_message(Msg::AuxMouse(e.dyn_into::<MouseEvent>().unwrap()));
+        });
+        // Back and forward navigation happens on mouseup, so it can be prevented there
+        body()
+            .add_event_listener_with_callback("mouseup", mouse_cb.into_js_value().unchecked_ref())
+            .unwrap();
+        aux_input::watch_gamepads(ctx.link().callback(Msg::AuxPress));
+        fullscreen::watch(ctx.link().callback(Msg::FullscreenChanged));
+        let location = ctx
+            .link()
+            .add_location_listener(ctx.link().callback(|_| Msg::Navigate));
+        tabs::claim(ctx.link().callback(|()| Msg::TakenOver));
+
+        if let Err(e) = migrations::run() {
+            tracing::error!("Could not upgrade the stored data: {e}");
+        }
+        let store = ctx.props().store.clone();
+        let stats = TypingErrors::load(&*store);
+        let settings = Settings::load(&*store);
+        // Stats stored before the event log existed become its baseline
+        let events = EventLog::load(&*store).unwrap_or_else(|| EventLog::new(stats.clone()));
+        // Stats of an older scoring are used until the replay catches up
+        let outdated =
+            stats.scoring_version != SCORING_VERSION || stats.penalties != settings.penalties;
+        let recompute = outdated.then(|| {
+            schedule_replay_step(ctx);
+            Replay::new(&events, settings.penalties)
+        });
+        // The first prompt is always symbols, so it needs no fetching
+        if settings.prompt_source != sources::DEFAULT_SOURCE {
+            request_prompt(
+                ctx,
+                sources::find(&settings.prompt_source, &settings),
+                CharWeights::new(&stats, &settings.expected_chars(), js_sys::Date::now()),
+            );
+        }
+        let language_loading = load_language(ctx, settings.language());
+        let ambient = start_ambient(&settings);
+        let key_sound = start_key_sound(&settings);
+        let last_session = SessionSummary::load(&*store);
+        let discovery_stats = DiscoveryStats::load(&*store);
+        let numpad_stats = DigitStats::load(&*store, numpad::NUMPAD_STORAGE_KEY);
+        let number_row_stats = DigitStats::load(&*store, numbers::NUMBER_ROW_STORAGE_KEY);
+        let book = Book::load(&*store);
+        let curriculum = Curriculum::load();
+        let history = History::load(&*store);
+        let exam_results = ExamResults::load(&*store);
+        let regression = analytics::accuracy_regression(&analytics::daily_accuracy(&history));
+        startup::mark("startup-state-loaded");
+        let save_state = if settings.persist_stats {
+            SaveState::Saved
+        } else {
+            SaveState::Disabled
+        };
+
+        Practice {
+            prompt: sources::symbols(
+                &settings.symbol_chars(),
+                &CharWeights::new(
+                    &stats,
+                    &practiced_chars(&settings, curriculum.as_ref(), &settings.expected_chars()),
+                    js_sys::Date::now(),
+                ),
+                settings.prompt_len,
+            ),
+            correctness: vec![],
+            expected_chars: settings.expected_chars(),
+            mistyped: Default::default(),
+            error_stats: stats,
+            events,
+            recompute,
+            mistakes_on_this_line: 0,
+            input_guard: Default::default(),
+            flagged: None,
+            caps_lock: false,
+            settings,
+            shown_at: startup::now(),
+            started_at: None,
+            finished_at: None,
+            keystrokes: 0,
+            drill: None,
+            data_entry: None,
+            data_entry_result: None,
+            exam: None,
+            exam_loading: Loading::Idle,
+            exam_results,
+            timed: None,
+            curriculum,
+            unlocked_key: None,
+            timed_result: None,
+            prompts_done: 0,
+            retention_check: false,
+            log: vec![],
+            backspaces: 0,
+            previous_key: None,
+            file_reader: None,
+            drill_loading: Loading::Idle,
+            language_loading,
+            _location: location,
+            warm_up: None,
+            read_ahead: ReadAhead::Empty,
+            source_loading: Loading::Idle,
+            history,
+            store,
+            session_tags: Default::default(),
+            paused_at: None,
+            zen: false,
+            pacer: None,
+            save_state,
+            prompt_ref: NodeRef::default(),
+            char_ref: NodeRef::default(),
+            chars_per_line: 0,
+            ambient,
+            stream: Default::default(),
+            seen_hints: SeenHints::load(),
+            regression,
+            regression_dismissed: false,
+            active_at: js_sys::Date::now(),
+            advance_at: None,
+            discovery: None,
+            discovery_stats,
+            numpad_stats,
+            number_row_stats,
+            key_sound,
+            sound_latency: keysound::load_latency(),
+            calibration: None,
+            session_started_at: js_sys::Date::now(),
+            goal: None,
+            goal_summary: None,
+            fumbled_at: BTreeSet::new(),
+            word_stats: BTreeMap::new(),
+            last_export: LastExport::load(),
+            rhythm: None,
+            book,
+            stats_page: StatsPage {
+                comparison: [(None, None); 2],
+                tag_filter: Default::default(),
+            },
+            settings_page: SettingsPage {
+                pending_import: None,
+                import_error: None,
+                file_password: String::new(),
+                shared_preset: None,
+                deletion_range: (None, None),
+                pending_deletion: None,
+                penalty_preview: None,
+                binding: None,
+            },
+            lessons_page: LessonsPage {
+                bookmark_note: String::new(),
+                book_error: None,
+                book_substitutions: vec![],
+            },
+            practice_page: PracticePage {
+                tip: TipState::Pending,
+                last_session,
+                suggestions: vec![],
+                suggestion_shown: 0,
+                unreadable: codec::unreadable(),
+                backup_postponed: false,
+                warm_up_offered: true,
+            },
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            startup::mark("startup-first-render");
+            bootstrap::dismiss();
+            schedule_idle_check(ctx);
+        }
+        // Fonts, zoom and the window size all change how much fits on a line
+        if let Some(width) = self.measure_line_width() {
+            if width != self.chars_per_line {
+                ctx.link().send_message(Msg::SetLineWidth(width));
+            }
+        }
+        if let SaveState::Unsaved = self.save_state {
+            self.save_state = SaveState::Scheduled;
+            schedule_save(ctx);
+        }
+        if let (ReadAhead::Empty, None) = (&self.read_ahead, &self.drill) {
+            self.read_ahead = ReadAhead::Scheduled;
+            let next = self.prompt_source().next_prompt(self.char_weights());
+            // Yield to the browser first so the read-ahead never delays a paint
+            ctx.link().send_future(async {
+                TimeoutFuture::new(0).await;
+                Msg::ReadAhead(next.await)
+            });
+        }
+        // A timed test at a fast pace may get through several prompts
+        // in the time the source takes to produce one
+        if self.timed.as_ref().is_some_and(|t| t.hand.is_none()) {
+            let untyped = self.prompt.chars().count() - self.correctness.len();
+            let pace = self
+                .live_speed_sample()
+                .map(|s| s.chars as f64 / s.millis)
+                .filter(|pace| pace.is_finite() && *pace > 0.0)
+                .or_else(|| self.history.recent_pace(RECENT_RESULTS));
+            if self.stream.wants_more(pace, untyped) {
+                self.stream.requested(startup::now());
+                let next = self.prompt_source().next_prompt(self.char_weights());
+                ctx.link().send_future(async {
+                    TimeoutFuture::new(0).await;
+                    Msg::StreamPrompt(next.await)
+                });
+            }
+        }
+    }
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let all_done = self.correctness.len() == self.prompt.chars().count();
+        let all_correct = all_done && self.mistakes_on_this_line == 0;
+        let app_class = classes!("app", self.settings.large_text.then_some("large-text"));
+        let prompt = if let Some(discovery) = &self.discovery {
+            self.render_discovery(ctx, discovery)
+        } else {
+            html!(<>
+            <div class="prompt" ref={self.prompt_ref.clone()}>
+                <span class="measure" ref={self.char_ref.clone()}>{"0"}</span>
+                {self.render_chars()}
+            </div>
+            {self.settings.show_keyboard.then(|| if self.data_entry.is_some() {
+                self.render_numpad()
+            } else {
+                self.render_keyboard()
+            })}
+            {all_correct.then(|| html!(<p>{t("all-correct")}</p>))}
+            {all_done.then(|| self.render_word_accuracy())}
+            {(all_done && self.is_sloppy()).then(|| html!(<p class="flagged">{t("sloppy-prompt")}</p>))}
+            {all_done.then(|| html!(<p>{match self.advance_at {
+                Some(at) => {
+                    let secs = ((at - startup::now()) / 1000.0).ceil().max(0.0);
+                    t_args("auto-advance-in", &[("seconds", &secs)])
+                }
+                None => t("enter-to-continue"),
+            }}</p>))}
+            {self.paused_at.map(|_| html!(<p class="flagged">
+                {t("paused")}{" "}
+                <button onclick={ctx.link().callback(|_| Msg::RunAction(AuxAction::Pause))}>
+                    {t("resume")}
+                </button>
+            </p>))}
+            </>)
+        };
+        if self.zen {
+            return html!(
+                <div class={app_class} style={self.feedback_delay_style()}>
+                {prompt}
+                <button onclick={ctx.link().callback(|_| Msg::RunAction(AuxAction::ToggleZen))}>
+                    {t("leave-zen")}
+                </button>
+                {self.render_save_state()}
+                <style>{&self.settings.custom_css}</style>
+                </div>
+            );
+        }
+        html!(
+            <div class={app_class} style={self.feedback_delay_style()}>
+            {self.render_save_state()}
+            <a href="https://github.com/samoylovfp/Typing-tutor/">{"GitHub"}</a>
+            {" "}{self.render_language_select(ctx)}
+            {render_loading(&self.language_loading)}
+            {" "}
+            <button onclick={ctx.link().callback(|_| Msg::RunAction(AuxAction::ToggleFullscreen))}>
+                {t("fullscreen")}
+            </button>
+            {self.recompute.as_ref().map(|replay| {
+                let (done, total) = replay.progress(&self.events);
+                html!(<span>{" "}{t_args("recompute-progress", &[("done", &done), ("total", &total)])}</span>)
+            })}
+            {" "}
+            <KeyboardPicker
+                keyboards={self.settings.keyboards.clone()}
+                active={self.settings.active_keyboard}
+                on_select={ctx.link().callback(Msg::SelectKeyboard)}
+                on_add={ctx.link().callback(Msg::AddKeyboard)}
+            />
+            <br />
+            {self.render_nav(ctx)}
+            {self.render_page(ctx, Route::current(ctx.link()), prompt)}
+                <style>{&self.settings.custom_css}</style>
+            </div>
+        )
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let reset = |s: &mut Self| {
+            let due = review::due_keys(&s.error_stats, js_sys::Date::now());
+            s.retention_check = s.drill.is_none()
+                && s.warm_up.is_none()
+                && s.book_passage().is_none()
+                && s.data_entry.is_none()
+                && s.exam.is_none()
+                && s.timed.is_none()
+                && s.prompts_done % review::REVIEW_EVERY == review::REVIEW_EVERY - 1
+                && !due.is_empty();
+            let read_ahead = std::mem::replace(&mut s.read_ahead, ReadAhead::Empty);
+            // A warm-up comes before the book, a timed test is typed from the source
+            let book = s.book_passage().filter(|_| s.timed.is_none());
+            let line = s.warm_up.as_ref().map(WarmUp::prompt).or(book);
+            s.prompt = match (&s.exam, &s.data_entry, &s.drill, line, read_ahead) {
+                (Some(exam), _, _, _, _) => exam.prompt().to_string(),
+                (None, Some(test), _, _, _) => test.prompt().to_string(),
+                (None, None, Some(drill), _, _) => drill.prompt().to_string(),
+                (None, None, None, Some(line), _) => line.to_string(),
+                (None, None, None, None, _) if s.retention_check => {
+                    sources::symbols(&due, &s.char_weights(), s.settings.prompt_len)
+                }
+                // The source's prompts use both hands
+                (None, None, None, None, _)
+                    if s.timed.as_ref().is_some_and(|t| t.hand.is_some()) =>
+                {
+                    s.random_symbols()
+                }
+                (None, None, None, None, ReadAhead::Ready(prompt)) => prompt,
+                (None, None, None, None, _) => s.random_symbols(),
+            };
+            s.correctness.clear();
+            s.mistakes_on_this_line = 0;
+            s.fumbled_at.clear();
+            s.flagged = None;
+            s.shown_at = startup::now();
+            s.started_at = None;
+            s.finished_at = None;
+            s.keystrokes = 0;
+            s.previous_key = None;
+            s.pacer = None;
+            s.advance_at = None;
+            // Nothing is being typed between prompts for a save to slow down
+            if matches!(s.save_state, SaveState::Unsaved | SaveState::Scheduled) {
+                s.save_stats();
+            }
+        };
+        // Only the practice page is typed on
+        if matches!(msg, Msg::KeyPress(_) | Msg::Paste(_))
+            && Route::current(ctx.link()) != Route::Practice
+        {
+            return false;
+        }
+        // Typing anything but Enter during the countdown cancels it
+        if let Msg::KeyPress(ev) = &msg {
+            if ev.key() != "Enter" {
+                self.advance_at = None;
+            }
+            self.active_at = js_sys::Date::now();
+            self.practice_page.suggestions.clear();
+        }
+        let caps_lock_changed = match &msg {
+            Msg::KeyPress(ev) => {
+                let on = ev.caps_lock();
+                std::mem::replace(&mut self.caps_lock, on) != on
+            }
+            _ => false,
+        };
+
+        // Browsers hold sound back until the user interacts with the page
+        if let (Msg::KeyPress(_), Some(ambient)) = (&msg, &self.ambient) {
+            ambient.resume();
+        }
+        if let (Msg::KeyPress(ev), Some(sound), None) = (&msg, &self.key_sound, &self.calibration) {
+            if ev.key().chars().count() == 1 || ev.key() == "Backspace" {
+                sound.play(Sound::Key, 0.0);
+            }
+        }
+        match msg {
+            #[cfg(debug_assertions)]
+            Msg::InjectionState(resolve) => {
+                let snapshot = injection::Snapshot {
+                    route: format!("{:?}", Route::current(ctx.link())),
+                    mode: self.mode().map(|m| format!("{m:?}")),
+                    prompt: self.prompt.clone(),
+                    typed: self.correctness.len(),
+                    mistakes: self.mistakes_on_this_line,
+                    keystrokes: self.keystrokes,
+                    prompts_done: self.prompts_done,
+                    results: self.history.results.len(),
+                };
+                snapshot.resolve(&resolve);
+                return false;
+            }
+            Msg::StartRecompute => {
+                self.recompute = Some(Replay::new(&self.events, self.settings.penalties));
+                schedule_replay_step(ctx);
+            }
+            Msg::RecomputeStep => {
+                let Some(replay) = &mut self.recompute else {
+                    return false;
+                };
+                match replay.step(&self.events, REPLAY_SLICE) {
+                    Some(stats) => {
+                        self.error_stats = stats;
+                        self.mark_unsaved();
+                        self.recompute = None;
+                    }
+                    None => schedule_replay_step(ctx),
+                }
+            }
+            Msg::PageHide => {
+                self.save_stats();
+                // A visit without typing keeps the recap of the last real session
+                if !self.log.is_empty() {
+                    let input = metrics::MetricInput {
+                        log: &self.log,
+                        backspaces: self.backspaces,
+                        settings: &self.settings,
+                    };
+                    let summary = SessionSummary::new(
+                        self.session_started_at,
+                        js_sys::Date::now(),
+                        self.prompts_done,
+                        &input,
+                    );
+                    if let Err(e) = summary.save(&*self.store, self.settings.compact_storage) {
+                        tracing::warn!("Saving the session summary failed: {e}");
+                    }
+                }
+                return false;
+            }
+            Msg::DismissLastSession => self.practice_page.last_session = None,
+            Msg::StartGoal(goal) => {
+                self.goal = Some(GoalSession::new(
+                    goal,
+                    js_sys::Date::now(),
+                    self.prompts_done,
+                    self.log.len(),
+                    self.backspaces,
+                ));
+                self.goal_summary = None;
+                if let Goal::Minutes(_) = goal {
+                    schedule_goal_tick(ctx);
+                }
+            }
+            Msg::StopGoal => self.finish_goal(false),
+            Msg::GoalTick => {
+                let Some(goal) = &self.goal else {
+                    return false;
+                };
+                if goal.reached(js_sys::Date::now(), self.prompts_done) {
+                    self.finish_goal(true);
+                } else {
+                    schedule_goal_tick(ctx);
+                }
+            }
+            Msg::DismissGoalSummary => self.goal_summary = None,
+            Msg::SaveDue => {
+                // Saving serializes everything, it waits for a pause in typing
+                let typing = self
+                    .previous_key
+                    .is_some_and(|(_, at)| startup::now() - at < f64::from(SAVE_DELAY_MS));
+                if typing {
+                    schedule_save(ctx);
+                    return false;
+                }
+                // Saved between prompts since
+                if !matches!(self.save_state, SaveState::Scheduled) {
+                    return false;
+                }
+                self.save_stats();
+            }
+            // Re-rendering measures the new width
+            Msg::Resize => {}
+            Msg::SetLineWidth(width) => self.chars_per_line = width,
+            Msg::Paste(ev) => {
+                ev.prevent_default();
+                self.flagged = Some(Suspicion::Paste);
+            }
+            Msg::AuxMouse(ev) => {
+                let Some(button) = AuxButton::from_mouse(ev.button()) else {
+                    return false;
+                };
+                if self.settings_page.binding.is_some()
+                    || aux_input::action_for(&self.settings.aux_bindings, button).is_some()
+                {
+                    ev.prevent_default();
+                }
+                return Component::update(self, ctx, Msg::AuxPress(button));
+            }
+            Msg::AuxPress(button) => {
+                if let Some(action) = self.settings_page.binding.take() {
+                    aux_input::bind(&mut self.settings.aux_bindings, button, action);
+                    self.save_settings();
+                } else {
+                    let Some(action) = aux_input::action_for(&self.settings.aux_bindings, button)
+                    else {
+                        return false;
+                    };
+                    return Component::update(self, ctx, Msg::RunAction(action));
+                }
+            }
+            // Exam and timed test rules allow neither pausing nor skipping lines
+            Msg::RunAction(AuxAction::NextPrompt | AuxAction::Pause)
+                if self.exam.is_some() || self.timed.is_some() =>
+            {
+                return false
+            }
+            Msg::RunAction(AuxAction::NextPrompt) => {
+                self.paused_at = None;
+                reset(self);
+            }
+            Msg::RunAction(AuxAction::Pause) => {
+                let now = startup::now();
+                match self.paused_at.take() {
+                    // Time spent paused does not count towards the speed
+                    Some(at) if self.finished_at.is_none() => {
+                        let paused_for = now - at;
+                        self.shown_at += paused_for;
+                        if let Some(started_at) = &mut self.started_at {
+                            *started_at += paused_for;
+                        }
+                        if let Some((_c, pressed_at)) = &mut self.previous_key {
+                            *pressed_at += paused_for;
+                        }
+                    }
+                    Some(_) => {}
+                    None => {
+                        self.paused_at = Some(now);
+                        self.advance_at = None;
+                    }
+                }
+            }
+            // Leaving fullscreen leaves zen too, through `FullscreenChanged`
+            Msg::RunAction(AuxAction::ToggleZen | AuxAction::ToggleFullscreen)
+                if fullscreen::is_active() =>
+            {
+                fullscreen::exit();
+                return false;
+            }
+            Msg::RunAction(AuxAction::ToggleZen) => self.zen = !self.zen,
+            Msg::RunAction(AuxAction::ToggleFullscreen) => {
+                fullscreen::enter();
+                return false;
+            }
+            Msg::FullscreenChanged(on) => self.zen = on,
+            Msg::TakenOver => {
+                tracing::info!("Another tab took over saving statistics");
+                self.save_state = SaveState::OtherTab;
+            }
+            Msg::SetAmbient(soundscape) => {
+                self.settings.ambient = soundscape;
+                self.save_settings();
+                self.ambient = start_ambient(&self.settings);
+            }
+            Msg::PreviewPenalties(penalties) => {
+                let stats = self.events.derive_errors(penalties);
+                self.settings_page.penalty_preview = Some((penalties, stats));
+            }
+            Msg::ApplyPenalties => {
+                let Some((penalties, _stats)) = self.settings_page.penalty_preview.take() else {
+                    return false;
+                };
+                self.settings.penalties = penalties;
+                self.save_settings();
+                return Component::update(self, ctx, Msg::StartRecompute);
+            }
+            Msg::SetAmbientVolume(volume) => {
+                self.settings.ambient_volume = volume.clamp(0.0, 1.0);
+                self.save_settings();
+                if let Some(ambient) = &self.ambient {
+                    ambient.set_volume(self.settings.ambient_volume);
+                }
+                return false;
+            }
+            Msg::StartBinding(action) => self.settings_page.binding = Some(action),
+            Msg::ClearBindings(action) => {
+                self.settings.aux_bindings.retain(|b| b.action != action);
+                self.save_settings();
+            }
+            Msg::SetSpeedUnit(unit) => {
+                self.settings.speed_unit = unit;
+                self.save_settings();
+            }
+            Msg::SetLanguage(language) => {
+                self.settings.language = language;
+                self.save_settings();
+                self.language_loading = load_language(ctx, self.settings.language());
+            }
+            Msg::LanguageLoaded(language, res) => {
+                // The user may have picked another language in the meantime
+                if language != self.settings.language() {
+                    return false;
+                }
+                match res {
+                    Ok(resource) => {
+                        i18n::set_resource(&resource);
+                        self.language_loading = Loading::Idle;
+                    }
+                    Err(e) => self.language_loading = Loading::Failed(e),
+                }
+            }
+            Msg::ExtendExpectedChars(chars) => {
+                self.settings.extra_chars.extend(chars);
+                self.save_settings();
+                self.expected_chars = self.settings.expected_chars();
+            }
+            Msg::PracticeText(text) => {
+                self.timed = None;
+                self.drill = None;
+                self.data_entry = None;
+                self.exam = None;
+                self.warm_up = None;
+                reset(self);
+                self.prompt = text;
+                route::go(ctx.link(), Route::Practice);
+            }
+            Msg::Navigate => {}
+            Msg::StartWarmUp => {
+                self.warm_up = Some(WarmUp::new(self.settings.typed_layout()));
+                self.practice_page.warm_up_offered = false;
+                reset(self);
+            }
+            Msg::SkipWarmUp => {
+                self.practice_page.warm_up_offered = false;
+                if self.warm_up.take().is_some() {
+                    reset(self);
+                }
+            }
+            Msg::SelectKeyboard(id) => {
+                self.settings.active_keyboard = id;
+                self.save_settings();
+            }
+            Msg::AddKeyboard(name) => {
+                let id = Ulid::new(js_sys::Date::now());
+                self.settings.keyboards.push(Keyboard { id, name });
+                self.settings.active_keyboard = Some(id);
+                self.save_settings();
+            }
+            Msg::SetFingerMap(map) => {
+                self.settings.finger_map = map;
+                self.save_settings();
+            }
+            Msg::SetLayerChars(chars) => {
+                self.settings.layer_chars = chars.chars().filter(|c| !c.is_whitespace()).collect();
+                self.save_settings();
+            }
+            Msg::SetCustomCss(css) => {
+                self.settings.custom_css = css;
+                self.save_settings();
+            }
+            Msg::SetLargeText(on) => {
+                self.settings.large_text = on;
+                self.save_settings();
+            }
+            Msg::SetShowKeyboard(on) => {
+                self.settings.show_keyboard = on;
+                self.save_settings();
+            }
+            Msg::SetReadAloud(on) => {
+                self.settings.read_aloud = on;
+                self.save_settings();
+            }
+            Msg::SetKeySounds(on) => {
+                self.settings.key_sounds = on;
+                self.save_settings();
+                self.key_sound = start_key_sound(&self.settings);
+            }
+            Msg::SetSoundTheme(theme) => {
+                self.settings.sound_theme = theme;
+                self.save_settings();
+                self.key_sound = start_key_sound(&self.settings);
+                // A sample of the theme
+                if let Some(sound) = &self.key_sound {
+                    sound.play(Sound::Key, 0.0);
+                }
+            }
+            Msg::SetErrorSounds(on) => {
+                self.settings.error_sounds = on;
+                self.save_settings();
+            }
+            Msg::StartCalibration => {
+                // Calibrating works with the clicks off too
+                if self.key_sound.is_none() {
+                    self.key_sound = KeySound::new(self.settings.sound_theme)
+                        .map_err(|e| tracing::warn!("Clicks are unavailable: {e}"))
+                        .ok();
+                }
+                let Some(sound) = &self.key_sound else {
+                    return false;
+                };
+                let calibration = Calibration::start(sound, startup::now());
+                let (first_click_at, wait) = (
+                    calibration.first_click_at,
+                    calibration.ends_at() - startup::now(),
+                );
+                self.calibration = Some(calibration);
+                ctx.link().send_future(async move {
+                    TimeoutFuture::new(wait as u32).await;
+                    Msg::CalibrationDone(first_click_at)
+                });
+            }
+            Msg::CalibrationDone(first_click_at) => {
+                if self.calibration.as_ref().map(|c| c.first_click_at) != Some(first_click_at) {
+                    return false;
+                }
+                let Some(calibration) = self.calibration.take() else {
+                    return false;
+                };
+                match calibration.latency() {
+                    Some(latency) => {
+                        keysound::save_latency(latency);
+                        self.sound_latency = Some(latency);
+                    }
+                    None => tracing::warn!("Too few key presses to calibrate"),
+                }
+                if !self.settings.key_sounds {
+                    self.key_sound = None;
+                }
+            }
+            Msg::SetPaceCaret(on) => {
+                self.settings.pace_caret = on;
+                self.save_settings();
+            }
+            Msg::CursorFrame(id) => {
+                let (Some(pacer), Some(started_at)) = (&mut self.pacer, self.started_at) else {
+                    return false;
+                };
+                // A frame of a prompt that was completed or restarted since
+                if pacer.id != id || self.finished_at.is_some() {
+                    return false;
+                }
+                let now = startup::now();
+                pacer.tick(self.paused_at.unwrap_or(now) - started_at, now);
+                schedule_cursor_frame(ctx, id);
+            }
+            Msg::SetHomeRowShift(on) => {
+                self.settings.home_row_shift = on;
+                self.save_settings();
+            }
+            Msg::SetSessionTags(tags) => {
+                self.session_tags = tags
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            Msg::ToggleTagFilter(tag) => {
+                if !self.stats_page.tag_filter.remove(&tag) {
+                    self.stats_page.tag_filter.insert(tag);
+                }
+            }
+            Msg::SetNote(id, note) => {
+                if let Some(result) = self.history.get_mut(id) {
+                    result.note = note;
+                    result.meta.updated_at = js_sys::Date::now();
+                    self.mark_unsaved();
+                }
+            }
+            Msg::StreamPrompt(res) => {
+                self.stream.received(res.ok(), startup::now());
+                return false;
+            }
+            Msg::ReadAhead(res) => {
+                if let ReadAhead::Scheduled = self.read_ahead {
+                    let prompt = match res {
+                        Ok(prompt) => prompt,
+                        // Practice goes on with symbols while the source is unavailable
+                        Err(e) => {
+                            self.source_loading = Loading::Failed(e);
+                            self.random_symbols()
+                        }
+                    };
+                    self.read_ahead = ReadAhead::Ready(prompt);
+                }
+                return matches!(self.source_loading, Loading::Failed(_));
+            }
+            Msg::SetPromptSource(id) => {
+                self.settings.prompt_source = id;
+                self.save_settings();
+                self.read_ahead = ReadAhead::Empty;
+                self.stream.clear();
+                self.source_loading = Loading::InProgress;
+                request_prompt(ctx, self.prompt_source(), self.char_weights());
+            }
+            Msg::SetSourceUrl(url) => {
+                self.settings.source_url = url;
+                self.save_settings();
+                return Component::update(
+                    self,
+                    ctx,
+                    Msg::SetPromptSource(self.settings.prompt_source.clone()),
+                );
+            }
+            Msg::SetSnippetLanguage(language) => {
+                self.settings.snippet_language = language;
+                self.save_settings();
+                return Component::update(
+                    self,
+                    ctx,
+                    Msg::SetPromptSource(self.settings.prompt_source.clone()),
+                );
+            }
+            Msg::SetUppercaseWords(on) => {
+                self.settings.uppercase_words = on;
+                self.save_settings();
+                return Component::update(
+                    self,
+                    ctx,
+                    Msg::SetPromptSource(self.settings.prompt_source.clone()),
+                );
+            }
+            Msg::SetBuiltInBlocklist(on) => {
+                self.settings.built_in_blocklist = on;
+                self.save_settings();
+                return Component::update(
+                    self,
+                    ctx,
+                    Msg::SetPromptSource(self.settings.prompt_source.clone()),
+                );
+            }
+            Msg::SetBlockedWords(words) => {
+                self.settings.blocked_words = words;
+                self.save_settings();
+                return Component::update(
+                    self,
+                    ctx,
+                    Msg::SetPromptSource(self.settings.prompt_source.clone()),
+                );
+            }
+            Msg::SourcePrompt(Err(e)) => self.source_loading = Loading::Failed(e),
+            Msg::SourcePrompt(Ok(prompt)) => {
+                self.source_loading = Loading::Idle;
+                // Replace the current prompt only if it is a regular one that is not started yet
+                let regular = self.drill.is_none()
+                    && self.exam.is_none()
+                    && self.data_entry.is_none()
+                    && !self.retention_check;
+                if regular && self.correctness.is_empty() {
+                    self.prompt = prompt;
+                }
+            }
+            Msg::StartBuiltinDrill => {
+                self.drill_loading = Loading::InProgress;
+                ctx.link().send_future(async {
+                    match assets::fetch_text(drill::SENTENCES_ASSET).await {
+                        Ok(text) => Msg::StartDrill(drill::parse_sentences(&text)),
+                        Err(e) => Msg::DrillFailed(e),
+                    }
+                });
+            }
+            Msg::DrillFailed(e) => self.drill_loading = Loading::Failed(e),
+            Msg::StartDrill(sentences) if sentences.is_empty() => return false,
+            Msg::StartDrill(sentences) => {
+                self.drill_loading = Loading::Idle;
+                self.timed = None;
+                self.data_entry = None;
+                self.exam = None;
+                self.warm_up = None;
+                self.drill = Some(RepeatDrill::new(sentences, self.settings.clean_repetitions));
+                reset(self);
+                route::go(ctx.link(), Route::Practice);
+            }
+            Msg::StartNumberDrill(keys) => {
+                let sentences = numbers::sentences(keys, &mut rand::thread_rng());
+                Component::update(self, ctx, Msg::StartDrill(sentences));
+                if let Some(drill) = &mut self.drill {
+                    drill.numbers = true;
+                }
+            }
+            Msg::StopDrill => {
+                self.drill = None;
+                reset(self);
+            }
+            Msg::StartDataEntry => {
+                self.timed = None;
+                self.drill = None;
+                self.exam = None;
+                self.warm_up = None;
+                self.data_entry = Some(DataEntryTest::new());
+                self.data_entry_result = None;
+                reset(self);
+                route::go(ctx.link(), Route::Practice);
+            }
+            Msg::StopDataEntry => {
+                self.data_entry = None;
+                reset(self);
+            }
+            Msg::PrintDataEntryResult => {
+                let _ = gloo_utils::window().print();
+                return false;
+            }
+            Msg::CloseDataEntryResult => self.data_entry_result = None,
+            Msg::StartExam => {
+                self.exam_loading = Loading::InProgress;
+                ctx.link().send_future(async {
+                    match assets::fetch_text(exam::PASSAGE_ASSET).await {
+                        Ok(text) => Msg::BeginExam(drill::parse_sentences(&text)),
+                        Err(e) => Msg::ExamFailed(e),
+                    }
+                });
+            }
+            Msg::ExamFailed(e) => self.exam_loading = Loading::Failed(e),
+            Msg::BeginExam(lines) if lines.is_empty() => return false,
+            Msg::BeginExam(lines) => {
+                self.exam_loading = Loading::Idle;
+                self.timed = None;
+                self.drill = None;
+                self.data_entry = None;
+                self.paused_at = None;
+                self.warm_up = None;
+                self.exam = Some(Exam::new(lines, self.settings.exam_backspace_limit));
+                reset(self);
+                route::go(ctx.link(), Route::Practice);
+            }
+            Msg::ExamTick(started_at) => {
+                let Some(exam) = &self.exam else {
+                    return false;
+                };
+                // A tick of an exam that was stopped or restarted since
+                if exam.started_at != Some(started_at) {
+                    return false;
+                }
+                let now = startup::now();
+                if exam.remaining(now) > 0.0 {
+                    schedule_exam_tick(ctx, started_at);
+                } else {
+                    self.finish_exam(now);
+                    reset(self);
+                }
+            }
+            Msg::StartDiscovery => {
+                self.next_discovery(ctx, None);
+                route::go(ctx.link(), Route::Practice);
+            }
+            Msg::StartMigration(to) => {
+                self.settings.migration = Some(Migration::new(self.settings.layout, to));
+                self.settings.layout = to;
+                self.migration_changed();
+                route::go(ctx.link(), Route::Practice);
+            }
+            Msg::NextMigrationStage => {
+                let Some(migration) = &mut self.settings.migration else {
+                    return false;
+                };
+                migration.stage = (migration.stage + 1).min(layout_switch::STAGES - 1);
+                self.migration_changed();
+            }
+            Msg::SetPracticingNewLayout(new) => {
+                let Some(migration) = &mut self.settings.migration else {
+                    return false;
+                };
+                migration.practicing_new = new;
+                self.migration_changed();
+            }
+            Msg::EndMigration => {
+                self.settings.migration = None;
+                self.migration_changed();
+            }
+            Msg::StopDiscovery => self.discovery = None,
+            Msg::BookFileChosen(None) => return false,
+            Msg::BookFileChosen(Some(file)) => {
+                let name = file.name();
+                let title = name
+                    .rsplit_once('.')
+                    .map_or(name.as_str(), |(stem, _)| stem);
+                let title = title.to_string();
+                let link = ctx.link().clone();
+                self.file_reader = Some(if name.to_lowercase().ends_with(".epub") {
+                    gloo_file::callbacks::read_as_bytes(&file.into(), move |res| {
+                        let res = res
+                            .map_err(|e| e.to_string())
+                            .and_then(|bytes| epub::read(&bytes));
+                        let (title, res) = match res {
+                            Ok((epub_title, text)) => (epub_title.unwrap_or(title), Ok(text)),
+                            Err(e) => (title, Err(e)),
+                        };
+                        link.send_message(Msg::BookFileRead(title, res))
+                    })
+                } else {
+                    gloo_file::callbacks::read_as_text(&file.into(), move |res| {
+                        link.send_message(Msg::BookFileRead(title, res.map_err(|e| e.to_string())))
+                    })
+                });
+                return false;
+            }
+            Msg::BookFileRead(title, res) => {
+                self.file_reader = None;
+                self.lessons_page.book_error = None;
+                self.lessons_page.book_substitutions.clear();
+                let res = res.map(|text| {
+                    let (text, substitutions) = self
+                        .settings
+                        .normalization
+                        .apply(&text, &self.expected_chars);
+                    self.lessons_page.book_substitutions = substitutions;
+                    text
+                });
+                match res.and_then(|text| Book::open(title, &text, js_sys::Date::now())) {
+                    Ok(book) => {
+                        let store = &*self.store;
+                        if let Err(e) = book.save_text(store, self.settings.compact_storage) {
+                            self.lessons_page.book_error =
+                                Some(t_args("book-save-failed", &[("error", &e)]));
+                            return true;
+                        }
+                        self.book = Some(book);
+                        self.mark_unsaved();
+                        reset(self);
+                    }
+                    Err(e) => {
+                        self.lessons_page.book_error =
+                            Some(t_args("book-open-failed", &[("error", &e)]))
+                    }
+                }
+            }
+            Msg::SetHesitationPercentile(percentile) => {
+                self.settings.hesitation_percentile = percentile;
+                self.save_settings();
+            }
+            Msg::SetPassageLen(len) => {
+                let typing_book = self.typing_book();
+                self.settings.passage_len = len;
+                self.save_settings();
+                // The passage being typed is not the one to advance past any more
+                if typing_book {
+                    reset(self);
+                }
+            }
+            Msg::SetNormalization(normalization) => {
+                self.settings.normalization = normalization;
+                self.save_settings();
+            }
+            Msg::SetReadingBook(on) => {
+                if let Some(book) = &mut self.book {
+                    book.reading = on;
+                    self.mark_unsaved();
+                    reset(self);
+                }
+            }
+            Msg::JumpInBook(offset) => {
+                if let Some(book) = &mut self.book {
+                    book.jump(offset, js_sys::Date::now());
+                    book.reading = true;
+                    self.mark_unsaved();
+                    reset(self);
+                }
+            }
+            Msg::SetBookmarkNote(note) => {
+                self.lessons_page.bookmark_note = note;
+                return false;
+            }
+            Msg::AddBookmark => {
+                if let Some(book) = &mut self.book {
+                    book.add_bookmark(
+                        std::mem::take(&mut self.lessons_page.bookmark_note),
+                        js_sys::Date::now(),
+                    );
+                    self.mark_unsaved();
+                }
+            }
+            Msg::RemoveBookmark(index) => {
+                if let Some(book) = self.book.as_mut().filter(|b| index < b.bookmarks.len()) {
+                    book.bookmarks.remove(index);
+                    self.mark_unsaved();
+                }
+            }
+            Msg::PlayRhythm => match Rhythm::play(&self.log) {
+                Ok(rhythm) => {
+                    let started_at = js_sys::Date::now();
+                    let duration = rhythm.duration_ms as u32;
+                    ctx.link().send_future(async move {
+                        TimeoutFuture::new(duration).await;
+                        Msg::RhythmEnded(started_at)
+                    });
+                    self.rhythm = Some((started_at, rhythm));
+                }
+                Err(e) => tracing::warn!("Could not play the rhythm: {e}"),
+            },
+            Msg::StopRhythm => self.rhythm = None,
+            Msg::RhythmEnded(started_at) => match &self.rhythm {
+                Some((at, _)) if *at == started_at => self.rhythm = None,
+                // Stopped or played again since
+                _ => return false,
+            },
+            Msg::RevealKey(shown_at) => match &mut self.discovery {
+                Some(discovery) if discovery.shown_at == shown_at => discovery.revealed = true,
+                // Found or stopped since
+                _ => return false,
+            },
+            Msg::SetLayout(layout) => {
+                self.settings.layout = layout;
+                self.save_settings();
+                if self.discovery.is_some() {
+                    self.next_discovery(ctx, None);
+                }
+            }
+            Msg::StartCurriculum => {
+                let curriculum = Curriculum::new();
+                curriculum.save();
+                self.curriculum = Some(curriculum);
+                self.read_ahead = ReadAhead::Empty;
+                reset(self);
+                route::go(ctx.link(), Route::Practice);
+            }
+            Msg::SetCurriculumActive(active) => {
+                let Some(curriculum) = &mut self.curriculum else {
+                    return false;
+                };
+                curriculum.active = active;
+                curriculum.save();
+                self.read_ahead = ReadAhead::Empty;
+            }
+            Msg::ResetCurriculum => {
+                Curriculum::delete();
+                self.curriculum = None;
+                self.unlocked_key = None;
+                self.read_ahead = ReadAhead::Empty;
+            }
+            Msg::SetCurriculumAccuracy(accuracy) => {
+                self.settings.curriculum_accuracy = accuracy.clamp(0.0, 100.0);
+                self.save_settings();
+            }
+            Msg::SetCurriculumWpm(wpm) => {
+                self.settings.curriculum_wpm = wpm.max(0.0);
+                self.save_settings();
+            }
+            Msg::SetDiscoveryDelay(ms) => {
+                self.settings.discovery_delay_ms = ms;
+                self.save_settings();
+            }
+            Msg::StopExam => {
+                self.exam = None;
+                reset(self);
+            }
+            Msg::StartTimedTest(seconds, hand) => {
+                self.drill = None;
+                self.data_entry = None;
+                self.exam = None;
+                self.paused_at = None;
+                self.warm_up = None;
+                self.timed = Some(TimedTest::new(seconds, hand));
+                self.stream.clear();
+                self.timed_result = None;
+                reset(self);
+                route::go(ctx.link(), Route::Practice);
+            }
+            Msg::Tick(started_at) => {
+                let Some(test) = &self.timed else {
+                    return false;
+                };
+                // A tick of a test that was stopped or restarted since
+                if test.started_at != Some(started_at) {
+                    return false;
+                }
+                let now = startup::now();
+                if test.remaining(now) > 0.0 {
+                    schedule_tick(ctx, started_at);
+                } else {
+                    self.finish_timed_test(now);
+                    reset(self);
+                }
+            }
+            Msg::StopTimedTest => {
+                self.timed = None;
+                reset(self);
+            }
+            Msg::CloseTimedResult => self.timed_result = None,
+            Msg::SetExamBackspaceLimit(limit) => {
+                self.settings.exam_backspace_limit = limit;
+                self.save_settings();
+            }
+            Msg::SetWarmUpPrompts(n) => {
+                self.settings.warm_up_prompts = n;
+                self.save_settings();
+            }
+            Msg::SetTimerStart(start) => {
+                self.settings.timer_start = start;
+                self.save_settings();
+            }
+            Msg::SetAutoAdvance(secs) => {
+                self.settings.auto_advance = secs;
+                self.save_settings();
+            }
+            Msg::AdvanceTick(at) => {
+                // A countdown that was canceled since
+                if self.advance_at != Some(at) {
+                    return false;
+                }
+                let now = startup::now();
+                if now < at {
+                    schedule_advance_tick(ctx, at, now);
+                } else {
+                    return Component::update(self, ctx, Msg::CompletePrompt(now));
+                }
+            }
+            Msg::SetMinAccuracy(min) => {
+                self.settings.min_accuracy = min;
+                self.save_settings();
+            }
+            Msg::SetCleanRepetitions(n) => {
+                self.settings.clean_repetitions = n.max(1);
+                self.save_settings();
+            }
+            Msg::DismissTip => self.practice_page.tip = TipState::Dismissed,
+            Msg::DismissHint(mode) => self.seen_hints.insert(mode),
+            Msg::CompareRegression => {
+                let Some(regression) = self.regression else {
+                    return false;
+                };
+                self.stats_page.comparison = [
+                    (Some(regression.before_from), Some(regression.since)),
+                    (Some(regression.since), Some(js_sys::Date::now())),
+                ];
+                route::go(ctx.link(), Route::Stats);
+            }
+            Msg::DismissRegression => self.regression_dismissed = true,
+            Msg::DismissUnreadable => self.practice_page.unreadable.clear(),
+            Msg::IdleCheck => {
+                schedule_idle_check(ctx);
+                if !self.practice_page.suggestions.is_empty() {
+                    self.practice_page.suggestion_shown = (self.practice_page.suggestion_shown + 1)
+                        % self.practice_page.suggestions.len();
+                    return true;
+                }
+                let now = js_sys::Date::now();
+                let assessed =
+                    self.exam.is_some() || self.timed.is_some() || self.data_entry.is_some();
+                if now - self.active_at < suggestions::IDLE_AFTER_MS
+                    || Route::current(ctx.link()) != Route::Practice
+                    || assessed
+                {
+                    return false;
+                }
+                let week = |from: f64| {
+                    comparison::summarize(
+                        &self.events,
+                        Period {
+                            from,
+                            to: from + suggestions::WEEK_MS,
+                        },
+                        &self.settings,
+                    )
+                    .per_key
+                };
+                let last_week = now - suggestions::WEEK_MS;
+                self.practice_page.suggestions = suggestions::suggest(
+                    &week(last_week - suggestions::WEEK_MS),
+                    &week(last_week),
+                    &self.history,
+                    &self.error_stats.error_score,
+                    now,
+                );
+                self.practice_page.suggestion_shown = 0;
+                if self.practice_page.suggestions.is_empty() {
+                    // Nothing to suggest, looked into again after another idle while
+                    self.active_at = now;
+                    return false;
+                }
+            }
+            Msg::ActOnSuggestion(suggestion) => {
+                self.practice_page.suggestions.clear();
+                self.active_at = js_sys::Date::now();
+                let msg = match suggestion {
+                    Suggestion::SlowerKey { key, .. } | Suggestion::WeakestKey { key } => {
+                        let pairs = suggestions::drill_pairs(&bigrams::collect(&self.events), key);
+                        Msg::StartDrill(bigrams::drill_sentences(&pairs))
+                    }
+                    Suggestion::TimedTest { .. } => Msg::StartTimedTest(60, None),
+                };
+                return Component::update(self, ctx, msg);
+            }
+            Msg::DismissSuggestions => {
+                self.practice_page.suggestions.clear();
+                self.active_at = js_sys::Date::now();
+            }
+            Msg::ResetHints => self.seen_hints.clear(),
+            Msg::SetBackupReminderDays(days) => {
+                self.settings.backup_reminder_days = days;
+                self.save_settings();
+            }
+            Msg::PostponeBackup => self.practice_page.backup_postponed = true,
+            Msg::SetCompactStorage(compact) => {
+                self.settings.compact_storage = compact;
+                self.save_settings();
+                self.save_stats();
+            }
+            Msg::SetStorageBackend(backend) => {
+                ctx.link()
+                    .send_future(async move { Msg::StoreOpened(store::open(backend).await) });
+                return false;
+            }
+            Msg::StoreOpened(store) => {
+                store.backend().save();
+                self.store = store;
+                if let Some(book) = &self.book {
+                    if let Err(e) = book.save_text(&*self.store, self.settings.compact_storage) {
+                        tracing::warn!("Saving the book failed: {e}");
+                    }
+                }
+                // The new store starts with everything the old one had
+                self.events.store_changed();
+                if let Err(e) = self.settings.save(&*self.store) {
+                    tracing::warn!("Saving settings failed: {e}");
+                }
+                self.save_stats();
+            }
+            Msg::DownloadStore => {
+                // The file holds the whole log, however the store keeps its events
+                let snapshot = MemoryStore::default();
+                let copied = store::copy(&*self.store, &snapshot, &STATS_KEYS)
+                    .and(self.events.save(&snapshot, self.settings.compact_storage));
+                if let Err(e) = copied {
+                    tracing::error!("Could not export the storage: {e}");
+                    return false;
+                }
+                let contents = store::export(&snapshot, &STATS_KEYS);
+                self.download_backup(ctx, contents, store::DOWNLOAD_FILE_NAME);
+                return false;
+            }
+            Msg::ExportReport => {
+                let report = report::Report {
+                    language: self.settings.language().tag(),
+                    results: &self.history.results,
+                    unit: self.settings.speed_unit,
+                    personal_best: self
+                        .history
+                        .personal_best(&self.history_query(), self.settings.speed_unit),
+                    weekly_speed: self.speed_chart_series(self.settings.speed_unit),
+                    daily_accuracy: analytics::daily_accuracy(&self.history),
+                    error_scores: &self.error_stats.error_score,
+                };
+                store::download(
+                    &report.render(js_sys::Date::now()),
+                    report::REPORT_FILE_NAME,
+                    "text/html",
+                );
+                return false;
+            }
+            Msg::ExportStats => {
+                let file = StatsFile {
+                    errors: self.error_stats.clone(),
+                    settings: Some(self.settings.clone()),
+                    history: self.history.clone(),
+                    events: Some(self.events.clone()),
+                };
+                match serde_json::to_string(&file) {
+                    Ok(contents) => self.download_backup(ctx, contents, backup::STATS_FILE_NAME),
+                    Err(e) => tracing::error!("Could not export the stats: {e}"),
+                }
+                return false;
+            }
+            Msg::EncryptedDownload(name, Ok(contents)) => {
+                store::download(&contents, name, "application/json");
+                return false;
+            }
+            Msg::EncryptedDownload(_, Err(e)) => {
+                self.settings_page.import_error =
+                    Some(t_args("encryption-failed", &[("error", &e)]));
+            }
+            Msg::SetFilePassword(password) => {
+                self.settings_page.file_password = password;
+                return false;
+            }
+            Msg::StoreFileRead(Ok(text)) if encryption::is_encrypted(&text) => {
+                self.decrypt_file(ctx, text, Msg::StoreFileRead);
+            }
+            Msg::StatsFileRead(Ok(text)) if encryption::is_encrypted(&text) => {
+                self.decrypt_file(ctx, text, Msg::StatsFileRead);
+            }
+            Msg::StoreFileChosen(None) => return false,
+            Msg::StoreFileChosen(Some(file)) => {
+                let link = ctx.link().clone();
+                self.file_reader = Some(gloo_file::callbacks::read_as_text(
+                    &file.into(),
+                    move |res| {
+                        link.send_message(Msg::StoreFileRead(res.map_err(|e| e.to_string())))
+                    },
+                ));
+            }
+            Msg::StoreFileRead(res) => {
+                self.file_reader = None;
+                match res.and_then(|text| store::open_file(&*self.store, &text)) {
+                    Ok(()) => {
+                        self.events = EventLog::load(&*self.store)
+                            .unwrap_or_else(|| EventLog::new(TypingErrors::load(&*self.store)));
+                        self.error_stats = self.events.derive_errors(self.settings.penalties);
+                        self.history = History::load(&*self.store);
+                        self.history_changed();
+                        self.exam_results = ExamResults::load(&*self.store);
+                        self.discovery_stats = DiscoveryStats::load(&*self.store);
+                        self.numpad_stats =
+                            DigitStats::load(&*self.store, numpad::NUMPAD_STORAGE_KEY);
+                        self.number_row_stats =
+                            DigitStats::load(&*self.store, numbers::NUMBER_ROW_STORAGE_KEY);
+                        self.settings_page.import_error = None;
+                    }
+                    Err(e) => {
+                        self.settings_page.import_error =
+                            Some(t_args("import-failed", &[("error", &e)]))
+                    }
+                }
+            }
+            Msg::StatsFileChosen(None) => return false,
+            Msg::StatsFileChosen(Some(file)) => {
+                let link = ctx.link().clone();
+                self.file_reader = Some(gloo_file::callbacks::read_as_text(
+                    &file.into(),
+                    move |res| {
+                        link.send_message(Msg::StatsFileRead(res.map_err(|e| e.to_string())))
+                    },
+                ));
+            }
+            Msg::StatsFileRead(res) => {
+                self.file_reader = None;
+                match res.and_then(|text| {
+                    serde_json::from_str::<StatsFile>(&text).map_err(|e| e.to_string())
+                }) {
+                    Ok(file) => {
+                        let preview = backup::preview(
+                            &self.error_stats,
+                            &self.events,
+                            &self.settings,
+                            &self.history,
+                            &file,
+                        );
+                        self.settings_page.pending_import = Some((file, preview));
+                        self.settings_page.import_error = None;
+                    }
+                    Err(e) => {
+                        self.settings_page.import_error =
+                            Some(t_args("import-failed", &[("error", &e)]))
+                    }
+                }
+            }
+            Msg::ApplyImport => {
+                let Some((file, _preview)) = self.settings_page.pending_import.take() else {
+                    return false;
+                };
+                let (errors, events) = backup::merge_stats(&self.error_stats, &self.events, &file);
+                self.error_stats = errors;
+                if let Some(events) = events {
+                    self.events = events;
+                }
+                self.history = backup::merge_history(&self.history, &file.history);
+                self.history_changed();
+                self.mark_unsaved();
+                self.settings = backup::merge_settings(&self.settings, file.settings.as_ref());
+                self.save_settings();
+                self.expected_chars = self.settings.expected_chars();
+                self.language_loading = load_language(ctx, self.settings.language());
+            }
+            Msg::CancelImport => self.settings_page.pending_import = None,
+            Msg::EnterShareCode(code) => {
+                self.settings_page.shared_preset =
+                    (!code.trim().is_empty()).then(|| Preset::decode(&code));
+            }
+            Msg::ApplySharedPreset => {
+                let Some(Ok(preset)) = self.settings_page.shared_preset.take() else {
+                    return false;
+                };
+                preset.apply(&mut self.settings);
+                self.expected_chars = self.settings.expected_chars();
+                if self.discovery.is_some() {
+                    self.next_discovery(ctx, None);
+                }
+                // Saves the settings and loads a prompt from the source
+                return Component::update(
+                    self,
+                    ctx,
+                    Msg::SetPromptSource(self.settings.prompt_source.clone()),
+                );
+            }
+            Msg::SetComparisonPeriod(side, period) => self.stats_page.comparison[side] = period,
+            Msg::SetDeletionFrom(from) => self.settings_page.deletion_range.0 = from,
+            Msg::SetDeletionTo(to) => self.settings_page.deletion_range.1 = to,
+            Msg::PreviewDeletion(deletion) => {
+                self.settings_page.pending_deletion =
+                    Some((deletion, deletion.preview(&self.events, &self.history)));
+            }
+            Msg::ApplyDeletion => {
+                let Some((deletion, _preview)) = self.settings_page.pending_deletion.take() else {
+                    return false;
+                };
+                deletion.apply(&mut self.events, &mut self.history, self.settings.penalties);
+                self.history_changed();
+                self.error_stats = self.events.derive_errors(self.settings.penalties);
+                self.mark_unsaved();
+            }
+            Msg::CancelDeletion => self.settings_page.pending_deletion = None,
+            Msg::SetPromptLen(len) => {
+                self.settings.prompt_len = len;
+                self.save_settings();
+            }
+            Msg::SetCharSet(charset) => {
+                self.settings.charset = charset;
+                self.save_settings();
+                self.expected_chars = self.settings.expected_chars();
+            }
+            Msg::SetSymbolClass(class, enabled) => {
+                if enabled {
+                    self.settings.disabled_classes.remove(&class);
+                } else {
+                    self.settings.disabled_classes.insert(class);
+                }
+                self.save_settings();
+            }
+            Msg::SetMistakesShown(n) => {
+                self.settings.mistakes_shown = n;
+                self.save_settings();
+                let excess = self.mistyped.len().saturating_sub(n);
+                self.mistyped.drain(..excess);
+            }
+            Msg::SetCapsLockGuard(on) => {
+                self.settings.caps_lock_guard = on;
+                self.save_settings();
+            }
+            Msg::SetPersistStats(on) => {
+                self.settings.persist_stats = on;
+                self.save_settings();
+                if !matches!(self.save_state, SaveState::OtherTab) {
+                    self.save_state = if on {
+                        SaveState::Unsaved
+                    } else {
+                        SaveState::Disabled
+                    };
+                }
+            }
+            Msg::KeyPress(ev) if self.settings.read_aloud && ev.key() == speech::READ_ALOUD_KEY => {
+                ev.prevent_default();
+                let upcoming: String = self.prompt.chars().skip(self.correctness.len()).collect();
+                speech::speak(&speech::describe(&upcoming), self.settings.language().tag());
+                return false;
+            }
+            Msg::KeyPress(ev) if self.calibration.is_some() => {
+                ev.prevent_default();
+                if let Some(calibration) = &mut self.calibration {
+                    calibration.tap(startup::now());
+                }
+            }
+            Msg::KeyPress(_) if self.paused_at.is_some() => return false,
+            Msg::KeyPress(ev) if self.discovery.is_some() => {
+                let key = ev.key();
+                let mut chars = key.chars();
+                let (Some(c), None) = (chars.next(), chars.next()) else {
+                    return false;
+                };
+                let Some(discovery) = &mut self.discovery else {
+                    return false;
+                };
+                if !c.to_lowercase().eq([discovery.target]) {
+                    // Pressing a wrong key is hesitation too
+                    discovery.revealed = true;
+                    return true;
+                }
+                let (target, revealed) = (discovery.target, discovery.revealed);
+                self.discovery_stats.record(target, revealed);
+                self.mark_unsaved();
+                self.next_discovery(ctx, Some(target));
+            }
+            Msg::KeyPress(ev) if ev.key() == fullscreen::FULLSCREEN_KEY => {
+                ev.prevent_default();
+                return Component::update(self, ctx, Msg::RunAction(AuxAction::ToggleFullscreen));
+            }
+            Msg::KeyPress(ev) if ev.key() == "Backspace" => {
+                if self
+                    .exam
+                    .as_mut()
+                    .is_some_and(|exam| !exam.take_backspace())
+                {
+                    ev.prevent_default();
+                    return false;
+                }
+                self.correctness.pop();
+                self.events.push(EventKind::Backspace, js_sys::Date::now());
+                self.keystrokes += 1;
+                self.backspaces += 1;
+                self.finished_at = None;
+            }
+            Msg::KeyPress(ev)
+                if ev.key() == "Enter" && self.prompt.chars().count() == self.correctness.len() =>
+            {
+                ev.prevent_default();
+                return Component::update(self, ctx, Msg::CompletePrompt(ev.time_stamp()));
+            }
+            Msg::CompletePrompt(now) => {
+                self.events.push(
+                    EventKind::PromptEnd {
+                        flagged: self.flagged.is_some(),
+                    },
+                    js_sys::Date::now(),
+                );
+                self.mark_unsaved();
+                if let Some(test) = &mut self.data_entry {
+                    let has_error = self.correctness.contains(&false);
+                    let result =
+                        test.record_field(self.keystrokes, has_error, self.started_at, now);
+                    if result.is_some() {
+                        self.data_entry = None;
+                        self.data_entry_result = result;
+                    }
+                    reset(self);
+                    return true;
+                }
+                if let Some(exam) = &mut self.exam {
+                    let errors = self.correctness.iter().filter(|c| !**c).count();
+                    if exam.record_line(self.correctness.len(), errors) {
+                        self.correctness.clear();
+                        self.finish_exam(now);
+                    }
+                    reset(self);
+                    return true;
+                }
+                self.prompts_done += 1;
+                self.record_result();
+                self.record_lesson();
+                if let Some(goal) = &mut self.goal {
+                    goal.stage_done |= self.unlocked_key.is_some();
+                    if goal.reached(js_sys::Date::now(), self.prompts_done) {
+                        self.finish_goal(true);
+                    }
+                }
+                if self.typing_book() && self.flagged.is_none() {
+                    if let Some(book) = &mut self.book {
+                        book.advance(self.settings.passage_len, js_sys::Date::now());
+                        self.mark_unsaved();
+                    }
+                }
+                if self.flagged.is_none() {
+                    words::account(&mut self.word_stats, &self.prompt, &self.fumbled_at);
+                }
+                if let TipState::Pending = self.practice_page.tip {
+                    let analytics =
+                        SessionAnalytics::compute(&self.log, self.backspaces, &self.settings);
+                    if let Some(rule) = tips::pick(&analytics) {
+                        self.practice_page.tip = TipState::Showing(rule);
+                    }
+                }
+                self.practice_page.warm_up_offered = false;
+                if let Some(warm_up) = &mut self.warm_up {
+                    if !warm_up.advance() {
+                        self.warm_up = None;
+                    }
+                }
+                let clean = self.mistakes_on_this_line == 0 && self.flagged.is_none();
+                if let Some(drill) = &mut self.drill {
+                    drill.record_attempt(clean);
+                }
+                reset(self)
+            }
+            Msg::KeyPress(ev) => {
+                let key = ev.key();
+                // In code Enter types a line break and Tab the indentation
+                let multi_line = self.prompt.contains('\n');
+                let typed: Vec<char> = match key.as_str() {
+                    "Enter" if multi_line => vec!['\n'],
+                    "Tab" if multi_line => {
+                        vec![' '; snippets::indentation(&self.prompt, self.correctness.len())]
+                    }
+                    _ => {
+                        let mut chars = key.chars();
+                        let char = chars.next().unwrap();
+                        // Pressing CapsLock itself shows or hides the warning
+                        if chars.next().is_some() {
+                            return caps_lock_changed;
+                        }
+                        vec![char]
+                    }
+                };
+
+                ev.prevent_default();
+
+                // Keys pressed after the time is up, before the tick ends the test
+                let now = ev.time_stamp();
+                if self
+                    .timed
+                    .as_ref()
+                    .is_some_and(|t| t.started_at.is_some() && t.remaining(now) <= 0.0)
+                {
+                    self.finish_timed_test(now);
+                    reset(self);
+                    return true;
+                }
+
+                if !typed
+                    .iter()
+                    .all(|c| *c == '\n' || self.expected_chars.contains(c))
+                {
+                    return caps_lock_changed;
+                }
+
+                // Every letter would come out in the wrong case
+                if self.caps_lock && self.settings.caps_lock_guard {
+                    return true;
+                }
+
+                if let Some(reason) = self.input_guard.check_key(ev.is_trusted(), ev.time_stamp()) {
+                    self.flagged = Some(reason);
+                    return true;
+                }
+
+                for char in typed {
+                    match self.prompt.chars().nth(self.correctness.len()) {
+                        None => {}
+                        Some(expected_c) => {
+                            let correct = expected_c == char;
+                            let now = ev.time_stamp();
+                            if self.started_at.is_none() {
+                                self.events.push(
+                                    EventKind::PromptStart {
+                                        prompt: self.prompt.clone(),
+                                        retention_check: self.retention_check,
+                                        warm_up: self.is_warm_up(),
+                                        kept_apart: self.keeps_stats_apart(),
+                                    },
+                                    js_sys::Date::now(),
+                                );
+                            }
+                            if self.started_at.is_none() && self.settings.pace_caret {
+                                self.pacer = self
+                                    .history
+                                    .recent_pace(RECENT_RESULTS)
+                                    .map(|pace| Pacer::new(t("pace-caret-name"), pace, now));
+                                schedule_cursor_frame(ctx, now);
+                            }
+                            self.started_at.get_or_insert(now);
+                            if let Some(exam) =
+                                self.exam.as_mut().filter(|e| e.started_at.is_none())
+                            {
+                                exam.started_at = Some(now);
+                                schedule_exam_tick(ctx, now);
+                            }
+                            if let Some(test) =
+                                self.timed.as_mut().filter(|t| t.started_at.is_none())
+                            {
+                                test.started_at = Some(now);
+                                schedule_tick(ctx, now);
+                            }
+                            self.keystrokes += 1;
+                            let latency = self.previous_key.map(|(_c, at)| now - at);
+                            self.log.push(Keystroke {
+                                expected: expected_c,
+                                typed: char,
+                                latency,
+                                previous: self.previous_key.map(|(c, _at)| c),
+                            });
+                            self.previous_key = Some((expected_c, now));
+                            self.correctness.push(correct);
+                            // A timed test never runs out of text
+                            if self.timed.is_some()
+                                && self.prompt.chars().count() - self.correctness.len()
+                                    < timed::EXTEND_BELOW
+                            {
+                                self.extend_prompt();
+                            }
+                            if self.correctness.len() == self.prompt.chars().count() {
+                                self.finished_at = Some(now);
+                                if let Some(secs) = self.settings.auto_advance {
+                                    let at = now + f64::from(secs) * 1000.0;
+                                    self.advance_at = Some(at);
+                                    schedule_advance_tick(ctx, at, now);
+                                }
+                            }
+                            let kept_apart = self.keeps_stats_apart();
+                            let numpad = ev.on_numpad();
+                            if self.is_warm_up() {
+                                // Warm-up keystrokes only count in the session view
+                            } else if numpad {
+                                self.numpad_stats.record(expected_c, correct, latency);
+                            } else if kept_apart {
+                                self.number_row_stats.record(expected_c, correct, latency);
+                            } else {
+                                self.error_stats
+                                    .account(expected_c, char, js_sys::Date::now());
+                                if let (true, Some(ms)) = (correct, latency) {
+                                    self.error_stats.account_latency(expected_c, ms);
+                                }
+                                let before: Vec<char> = self
+                                    .prompt
+                                    .chars()
+                                    .take(self.correctness.len() - 1)
+                                    .collect();
+                                self.error_stats
+                                    .account_ngrams(&before, expected_c, correct, latency);
+                                if self.retention_check {
+                                    review::account(
+                                        &mut self.error_stats.retention,
+                                        expected_c,
+                                        correct,
+                                    );
+                                }
+                            }
+                            self.events.push(
+                                EventKind::Keystroke {
+                                    expected: expected_c,
+                                    typed: char,
+                                    numpad,
+                                },
+                                js_sys::Date::now(),
+                            );
+                            self.mark_unsaved();
+                            if !correct {
+                                match self.mistyped.back_mut() {
+                                    Some((ex, ty, n)) if (*ex, *ty) == (expected_c, char) => {
+                                        *n += 1
+                                    }
+                                    _ => self.mistyped.push_back((expected_c, char, 1)),
+                                }
+                                if self.mistyped.len() > self.settings.mistakes_shown {
+                                    self.mistyped.pop_front();
+                                }
+                                self.mistakes_on_this_line += 1;
+                                self.fumbled_at.insert(self.correctness.len() - 1);
+                                if let (true, Some(sound), None) = (
+                                    self.settings.error_sounds,
+                                    &self.key_sound,
+                                    &self.calibration,
+                                ) {
+                                    sound.play(Sound::Error, 0.0);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Opens the stats store and renders the app into the page body
+pub fn start() {
+    startup::mark("startup-wasm-started");
+    tracing_wasm::set_as_global_default();
+    wasm_bindgen_futures::spawn_local(async {
+        let store = store::open(Backend::load()).await;
+        yew::Renderer::<App>::with_props(PracticeProps { store }).render();
+    });
+}
+
+/// The app within the router, which keeps the page in the URL
+#[function_component]
+fn App(props: &PracticeProps) -> Html {
+    html!(<HashRouter><Practice store={props.store.clone()} /></HashRouter>)
+}
+
+/// Checks again in a while whether the app is idle
+fn schedule_idle_check(ctx: &Context<Practice>) {
+    ctx.link().send_future(async {
+        TimeoutFuture::new(suggestions::ROTATE_MS).await;
+        Msg::IdleCheck
+    });
+}
+
+/// Saves the statistics after [`SAVE_DELAY_MS`] unless typing goes on
+fn schedule_save(ctx: &Context<Practice>) {
+    ctx.link().send_future(async {
+        TimeoutFuture::new(SAVE_DELAY_MS).await;
+        Msg::SaveDue
+    });
+}
+
+/// Replays the next slice of the event log after yielding to the browser
+fn schedule_replay_step(ctx: &Context<Practice>) {
+    ctx.link().send_future(async {
+        TimeoutFuture::new(0).await;
+        Msg::RecomputeStep
+    });
+}
+
+/// Re-renders the countdown of the exam started at `started_at` and ends it on time
+fn schedule_exam_tick(ctx: &Context<Practice>, started_at: f64) {
+    ctx.link().send_future(async move {
+        TimeoutFuture::new(exam::TICK_MS).await;
+        Msg::ExamTick(started_at)
+    });
+}
+
+/// Updates the progress of the session goal and ends the session once it is reached
+fn schedule_goal_tick(ctx: &Context<Practice>) {
+    ctx.link().send_future(async {
+        TimeoutFuture::new(goal::TICK_MS).await;
+        Msg::GoalTick
+    });
+}
+
+/// Re-renders the countdown of the timed test started at `started_at` and ends it on time
+fn schedule_tick(ctx: &Context<Practice>, started_at: f64) {
+    ctx.link().send_future(async move {
+        TimeoutFuture::new(timed::TICK_MS).await;
+        Msg::Tick(started_at)
+    });
+}
+
+/// Plays the soundscape picked in `settings`, if any
+fn start_ambient(settings: &Settings) -> Option<Ambient> {
+    let soundscape = settings.ambient?;
+    Ambient::start(soundscape, settings.ambient_volume)
+        .map_err(|e| tracing::warn!("Ambient sound is unavailable: {e}"))
+        .ok()
+}
+
+/// Loads the sounds of the theme if `settings` turn them on
+fn start_key_sound(settings: &Settings) -> Option<KeySound> {
+    if !settings.key_sounds {
+        return None;
+    }
+    KeySound::new(settings.sound_theme)
+        .map_err(|e| tracing::warn!("Clicks are unavailable: {e}"))
+        .ok()
+}
+
+/// Counts down to `at` a second at a time, then starts the next prompt
+fn schedule_advance_tick(ctx: &Context<Practice>, at: f64, now: f64) {
+    let wait = (at - now).clamp(0.0, 1000.0) as u32;
+    ctx.link().send_future(async move {
+        TimeoutFuture::new(wait).await;
+        Msg::AdvanceTick(at)
+    });
+}
+
+/// Redraws the remote carets of the prompt started at `id`
+fn schedule_cursor_frame(ctx: &Context<Practice>, id: f64) {
+    ctx.link().send_future(async move {
+        TimeoutFuture::new(cursors::FRAME_MS).await;
+        Msg::CursorFrame(id)
+    });
+}
+
+/// Switches the UI language, fetching its pack if it is not built in
+fn load_language(ctx: &Context<Practice>, language: Language) -> Loading {
+    let Some(asset) = language.asset() else {
+        i18n::set_english();
+        return Loading::Idle;
+    };
+    ctx.link()
+        .send_future(async move { Msg::LanguageLoaded(language, assets::fetch_text(asset).await) });
+    Loading::InProgress
+}
+
+fn render_loading(loading: &Loading) -> Html {
+    match loading {
+        Loading::Idle => html!(),
+        Loading::InProgress => html!(<span>{" "}{t("loading")}</span>),
+        Loading::Failed(e) => {
+            html!(<span class="flagged">{" "}{t_args("loading-failed", &[("error", e)])}</span>)
+        }
+    }
+}
+
+/// Characters prompts are made of: the expected ones limited to the keys
+/// introduced by a layout switch and by the course
+fn practiced_chars(
+    settings: &Settings,
+    curriculum: Option<&Curriculum>,
+    expected: &HashSet<char>,
+) -> HashSet<char> {
+    curriculum::practiced_chars(
+        curriculum,
+        settings.typed_layout(),
+        layout_switch::practiced_chars(settings.migration.as_ref(), expected),
+    )
+}
+
+/// Keys and pastes in text fields are not practice input
+fn targets_text_field(e: &Event) -> bool {
+    e.target()
+        .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+        .is_some_and(|el| matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA"))
+}
+
+/// Fetches a prompt from `source` to replace the current one
+fn request_prompt(ctx: &Context<Practice>, source: Rc<dyn PromptSource>, weights: CharWeights) {
+    let next = source.next_prompt(weights);
+    ctx.link()
+        .send_future(async { Msg::SourcePrompt(next.await) });
+}
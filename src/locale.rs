@@ -0,0 +1,63 @@
+//! Locale-aware formatting of numbers, dates and durations
+//! through the browser `Intl` API, using the browser's default locale.
+
+use js_sys::{Array, Date, Intl, Object, Reflect};
+use wasm_bindgen::JsValue;
+
+fn options(pairs: &[(&str, JsValue)]) -> Object {
+    let options = Object::new();
+    for (key, value) in pairs {
+        Reflect::set(&options, &(*key).into(), value).unwrap();
+    }
+    options
+}
+
+fn format_with(options: &Object, value: f64) -> String {
+    Intl::NumberFormat::new(&Array::new(), options)
+        .format()
+        .call1(&JsValue::UNDEFINED, &value.into())
+        .ok()
+        .and_then(|s| s.as_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Formats a number with exactly `fraction_digits` digits after the decimal separator
+pub fn format_number(value: f64, fraction_digits: u8) -> String {
+    let digits = JsValue::from(fraction_digits);
+    format_with(
+        &options(&[
+            ("minimumFractionDigits", digits.clone()),
+            ("maximumFractionDigits", digits),
+        ]),
+        value,
+    )
+}
+
+/// Formats a duration as seconds, e.g. "12.3 sec" or "12,3 с"
+pub fn format_duration(millis: f64) -> String {
+    format_with(
+        &options(&[
+            ("style", "unit".into()),
+            ("unit", "second".into()),
+            ("maximumFractionDigits", 1.into()),
+        ]),
+        millis / 1000.0,
+    )
+}
+
+/// Formats a Unix time in ms as a short date and time
+pub fn format_date(millis: f64) -> String {
+    let options = options(&[("dateStyle", "short".into()), ("timeStyle", "short".into())]);
+    Intl::DateTimeFormat::new(&Array::new(), &options)
+        .format()
+        .call1(&JsValue::UNDEFINED, &Date::new(&millis.into()))
+        .ok()
+        .and_then(|s| s.as_string())
+        .unwrap_or_default()
+}
+
+/// Local midnight of a `YYYY-MM-DD` date as a Unix time in ms
+pub fn parse_date(value: &str) -> Option<f64> {
+    let time = Date::new(&format!("{value}T00:00").into()).get_time();
+    (!time.is_nan()).then_some(time)
+}
@@ -0,0 +1,245 @@
+//! Metrics over the keystroke log. To add one, implement [`Metric`],
+//! list it in [`METRICS`] and add its name to the language packs:
+//! the statistics and exports go through the list and never name a metric.
+
+use crate::{
+    analytics::{Keystroke, SessionAnalytics},
+    i18n::t,
+    locale,
+    settings::Settings,
+    units::{SpeedSample, SpeedUnit},
+};
+
+/// What a metric is computed over
+pub struct MetricInput<'a> {
+    pub log: &'a [Keystroke],
+    pub backspaces: usize,
+    pub settings: &'a Settings,
+}
+
+#[derive(Clone, Copy)]
+pub enum MetricUnit {
+    Speed(SpeedUnit),
+    Percent,
+    Millis,
+}
+
+impl MetricUnit {
+    pub fn format(self, value: f64) -> String {
+        match self {
+            MetricUnit::Speed(unit) => format!(
+                "{} {}",
+                locale::format_number(value, 1),
+                t(unit.message_key())
+            ),
+            MetricUnit::Percent => format!("{}%", locale::format_number(value, 1)),
+            MetricUnit::Millis => locale::format_number(value, 0),
+        }
+    }
+}
+
+pub trait Metric {
+    /// Stable name for exports
+    fn id(&self) -> &'static str;
+    fn name_key(&self) -> &'static str;
+    /// The unit of the values computed while speed was shown in `speed`
+    fn unit(&self, speed: SpeedUnit) -> MetricUnit;
+    /// `None` when there is not enough data
+    fn compute(&self, input: &MetricInput) -> Option<f64>;
+}
+
+fn latencies(log: &[Keystroke]) -> Vec<f64> {
+    log.iter().filter_map(|k| k.latency).collect()
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Speed in the unit of the settings over the time between keystrokes,
+/// pauses between prompts are not counted
+struct Speed;
+
+impl Metric for Speed {
+    fn id(&self) -> &'static str {
+        "speed"
+    }
+    fn name_key(&self) -> &'static str {
+        "metric-speed"
+    }
+    fn unit(&self, speed: SpeedUnit) -> MetricUnit {
+        MetricUnit::Speed(speed)
+    }
+    fn compute(&self, input: &MetricInput) -> Option<f64> {
+        let timed = input.log.iter().filter(|k| k.latency.is_some());
+        let sample = SpeedSample {
+            chars: timed.clone().count(),
+            words: timed.filter(|k| k.expected == ' ').count(),
+            keystrokes: input.log.len() + input.backspaces,
+            millis: latencies(input.log).iter().sum(),
+        };
+        (sample.millis > 0.0).then(|| input.settings.speed_unit.rate(&sample))
+    }
+}
+
+struct Accuracy;
+
+impl Metric for Accuracy {
+    fn id(&self) -> &'static str {
+        "accuracy"
+    }
+    fn name_key(&self) -> &'static str {
+        "metric-accuracy"
+    }
+    fn unit(&self, _speed: SpeedUnit) -> MetricUnit {
+        MetricUnit::Percent
+    }
+    fn compute(&self, input: &MetricInput) -> Option<f64> {
+        let correct = input.log.iter().filter(|k| k.expected == k.typed).count();
+        (!input.log.is_empty()).then(|| 100.0 * correct as f64 / input.log.len() as f64)
+    }
+}
+
+/// How even the rhythm is: 100% less the coefficient of variation of latencies
+struct Consistency;
+
+impl Metric for Consistency {
+    fn id(&self) -> &'static str {
+        "consistency"
+    }
+    fn name_key(&self) -> &'static str {
+        "metric-consistency"
+    }
+    fn unit(&self, _speed: SpeedUnit) -> MetricUnit {
+        MetricUnit::Percent
+    }
+    fn compute(&self, input: &MetricInput) -> Option<f64> {
+        let latencies = latencies(input.log);
+        let mean = mean(&latencies).filter(|m| *m > 0.0)?;
+        let variance =
+            latencies.iter().map(|l| (l - mean).powi(2)).sum::<f64>() / latencies.len() as f64;
+        Some((100.0 * (1.0 - variance.sqrt() / mean)).max(0.0))
+    }
+}
+
+struct SameFingerLatency;
+
+impl Metric for SameFingerLatency {
+    fn id(&self) -> &'static str {
+        "same_finger_latency_ms"
+    }
+    fn name_key(&self) -> &'static str {
+        "metric-same-finger-latency"
+    }
+    fn unit(&self, _speed: SpeedUnit) -> MetricUnit {
+        MetricUnit::Millis
+    }
+    fn compute(&self, input: &MetricInput) -> Option<f64> {
+        SessionAnalytics::compute(input.log, input.backspaces, input.settings).same_finger_latency
+    }
+}
+
+struct BackspaceRate;
+
+impl Metric for BackspaceRate {
+    fn id(&self) -> &'static str {
+        "backspace_rate"
+    }
+    fn name_key(&self) -> &'static str {
+        "metric-backspace-rate"
+    }
+    fn unit(&self, _speed: SpeedUnit) -> MetricUnit {
+        MetricUnit::Percent
+    }
+    fn compute(&self, input: &MetricInput) -> Option<f64> {
+        let keystrokes = input.log.len() + input.backspaces;
+        (keystrokes > 0).then(|| 100.0 * input.backspaces as f64 / keystrokes as f64)
+    }
+}
+
+/// All metrics, in display order
+pub const METRICS: &[&dyn Metric] = &[
+    &Speed,
+    &Accuracy,
+    &Consistency,
+    &SameFingerLatency,
+    &BackspaceRate,
+];
+
+/// The metric with `id`, if it still exists
+pub fn find(id: &str) -> Option<&'static dyn Metric> {
+    METRICS.iter().copied().find(|m| m.id() == id)
+}
+
+/// Every metric with its value
+pub fn compute_all(input: &MetricInput) -> Vec<(&'static dyn Metric, Option<f64>)> {
+    METRICS.iter().map(|m| (*m, m.compute(input))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keystroke(expected: char, typed: char, latency: Option<f64>) -> Keystroke {
+        Keystroke {
+            expected,
+            typed,
+            latency,
+            previous: None,
+        }
+    }
+
+    fn compute(
+        metric: &dyn Metric,
+        log: &[Keystroke],
+        backspaces: usize,
+        unit: SpeedUnit,
+    ) -> Option<f64> {
+        let settings = Settings {
+            speed_unit: unit,
+            ..Default::default()
+        };
+        metric.compute(&MetricInput {
+            log,
+            backspaces,
+            settings: &settings,
+        })
+    }
+
+    #[test]
+    fn speed_follows_the_unit_of_the_settings() {
+        // Ten timed characters, two of them spaces, in 6 seconds
+        let mut log = vec![keystroke('a', 'a', None)];
+        log.extend("abcd efgh ".chars().map(|c| keystroke(c, c, Some(600.0))));
+        let speed = |unit| compute(&Speed, &log, 1, unit).unwrap();
+        assert!((speed(SpeedUnit::Cpm) - 100.0).abs() < 1e-9);
+        assert!((speed(SpeedUnit::Wpm) - 20.0).abs() < 1e-9);
+        assert!((speed(SpeedUnit::ActualWpm) - 20.0).abs() < 1e-9);
+        assert!((speed(SpeedUnit::Kpm) - 120.0).abs() < 1e-9);
+        assert_eq!(compute(&Speed, &log[..1], 0, SpeedUnit::Wpm), None);
+    }
+
+    #[test]
+    fn even_rhythm_is_fully_consistent() {
+        let even: Vec<_> = (0..4).map(|_| keystroke('a', 'a', Some(200.0))).collect();
+        assert_eq!(compute(&Consistency, &even, 0, SpeedUnit::Wpm), Some(100.0));
+        let uneven = [
+            keystroke('a', 'a', Some(100.0)),
+            keystroke('a', 'a', Some(300.0)),
+        ];
+        // Deviation of 100 around a mean of 200
+        assert_eq!(
+            compute(&Consistency, &uneven, 0, SpeedUnit::Wpm),
+            Some(50.0)
+        );
+        assert_eq!(compute(&Consistency, &[], 0, SpeedUnit::Wpm), None);
+    }
+
+    #[test]
+    fn backspaces_count_among_keystrokes() {
+        let log: Vec<_> = (0..3).map(|_| keystroke('a', 'a', None)).collect();
+        assert_eq!(compute(&BackspaceRate, &log, 1, SpeedUnit::Wpm), Some(25.0));
+        assert_eq!(compute(&BackspaceRate, &log, 0, SpeedUnit::Wpm), Some(0.0));
+        assert_eq!(compute(&BackspaceRate, &[], 0, SpeedUnit::Wpm), None);
+    }
+}
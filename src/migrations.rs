@@ -0,0 +1,51 @@
+//! Upgrades of data persisted by older versions of the app.
+//! Each migration runs once, the last applied one is stored in LocalStorage.
+
+use gloo_storage::{LocalStorage, Storage};
+
+use typing_core::{errors::TypingErrors, ids::RecordMeta};
+
+use crate::{codec, settings::Settings, store::LocalStore, StoredErrors, ERROR_STORAGE_KEY};
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Upgrades the storage as of the Unix time in ms it is given
+type Migration = fn(f64) -> Result<(), String>;
+
+/// Migration `i` upgrades storage from version `i` to `i + 1`
+const MIGRATIONS: &[Migration] = &[add_record_ids];
+
+/// Applies the migrations not applied yet. The version is only stored
+/// once all of them succeed, so a failed one is retried on the next launch.
+pub fn run() -> Result<(), String> {
+    let version: usize = LocalStorage::get(SCHEMA_VERSION_KEY).unwrap_or(0);
+    if version >= MIGRATIONS.len() {
+        return Ok(());
+    }
+    let now = js_sys::Date::now();
+    for migration in &MIGRATIONS[version..] {
+        migration(now)?;
+    }
+    LocalStorage::set(SCHEMA_VERSION_KEY, MIGRATIONS.len()).map_err(|e| e.to_string())
+}
+
+/// Gives ids to the records stored before they had them.
+/// Their real creation time is unknown, "now" is the best guess.
+/// Only LocalStorage existed back then.
+fn add_record_ids(now: f64) -> Result<(), String> {
+    if let Ok(mut errors) = codec::load::<TypingErrors>(&LocalStore, ERROR_STORAGE_KEY) {
+        if errors.meta.is_missing() {
+            errors.meta = RecordMeta::new(now);
+            // Compact storage did not exist before this migration
+            errors.save(&LocalStore, false)?;
+        }
+    }
+    if let Ok(mut settings) = LocalStorage::get::<Settings>(crate::settings::SETTINGS_STORAGE_KEY) {
+        if settings.meta.is_missing() {
+            settings.meta = RecordMeta::new(now);
+            LocalStorage::set(crate::settings::SETTINGS_STORAGE_KEY, settings)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
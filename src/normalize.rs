@@ -0,0 +1,363 @@
+//! Typography of imported texts made typeable: curly quotes straightened,
+//! dashes replaced, and characters the keyboard lacks transliterated or
+//! dropped. Every substitution is counted so it can be previewed.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashSet},
+};
+
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::i18n::t;
+
+const QUOTES: [(char, &str); 10] = [
+    ('\u{2018}', "'"),
+    ('\u{2019}', "'"),
+    ('\u{201a}', "'"),
+    ('\u{2032}', "'"),
+    ('\u{201c}', "\""),
+    ('\u{201d}', "\""),
+    ('\u{201e}', "\""),
+    ('\u{2033}', "\""),
+    ('«', "\""),
+    ('»', "\""),
+];
+const DASHES: [(char, &str); 6] = [
+    ('\u{2010}', "-"),
+    ('\u{2012}', "-"),
+    ('\u{2013}', "-"),
+    ('\u{2014}', "-"),
+    ('\u{2212}', "-"),
+    ('…', "..."),
+];
+/// Characters nobody means to type, always replaced
+const INVISIBLE: [(char, &str); 5] = [
+    ('\u{a0}', " "),
+    ('\u{202f}', " "),
+    ('\u{ad}', ""),
+    ('\u{200b}', ""),
+    ('\u{feff}', ""),
+];
+/// Latin letters with diacritics and ligatures, lower case; upper case follows
+const LATIN: [(char, &str); 40] = [
+    ('à', "a"),
+    ('á', "a"),
+    ('â', "a"),
+    ('ã', "a"),
+    ('ä', "a"),
+    ('å', "a"),
+    ('æ', "ae"),
+    ('ç', "c"),
+    ('č', "c"),
+    ('ď', "d"),
+    ('è', "e"),
+    ('é', "e"),
+    ('ê', "e"),
+    ('ë', "e"),
+    ('ě', "e"),
+    ('ì', "i"),
+    ('í', "i"),
+    ('î', "i"),
+    ('ï', "i"),
+    ('ł', "l"),
+    ('ñ', "n"),
+    ('ň', "n"),
+    ('ò', "o"),
+    ('ó', "o"),
+    ('ô', "o"),
+    ('õ', "o"),
+    ('ö', "o"),
+    ('ø', "o"),
+    ('œ', "oe"),
+    ('ř', "r"),
+    ('š', "s"),
+    ('ß', "ss"),
+    ('ť', "t"),
+    ('ù', "u"),
+    ('ú', "u"),
+    ('û', "u"),
+    ('ü', "u"),
+    ('ů', "u"),
+    ('ý', "y"),
+    ('ž', "z"),
+];
+/// Russian letters, lower case; upper case follows
+const CYRILLIC: [(char, &str); 33] = [
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('д', "d"),
+    ('е', "e"),
+    ('ё', "yo"),
+    ('ж', "zh"),
+    ('з', "z"),
+    ('и', "i"),
+    ('й', "y"),
+    ('к', "k"),
+    ('л', "l"),
+    ('м', "m"),
+    ('н', "n"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('у', "u"),
+    ('ф', "f"),
+    ('х', "kh"),
+    ('ц', "ts"),
+    ('ч', "ch"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', ""),
+    ('ы', "y"),
+    ('ь', ""),
+    ('э', "e"),
+    ('ю', "yu"),
+    ('я', "ya"),
+];
+
+/// What happens to characters that cannot be typed after the replacements
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Untypeable {
+    /// Left for the practiced set to be extended with
+    #[default]
+    Keep,
+    Strip,
+    /// Spelled in plain Latin letters where possible, dropped otherwise
+    Transliterate,
+}
+
+impl Untypeable {
+    pub const ALL: [Untypeable; 3] = [
+        Untypeable::Keep,
+        Untypeable::Strip,
+        Untypeable::Transliterate,
+    ];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Untypeable::Keep => "untypeable-keep",
+            Untypeable::Strip => "untypeable-strip",
+            Untypeable::Transliterate => "untypeable-transliterate",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct Normalization {
+    /// Curly quotes and guillemets become straight ones
+    pub straight_quotes: bool,
+    /// Dashes and minus signs become hyphens, ellipses three dots
+    pub plain_dashes: bool,
+    pub untypeable: Untypeable,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization {
+            straight_quotes: true,
+            plain_dashes: true,
+            untypeable: Untypeable::Keep,
+        }
+    }
+}
+
+/// A character replaced throughout a text and how often
+#[derive(Clone, PartialEq, Debug)]
+pub struct Substitution {
+    pub from: char,
+    /// Empty if the character was dropped
+    pub to: String,
+    pub count: usize,
+}
+
+fn lookup(table: &[(char, &'static str)], c: char) -> Option<String> {
+    let lower = c.to_lowercase().next()?;
+    let (_, plain) = table.iter().find(|(from, _)| *from == lower)?;
+    if lower == c {
+        return Some(plain.to_string());
+    }
+    // Capitalized, not shouted: "Ж" is "Zh"
+    let mut chars = plain.chars();
+    Some(
+        chars
+            .next()
+            .into_iter()
+            .flat_map(char::to_uppercase)
+            .chain(chars)
+            .collect(),
+    )
+}
+
+impl Normalization {
+    fn replacement(&self, c: char, expected: &HashSet<char>) -> Option<String> {
+        let typographic = [
+            (true, &INVISIBLE[..]),
+            (self.straight_quotes, &QUOTES[..]),
+            (self.plain_dashes, &DASHES[..]),
+        ];
+        let replaced = typographic
+            .iter()
+            .filter(|(on, _)| *on)
+            .find_map(|(_, table)| lookup(table, c));
+        if replaced.is_some() || expected.contains(&c) || c.is_whitespace() {
+            return replaced;
+        }
+        match self.untypeable {
+            Untypeable::Keep => None,
+            Untypeable::Strip => Some(String::new()),
+            Untypeable::Transliterate => Some(
+                lookup(&LATIN, c)
+                    .or_else(|| lookup(&CYRILLIC, c))
+                    .filter(|plain| plain.chars().all(|p| expected.contains(&p)))
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// `text` normalized for a keyboard typing `expected`, and what was
+    /// replaced, the most frequent first
+    pub fn apply(&self, text: &str, expected: &HashSet<char>) -> (String, Vec<Substitution>) {
+        let mut normalized = String::with_capacity(text.len());
+        let mut substitutions: BTreeMap<char, Substitution> = BTreeMap::new();
+        for c in text.chars() {
+            match self.replacement(c, expected) {
+                Some(to) => {
+                    normalized.push_str(&to);
+                    substitutions
+                        .entry(c)
+                        .or_insert(Substitution {
+                            from: c,
+                            to,
+                            count: 0,
+                        })
+                        .count += 1;
+                }
+                None => normalized.push(c),
+            }
+        }
+        let mut substitutions: Vec<Substitution> = substitutions.into_values().collect();
+        substitutions.sort_by_key(|s| Reverse(s.count));
+        (normalized, substitutions)
+    }
+}
+
+/// Substitutions as "“ → " ×3", with invisible characters by their code
+pub fn describe(substitutions: &[Substitution]) -> String {
+    let visible = |c: char| {
+        if c.is_whitespace() || INVISIBLE.iter().any(|(i, _)| *i == c) {
+            format!("U+{:04X}", u32::from(c))
+        } else {
+            c.to_string()
+        }
+    };
+    substitutions
+        .iter()
+        .map(|s| {
+            let to = if s.to.is_empty() {
+                "∅".to_string()
+            } else {
+                s.to.replace(' ', "␣")
+            };
+            format!("{} → {} ×{}", visible(s.from), to, s.count)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Properties, PartialEq)]
+pub struct OptionsProps {
+    pub value: Normalization,
+    pub on_change: Callback<Normalization>,
+}
+
+/// Controls of the normalization, shared by all ways of importing text
+#[function_component]
+pub fn NormalizationOptions(props: &OptionsProps) -> Html {
+    let value = props.value;
+    let on_quotes = props.on_change.reform(move |e: Event| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        Normalization {
+            straight_quotes: input.checked(),
+            ..value
+        }
+    });
+    let on_dashes = props.on_change.reform(move |e: Event| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        Normalization {
+            plain_dashes: input.checked(),
+            ..value
+        }
+    });
+    let on_untypeable = props.on_change.reform(move |e: Event| {
+        let select: HtmlSelectElement = e.target_unchecked_into();
+        Normalization {
+            untypeable: Untypeable::ALL[select.selected_index().max(0) as usize],
+            ..value
+        }
+    });
+    html!(<p>
+        <label>
+            <input type="checkbox" checked={value.straight_quotes} onchange={on_quotes} />
+            {t("normalize-quotes")}
+        </label>
+        {" "}
+        <label>
+            <input type="checkbox" checked={value.plain_dashes} onchange={on_dashes} />
+            {t("normalize-dashes")}
+        </label>
+        {" "}{t("normalize-untypeable")}{" "}
+        <select onchange={on_untypeable}>
+            {for Untypeable::ALL.iter().map(|u| html!(
+                <option selected={*u == value.untypeable}>{t(u.message_key())}</option>
+            ))}
+        </select>
+    </p>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ascii() -> HashSet<char> {
+        (' '..='~').collect()
+    }
+
+    #[test]
+    fn typography_made_plain() {
+        let (text, substitutions) = Normalization::default().apply(
+            "\u{201c}It\u{2019}s\u{a0}\u{2014} well\u{2026}\u{201d}",
+            &ascii(),
+        );
+        assert_eq!(text, "\"It's - well...\"");
+        assert_eq!(substitutions.len(), 6);
+    }
+
+    #[test]
+    fn untypeable_characters() {
+        let mut normalization = Normalization {
+            straight_quotes: false,
+            ..Default::default()
+        };
+        assert_eq!(normalization.apply("«Café»", &ascii()).0, "«Café»");
+        normalization.untypeable = Untypeable::Strip;
+        assert_eq!(normalization.apply("«Café»", &ascii()).0, "Caf");
+        normalization.untypeable = Untypeable::Transliterate;
+        let (text, substitutions) = normalization.apply("Café, Жук", &ascii());
+        assert_eq!(text, "Cafe, Zhuk");
+        assert_eq!(
+            substitutions[0],
+            Substitution {
+                from: 'é',
+                to: "e".to_string(),
+                count: 1
+            }
+        );
+    }
+}
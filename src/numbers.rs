@@ -0,0 +1,179 @@
+//! Number drills: dates, amounts and phone numbers typed on the number row
+//! or the keypad. Their keystrokes go to statistics of their own and not to
+//! the error scores, so number practice leaves the letter statistics alone.
+
+use std::collections::BTreeMap;
+
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{codec, store::StatsStore};
+
+pub const NUMBER_ROW_STORAGE_KEY: &str = "number_row";
+/// Sentences of a drill, each repeated until typed cleanly
+pub const DRILL_SENTENCES: usize = 10;
+/// Numbers in a sentence
+const SENTENCE_LEN: usize = 4;
+
+/// Where the numbers of a drill are typed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumberKeys {
+    Row,
+    /// Only what the keypad has: digits, `.`, `/`, `*`, `-` and `+`
+    Numpad,
+}
+
+impl NumberKeys {
+    pub const ALL: [NumberKeys; 2] = [NumberKeys::Row, NumberKeys::Numpad];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            NumberKeys::Row => "numbers-row",
+            NumberKeys::Numpad => "numbers-numpad",
+        }
+    }
+}
+
+/// `n` with commas between thousands
+fn thousands(n: u32) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn date(rng: &mut impl Rng, keys: NumberKeys) -> String {
+    let (year, month, day) = (
+        rng.gen_range(1990..=2035),
+        rng.gen_range(1..=12),
+        rng.gen_range(1..=28),
+    );
+    match (keys, rng.gen_bool(0.5)) {
+        (NumberKeys::Row, true) => format!("{year}-{month:02}-{day:02}"),
+        (NumberKeys::Numpad, true) => format!("{day:02}.{month:02}.{year}"),
+        (_, false) => format!("{month}/{day}/{year}"),
+    }
+}
+
+fn amount(rng: &mut impl Rng, keys: NumberKeys) -> String {
+    let (whole, cents) = (rng.gen_range(1..100_000), rng.gen_range(0..100));
+    match keys {
+        NumberKeys::Row => format!("${}.{cents:02}", thousands(whole)),
+        NumberKeys::Numpad => format!("{whole}.{cents:02}"),
+    }
+}
+
+fn phone(rng: &mut impl Rng, keys: NumberKeys) -> String {
+    let (area, exchange, line) = (
+        rng.gen_range(200..1000),
+        rng.gen_range(200..1000),
+        rng.gen_range(0..10_000),
+    );
+    match keys {
+        NumberKeys::Row => format!("+1 ({area}) {exchange}-{line:04}"),
+        NumberKeys::Numpad => format!("{area}-{exchange}-{line:04}"),
+    }
+}
+
+/// Ids, percentages and times on the number row, sums on the keypad
+fn other(rng: &mut impl Rng, keys: NumberKeys) -> String {
+    let (a, b) = (rng.gen_range(2..1000), rng.gen_range(2..100));
+    match (keys, rng.gen_range(0..3)) {
+        (NumberKeys::Row, 0) => format!("#{}", rng.gen_range(1000..100_000)),
+        (NumberKeys::Row, 1) => format!("{}.{}%", rng.gen_range(0..100), rng.gen_range(0..10)),
+        (NumberKeys::Row, _) => format!("{}:{:02}", rng.gen_range(0..24), rng.gen_range(0..60)),
+        (NumberKeys::Numpad, 0) => format!("{a}*{b}"),
+        (NumberKeys::Numpad, 1) => format!("{a}+{b}"),
+        (NumberKeys::Numpad, _) => format!("{a}/{b}"),
+    }
+}
+
+/// Sentences of a drill on `keys`, each of a few numbers of the kinds above
+pub fn sentences(keys: NumberKeys, rng: &mut impl Rng) -> Vec<String> {
+    type Pattern<R> = fn(&mut R, NumberKeys) -> String;
+    let patterns: [Pattern<_>; 4] = [date, amount, phone, other];
+    (0..DRILL_SENTENCES)
+        .map(|_| {
+            (0..SENTENCE_LEN)
+                .map(|_| patterns.choose(rng).expect("there are patterns")(rng, keys))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub struct KeyStats {
+    pub presses: usize,
+    pub mistakes: usize,
+    latency_sum: f64,
+    latency_count: usize,
+}
+
+impl KeyStats {
+    pub fn error_rate(self) -> f64 {
+        self.mistakes as f64 / self.presses.max(1) as f64
+    }
+
+    pub fn mean_latency(self) -> Option<f64> {
+        (self.latency_count > 0).then(|| self.latency_sum / self.latency_count as f64)
+    }
+}
+
+/// Keystrokes kept apart from the error scores, by the character expected
+#[derive(Serialize, Deserialize, Default)]
+pub struct DigitStats {
+    pub keys: BTreeMap<char, KeyStats>,
+}
+
+impl DigitStats {
+    pub fn load(store: &dyn StatsStore, key: &str) -> Self {
+        codec::load(store, key).unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &dyn StatsStore, key: &str, compact: bool) -> Result<(), String> {
+        codec::save(store, key, self, compact)
+    }
+
+    pub fn record(&mut self, expected: char, correct: bool, latency: Option<f64>) {
+        let stats = self.keys.entry(expected).or_default();
+        stats.presses += 1;
+        stats.mistakes += usize::from(!correct);
+        if let Some(latency) = latency {
+            stats.latency_sum += latency;
+            stats.latency_count += 1;
+        }
+    }
+
+    pub fn get(&self, c: char) -> KeyStats {
+        self.keys.get(&c).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numpad_drills_only_use_the_keypad() {
+        let mut rng = rand::thread_rng();
+        let numpad = sentences(NumberKeys::Numpad, &mut rng);
+        assert_eq!(numpad.len(), DRILL_SENTENCES);
+        assert!(numpad
+            .iter()
+            .flat_map(|s| s.chars())
+            .all(|c| c.is_ascii_digit() || " ./*-+".contains(c)));
+        assert_eq!(thousands(1_234_567), "1,234,567");
+        assert_eq!(thousands(999), "999");
+        let mut stats = DigitStats::default();
+        stats.record('5', true, None);
+        stats.record('5', false, Some(200.0));
+        assert_eq!(stats.get('5').error_rate(), 0.5);
+        assert_eq!(stats.get('5').mean_latency(), Some(200.0));
+    }
+}
@@ -0,0 +1,84 @@
+//! The numeric keypad, practiced in the data entry assessment and number
+//! drills. Keys pressed on it, told apart by `KeyboardEvent.location`, have
+//! their own statistics, see `numbers::DigitStats`.
+
+use crate::fingers::{Finger, Hand};
+
+pub const NUMPAD_STORAGE_KEY: &str = "numpad";
+/// `KeyboardEvent.location` of the keys of the numeric keypad
+pub const LOCATION_NUMPAD: u32 = 3;
+pub const COLUMNS: usize = 4;
+
+/// A key of the keypad, placed in reading order on a grid of [`COLUMNS`]
+pub struct NumpadKey {
+    pub label: &'static str,
+    /// `None` for NumLock and Enter, which type no character
+    pub char: Option<char>,
+    pub finger: Finger,
+    /// Spans two rows
+    pub tall: bool,
+    /// Spans two columns
+    pub wide: bool,
+}
+
+const fn key(label: &'static str, char: Option<char>, index: u8) -> NumpadKey {
+    NumpadKey {
+        label,
+        char,
+        finger: Finger {
+            hand: Hand::Right,
+            index,
+        },
+        tall: false,
+        wide: false,
+    }
+}
+
+/// The keys of a full-size board: the index finger rests on 4,
+/// the thumb takes 0 and the pinky the keys of the right column
+pub const KEYS: [NumpadKey; 17] = [
+    key("Num", None, 3),
+    key("/", Some('/'), 2),
+    key("*", Some('*'), 1),
+    key("-", Some('-'), 0),
+    key("7", Some('7'), 3),
+    key("8", Some('8'), 2),
+    key("9", Some('9'), 1),
+    NumpadKey {
+        tall: true,
+        ..key("+", Some('+'), 0)
+    },
+    key("4", Some('4'), 3),
+    key("5", Some('5'), 2),
+    key("6", Some('6'), 1),
+    key("1", Some('1'), 3),
+    key("2", Some('2'), 2),
+    key("3", Some('3'), 1),
+    NumpadKey {
+        tall: true,
+        ..key("Enter", None, 0)
+    },
+    NumpadKey {
+        wide: true,
+        ..key("0", Some('0'), 4)
+    },
+    key(".", Some('.'), 1),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_fill_the_grid() {
+        // Tall keys take a cell of the row below, wide ones the next cell
+        let cells: usize = KEYS
+            .iter()
+            .map(|k| 1 + usize::from(k.tall) + usize::from(k.wide))
+            .sum();
+        assert_eq!(cells, COLUMNS * 5);
+        assert!("0123456789."
+            .chars()
+            .all(|c| KEYS.iter().any(|k| k.char == Some(c))));
+    }
+}
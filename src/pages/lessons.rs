@@ -0,0 +1,537 @@
+//! The lessons page: ways to practice other than the prompts of the
+//! selected source, like drills, books, tests and the course.
+
+use itertools::Itertools;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::{
+    assets::Loading,
+    book, curriculum, data_entry,
+    fingers::Hand,
+    history::{self, PromptResult},
+    i18n::{t, t_args},
+    import::ImportText,
+    keycaps::Layout,
+    layout_switch, locale,
+    normalize::{self, NormalizationOptions, Substitution},
+    numbers::NumberKeys,
+    render_loading, startup, timed,
+    trend::{self, Trend},
+    units::{SpeedSample, SpeedUnit},
+    Msg, Practice,
+};
+
+/// Most recent days of a layout switch listed
+const MIGRATION_DAYS_LISTED: usize = 14;
+/// Keys listed as the ones revealed most often
+const DISCOVERY_HARDEST_KEYS: usize = 10;
+
+/// The book being typed: its bookmark note and what went wrong opening it
+pub(crate) struct LessonsPage {
+    pub(crate) bookmark_note: String,
+    pub(crate) book_error: Option<String>,
+    /// What was replaced in the book opened last
+    pub(crate) book_substitutions: Vec<Substitution>,
+}
+
+impl Practice {
+    pub(crate) fn render_book_progress(&self) -> Html {
+        let Some(book) = &self.book else {
+            return html!();
+        };
+        let chapter = book.chapter();
+        html!(<p>{t_args("book-progress", &[
+            ("title", &book.title),
+            ("chapter", &book.chapters[chapter].title),
+            ("chapter_percent", &locale::format_number(100.0 * book.chapter_progress(chapter), 1)),
+            ("percent", &locale::format_number(100.0 * book.progress(), 1)),
+        ])}</p>)
+    }
+
+    /// Opening a long text, its chapters with their results, and bookmarks
+    fn render_book(&self, ctx: &Context<Self>) -> Html {
+        let on_file = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::BookFileChosen(input.files().and_then(|files| files.get(0)))
+        });
+        let on_passage_len = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input
+                .value()
+                .parse()
+                .ok()
+                .filter(|n| *n > 0)
+                .map(Msg::SetPassageLen)
+        });
+        let open = html!(<>
+            {t("book-open")}{" "}
+            <input type="file" accept=".txt,.md,.epub,text/plain,application/epub+zip" onchange={on_file} />
+            <NormalizationOptions
+                value={self.settings.normalization}
+                on_change={ctx.link().callback(Msg::SetNormalization)}
+            />
+            {(!self.lessons_page.book_substitutions.is_empty()).then(|| html!(<p>{t_args("normalize-replaced", &[
+                ("substitutions", &normalize::describe(&self.lessons_page.book_substitutions)),
+            ])}</p>))}
+            {self.lessons_page.book_error.as_ref().map(|e| html!(<p class="flagged">{e}</p>))}
+            {t("book-passage-len")}{" "}
+            <input type="number" min="1" step="10" onchange={on_passage_len}
+                value={self.settings.passage_len.to_string()} />
+        </>);
+        let Some(book) = &self.book else {
+            return html!(<details><summary>{t("book")}</summary>{open}</details>);
+        };
+        let book_tag = format!("book:{}", book.title);
+        let current = book.chapter();
+        let chapters = book.chapters.iter().enumerate().map(|(i, chapter)| {
+            let tag = format!("{}{}", book::CHAPTER_TAG_PREFIX, i + 1);
+            let results: Vec<&PromptResult> = self
+                .history
+                .results
+                .iter()
+                .filter(|r| r.tags.contains(&book_tag) && r.tags.contains(&tag))
+                .collect();
+            let sample = SpeedSample {
+                chars: results.iter().map(|r| r.chars).sum(),
+                words: results.iter().map(|r| r.words).sum(),
+                keystrokes: results.iter().map(|r| r.keystrokes).sum(),
+                millis: results.iter().map(|r| r.millis).sum(),
+            };
+            let mistakes = results.iter().map(|r| r.mistakes).sum();
+            let start = chapter.start;
+            html!(<tr class={classes!((i == current).then_some("current"))}>
+                <td>{&chapter.title}</td>
+                <td>{locale::format_number(100.0 * book.chapter_progress(i), 0)}{"%"}</td>
+                <td>{results.len()}</td>
+                <td>{(!results.is_empty()).then(|| self.format_speed(&sample))}</td>
+                <td>{(!results.is_empty()).then(|| html!(<>
+                    {locale::format_number(history::accuracy(sample.keystrokes, mistakes), 1)}{"%"}
+                </>))}</td>
+                <td><button onclick={ctx.link().callback(move |_| Msg::JumpInBook(start))}>{t("book-go")}</button></td>
+            </tr>)
+        });
+        let bookmarks = book.bookmarks.iter().enumerate().map(|(i, bookmark)| {
+            let offset = bookmark.offset;
+            html!(<li>
+                {&bookmark.note}{" "}
+                <button onclick={ctx.link().callback(move |_| Msg::JumpInBook(offset))}>{t("book-go")}</button>
+                {" "}<button onclick={ctx.link().callback(move |_| Msg::RemoveBookmark(i))}>{t("book-bookmark-remove")}</button>
+            </li>)
+        });
+        let reading = book.reading;
+        let on_note = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetBookmarkNote(input.value())
+        });
+        html!(<details open={reading}>
+            <summary>{t("book")}</summary>
+            {self.render_book_progress()}
+            {book.is_finished().then(|| html!(<p>{t("book-finished")}</p>))}
+            <button onclick={ctx.link().callback(move |_| Msg::SetReadingBook(!reading))}>
+                {t(if reading { "book-stop" } else { "book-resume" })}
+            </button>
+            <table class="chapters">
+                <tr>
+                    <th>{t("book-chapter")}</th><th>{t("book-typed")}</th><th>{t("book-results")}</th>
+                    <th>{t("speed")}</th><th>{t("accuracy")}</th><th></th>
+                </tr>
+                {for chapters}
+            </table>
+            <ul>{for bookmarks}</ul>
+            <input type="text" placeholder={t("book-bookmark-note")} value={self.lessons_page.bookmark_note.clone()} onchange={on_note} />
+            {" "}<button onclick={ctx.link().callback(|_| Msg::AddBookmark)}>{t("book-bookmark-add")}</button>
+            <br />{open}
+        </details>)
+    }
+
+    pub(crate) fn render_drill(&self, ctx: &Context<Self>) -> Html {
+        let Some(drill) = &self.drill else {
+            let onchange = ctx.link().batch_callback(|e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                input.value().parse().ok().map(Msg::SetCleanRepetitions)
+            });
+            return html!(<p>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::StartBuiltinDrill)}
+                    disabled={self.drill_loading == Loading::InProgress}
+                >
+                    {t("drill-start")}
+                </button>
+                {render_loading(&self.drill_loading)}
+                {" "}{t("drill-clean-repetitions")}{" "}
+                <input type="number" min="1" max="10" {onchange}
+                    value={self.settings.clean_repetitions.to_string()} />
+                <br />
+                {t("numbers-drill")}
+                {for NumberKeys::ALL.into_iter().map(|keys| html!(<>
+                    {" "}<button onclick={ctx.link().callback(move |_| Msg::StartNumberDrill(keys))}>
+                        {t(keys.message_key())}
+                    </button>
+                </>))}
+            </p>);
+        };
+        let (sentence, sentences) = drill.position();
+        let (clean, required) = drill.clean_streak();
+        html!(<div>
+            {t_args("drill-progress", &[
+                ("sentence", &sentence),
+                ("sentences", &sentences),
+                ("attempt", &(drill.attempts() + 1)),
+                ("clean", &clean),
+                ("required", &required),
+            ])}
+            {" "}<button onclick={ctx.link().callback(|_| Msg::StopDrill)}>{t("drill-stop")}</button>
+            <pre>{
+                drill.results.iter().rev().map(|r| t_args("drill-result", &[
+                    ("attempts", &r.attempts),
+                    ("sentence", &r.sentence),
+                ]) + "\n").collect::<String>()
+            }</pre>
+        </div>)
+    }
+
+    pub(crate) fn render_migration(&self, ctx: &Context<Self>) -> Html {
+        let Some(migration) = &self.settings.migration else {
+            let targets: Vec<Layout> = Layout::ALL
+                .into_iter()
+                .filter(|l| *l != self.settings.layout)
+                .collect();
+            let options = targets.clone();
+            let onchange = ctx.link().callback(move |e: Event| {
+                let select: HtmlSelectElement = e.target_unchecked_into();
+                // The first option is a prompt to pick one
+                let target = (select.selected_index() as usize)
+                    .checked_sub(1)
+                    .and_then(|i| targets.get(i));
+                match target {
+                    Some(to) => Msg::StartMigration(*to),
+                    None => Msg::EndMigration,
+                }
+            });
+            return html!(<p>
+                {t_args("migration-start", &[("layout", &t(self.settings.layout.message_key()))])}{" "}
+                <select {onchange}>
+                    <option selected=true>{"—"}</option>
+                    {for options.iter().map(|l| html!(<option>{t(l.message_key())}</option>))}
+                </select>
+            </p>);
+        };
+        let unit = self.settings.speed_unit;
+        let progress = layout_switch::progress(&self.history, migration, unit);
+        let speed = |v: Option<f64>| v.map(|v| locale::format_number(v, 1));
+        let on_practicing = |new: bool| {
+            ctx.link()
+                .callback(move |_| Msg::SetPracticingNewLayout(new))
+        };
+        let days = progress
+            .days
+            .iter()
+            .rev()
+            .take(MIGRATION_DAYS_LISTED)
+            .map(|d| {
+                html!(<tr>
+            <td>{locale::format_date(d.at)}</td>
+            <td>{speed(d.old)}</td>
+            <td>{speed(d.new)}</td>
+        </tr>)
+            });
+        let (from, to) = (
+            t(migration.from.message_key()),
+            t(migration.to.message_key()),
+        );
+        html!(<div class="tip">
+            {t_args("migration", &[("from", &from), ("to", &to)])}{" "}
+            <label>
+                <input type="radio" checked={migration.practicing_new} onchange={on_practicing(true)} />
+                {to.clone()}
+            </label>
+            <label>
+                <input type="radio" checked={!migration.practicing_new} onchange={on_practicing(false)} />
+                {from.clone()}
+            </label>
+            {progress.minutes_left.map(|minutes| html!(<>
+                <br />
+                {t_args("migration-time-left", &[("minutes", &(minutes.ceil() as u64))])}
+            </>))}
+            <br />
+            {match migration.stage_chars() {
+                Some(chars) => html!(<>
+                    {t_args("migration-stage", &[
+                        ("stage", &(migration.stage + 1)),
+                        ("stages", &layout_switch::STAGES),
+                    ])}{" "}
+                    <code>{chars.iter().filter(|c| !c.is_uppercase() && **c != ' ').sorted().collect::<String>()}</code>{" "}
+                    <button onclick={ctx.link().callback(|_| Msg::NextMigrationStage)}>{t("migration-next-stage")}</button>
+                </>),
+                None if migration.practicing_new => html!(t("migration-all-keys")),
+                None => html!(),
+            }}
+            <br />
+            {match (progress.surpassed_at, progress.old) {
+                (Some(at), _) => t_args("migration-surpassed", &[("date", &locale::format_date(at))]),
+                (None, Some(old)) => t_args("migration-old-speed", &[
+                    ("speed", &locale::format_number(old, 1)),
+                    ("unit", &t(unit.message_key())),
+                ]),
+                (None, None) => t("migration-no-old-results"),
+            }}
+            {(!progress.days.is_empty()).then(|| html!(<table>
+                <tr><th /><th>{from.clone()}</th><th>{to.clone()}</th></tr>
+                {for days}
+            </table>))}
+            <button onclick={ctx.link().callback(|_| Msg::EndMigration)}>{t("migration-end")}</button>
+        </div>)
+    }
+
+    /// The keys of the course so far and how close the next one is
+    fn render_curriculum(&self, ctx: &Context<Self>) -> Html {
+        let on_accuracy = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input.value().parse().ok().map(Msg::SetCurriculumAccuracy)
+        });
+        let on_wpm = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input.value().parse().ok().map(Msg::SetCurriculumWpm)
+        });
+        let thresholds = html!(<>
+            {t("curriculum-thresholds")}{" "}
+            <input type="number" min="0" max="100" step="1" onchange={on_accuracy}
+                value={self.settings.curriculum_accuracy.to_string()} />{"% "}
+            <input type="number" min="0" step="5" onchange={on_wpm}
+                value={self.settings.curriculum_wpm.to_string()} />{" "}{t(SpeedUnit::Wpm.message_key())}
+        </>);
+        let Some(curriculum) = &self.curriculum else {
+            return html!(<p>
+                <button onclick={ctx.link().callback(|_| Msg::StartCurriculum)}>{t("curriculum-start")}</button>
+                {" "}{thresholds}
+            </p>);
+        };
+        let layout = self.settings.typed_layout();
+        let active = curriculum.active;
+        let minutes_left = curriculum
+            .next_key(layout)
+            .and(Trend::fit(&trend::points(
+                &self.history.results,
+                SpeedUnit::Wpm,
+            )))
+            .and_then(|trend| curriculum.minutes_left(&trend, self.settings.curriculum_wpm));
+        html!(<div class="tip">
+            {match curriculum.keys(layout) {
+                Some(keys) => html!(<>
+                    {t("curriculum-keys")}{" "}<code>{keys.into_iter().collect::<String>()}</code>
+                    {curriculum.next_key(layout).map(|next| html!(<>
+                        {" "}{t_args("curriculum-next", &[
+                            ("key", &next),
+                            ("prompts", &curriculum.recent.len()),
+                            ("needed", &curriculum::RECENT_PROMPTS),
+                        ])}
+                    </>))}
+                    {minutes_left.map(|minutes| html!(<>
+                        {" "}{t_args("curriculum-time-left", &[("minutes", &(minutes.ceil() as u64))])}
+                    </>))}
+                </>),
+                None => html!(t("curriculum-all-keys")),
+            }}
+            <br />
+            {thresholds}
+            <br />
+            <button onclick={ctx.link().callback(move |_| Msg::SetCurriculumActive(!active))}>
+                {t(if active { "curriculum-pause" } else { "curriculum-resume" })}
+            </button>
+            {" "}<button onclick={ctx.link().callback(|_| Msg::ResetCurriculum)}>{t("curriculum-reset")}</button>
+        </div>)
+    }
+
+    fn render_discovery_controls(&self, ctx: &Context<Self>) -> Html {
+        let on_delay = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input.value().parse().ok().map(Msg::SetDiscoveryDelay)
+        });
+        let hardest = self
+            .discovery_stats
+            .keys
+            .iter()
+            .filter(|(_c, r)| r.revealed > 0)
+            .sorted_by(|(_, a), (_, b)| b.rate().total_cmp(&a.rate()))
+            .take(DISCOVERY_HARDEST_KEYS)
+            .map(|(c, r)| html!(<tr>
+                <td>{c}</td>
+                <td>{t_args("discovery-revealed", &[("revealed", &r.revealed), ("shown", &r.shown)])}</td>
+            </tr>));
+        html!(<p>
+            {(self.discovery.is_none()).then(|| html!(<button onclick={ctx.link().callback(|_| Msg::StartDiscovery)}>
+                {t("discovery-start")}
+            </button>))}
+            {" "}{t("discovery-delay")}{" "}
+            <input type="number" min="0" step="100" onchange={on_delay}
+                value={self.settings.discovery_delay_ms.to_string()} />
+            <table>{for hardest}</table>
+        </p>)
+    }
+
+    pub(crate) fn render_exam(&self, ctx: &Context<Self>) -> Html {
+        if let Some(exam) = &self.exam {
+            let remaining = exam.remaining(startup::now());
+            let backspaces = exam
+                .backspaces_left()
+                .map_or_else(|| t("exam-unlimited"), |n| n.to_string());
+            return html!(<p>
+                {t_args("exam-progress", &[
+                    ("remaining", &locale::format_duration((remaining / 1000.0).ceil() * 1000.0)),
+                    ("backspaces", &backspaces),
+                ])}
+                {" "}<button onclick={ctx.link().callback(|_| Msg::StopExam)}>{t("exam-stop")}</button>
+            </p>);
+        }
+        let onchange = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            // An empty field is no limit
+            Msg::SetExamBackspaceLimit(input.value().parse().ok())
+        });
+        let rows = self.exam_results.results().iter().rev().map(|r| html!(<tr>
+            <td>{locale::format_date(r.meta.created_at)}</td>
+            <td>{locale::format_number(r.net_wpm(), 1)}</td>
+            <td>{locale::format_number(r.gross_wpm(), 1)}</td>
+            <td>{r.errors}</td>
+            <td>{format!("{}%", locale::format_number(r.accuracy(), 1))}</td>
+            <td>{r.backspace_limit.map_or_else(|| t("exam-unlimited"), |n| n.to_string())}</td>
+            <td>{(!r.is_intact()).then(|| html!(<span class="flagged">{t("exam-tampered")}</span>))}</td>
+        </tr>));
+        html!(<div>
+            <button
+                onclick={ctx.link().callback(|_| Msg::StartExam)}
+                disabled={self.exam_loading == Loading::InProgress}
+            >
+                {t("exam-start")}
+            </button>
+            {render_loading(&self.exam_loading)}
+            {" "}{t("exam-backspace-limit")}{" "}
+            <input type="number" min="0" {onchange} placeholder={t("exam-unlimited")}
+                value={self.settings.exam_backspace_limit.map(|n| n.to_string()).unwrap_or_default()} />
+            {(!self.exam_results.results().is_empty()).then(|| html!(<table>
+                <tr>
+                    <th>{t("result-date")}</th>
+                    <th>{t("exam-net-wpm")}</th>
+                    <th>{t("exam-gross-wpm")}</th>
+                    <th>{t("exam-errors")}</th>
+                    <th>{t("data-entry-accuracy")}</th>
+                    <th>{t("exam-backspace-limit")}</th>
+                    <th></th>
+                </tr>
+                {for rows}
+            </table>))}
+        </div>)
+    }
+
+    /// Countdown of the running timed test, its summary once the time is up,
+    /// or the lengths to start one with
+    pub(crate) fn render_timed(&self, ctx: &Context<Self>) -> Html {
+        if let Some(test) = &self.timed {
+            let remaining = test.remaining(startup::now());
+            return html!(<p>
+                {t_args("timed-progress", &[
+                    ("remaining", &locale::format_duration((remaining / 1000.0).ceil() * 1000.0)),
+                ])}
+                {" "}<button onclick={ctx.link().callback(|_| Msg::StopTimedTest)}>{t("timed-stop")}</button>
+            </p>);
+        }
+        if let Some(result) = &self.timed_result {
+            let (seconds, hand) = (result.seconds, result.hand);
+            return html!(<div class="tip">
+                {hand.map(|h| html!(<>{t(timed_hand_key(h))}{" "}</>))}
+                {t_args("timed-result", &[
+                    ("seconds", &seconds),
+                    ("speed", &self.format_speed(&result.sample)),
+                    ("accuracy", &locale::format_number(
+                        history::accuracy(result.sample.keystrokes, result.mistakes), 1,
+                    )),
+                    ("chars", &result.sample.chars),
+                    ("mistakes", &result.mistakes),
+                ])}<br />
+                <button onclick={ctx.link().callback(move |_| Msg::StartTimedTest(seconds, hand))}>{t("timed-again")}</button>
+                {" "}<button onclick={ctx.link().callback(|_| Msg::CloseTimedResult)}>{t("timed-close")}</button>
+            </div>);
+        }
+        let row = |hand: Option<Hand>| {
+            let buttons = timed::DURATIONS_S.into_iter().map(|seconds| {
+                let best =
+                    timed::personal_best(&self.history, seconds, hand, self.settings.speed_unit);
+                html!(<>
+                {" "}<button onclick={ctx.link().callback(move |_| Msg::StartTimedTest(seconds, hand))}>
+                    {t_args("timed-seconds", &[("seconds", &seconds)])}
+                </button>
+                {best.map(|r| html!(<>{" "}{t_args("timed-best", &[
+                    ("speed", &self.format_speed(&r.speed_sample())),
+                ])}</>))}
+            </>)
+            });
+            html!(<>{t(hand.map_or("timed", timed_hand_key))}{for buttons}<br /></>)
+        };
+        html!(<p>{for timed::HANDS.into_iter().map(row)}</p>)
+    }
+
+    pub(crate) fn render_data_entry(&self, ctx: &Context<Self>) -> Html {
+        if let Some(test) = &self.data_entry {
+            let (field, fields) = test.position();
+            return html!(<p>
+                {t_args("data-entry-progress", &[("field", &field), ("fields", &fields)])}
+                {" "}<button onclick={ctx.link().callback(|_| Msg::StopDataEntry)}>{t("data-entry-stop")}</button>
+            </p>);
+        }
+        let Some(result) = &self.data_entry_result else {
+            return html!(<p>
+                <button onclick={ctx.link().callback(|_| Msg::StartDataEntry)}>{t("data-entry-start")}</button>
+                {" "}{t_args("data-entry-hint", &[("fields", &data_entry::FIELDS)])}
+            </p>);
+        };
+        html!(<div class="print-summary">
+            <h3>{t("data-entry-summary")}</h3>
+            <table>
+                <tr><td>{t("result-date")}</td><td>{locale::format_date(result.date)}</td></tr>
+                <tr><td>{t("time")}</td><td>{locale::format_duration(result.millis)}</td></tr>
+                <tr><td>{t("data-entry-fields")}</td><td>{result.fields}</td></tr>
+                <tr><td>{t("data-entry-keystrokes")}</td><td>{result.keystrokes}</td></tr>
+                <tr><td>{t("data-entry-errors")}</td><td>{result.errors}</td></tr>
+                <tr><td>{t("data-entry-accuracy")}</td><td>{format!("{}%", locale::format_number(result.accuracy(), 1))}</td></tr>
+                <tr><td>{t("data-entry-gross-kph")}</td><td>{locale::format_number(result.gross_kph(), 0)}</td></tr>
+                <tr><td>{t("data-entry-net-kph")}</td><td>{locale::format_number(result.net_kph(), 0)}</td></tr>
+            </table>
+            <p>{t_args("data-entry-rules", &[("penalty", &data_entry::ERROR_PENALTY)])}</p>
+            <p class="no-print">
+                <button onclick={ctx.link().callback(|_| Msg::PrintDataEntryResult)}>{t("data-entry-print")}</button>
+                {" "}<button onclick={ctx.link().callback(|_| Msg::CloseDataEntryResult)}>{t("data-entry-close")}</button>
+            </p>
+        </div>)
+    }
+
+    /// Ways to practice other than the prompts of the selected source
+    pub(crate) fn render_lessons_page(&self, ctx: &Context<Self>) -> Html {
+        html!(<>
+            {self.render_drill(ctx)}
+            {self.render_book(ctx)}
+            {self.render_data_entry(ctx)}
+            {self.render_exam(ctx)}
+            {self.render_timed(ctx)}
+            {self.render_curriculum(ctx)}
+            {self.render_discovery_controls(ctx)}
+            {self.render_migration(ctx)}
+            <ImportText
+                expected_chars={self.expected_chars.clone()}
+                on_extend={ctx.link().callback(Msg::ExtendExpectedChars)}
+                on_practice={ctx.link().callback(Msg::PracticeText)}
+                on_drill={ctx.link().callback(Msg::StartDrill)}
+                on_open_book={ctx.link().callback(|text| Msg::BookFileRead(t("book-pasted"), Ok(text)))}
+                normalization={self.settings.normalization}
+                on_normalize={ctx.link().callback(Msg::SetNormalization)}
+            />
+        </>)
+    }
+}
+
+fn timed_hand_key(hand: Hand) -> &'static str {
+    match hand {
+        Hand::Left => "timed-left",
+        Hand::Right => "timed-right",
+    }
+}
@@ -0,0 +1,27 @@
+//! The pages the router shows, each with its view and the state only it uses
+
+mod lessons;
+mod practice;
+mod settings;
+mod stats;
+
+pub(crate) use lessons::LessonsPage;
+pub(crate) use practice::{PracticePage, TipState};
+pub(crate) use settings::SettingsPage;
+pub(crate) use stats::StatsPage;
+
+use yew::{html, Context, Html};
+
+use crate::{route::Route, Practice};
+
+impl Practice {
+    /// The page of `route`, `prompt` is what is typed on the practice page
+    pub(crate) fn render_page(&self, ctx: &Context<Self>, route: Route, prompt: Html) -> Html {
+        match route {
+            Route::Practice => self.render_practice_page(ctx, prompt),
+            Route::Stats => html!(<div class="stats">{self.render_stats(ctx)}</div>),
+            Route::Lessons => self.render_lessons_page(ctx),
+            Route::Settings => self.render_settings_page(ctx),
+        }
+    }
+}
@@ -0,0 +1,443 @@
+//! The practice page: the prompt and what is going on while typing it.
+
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::{
+    backup, blocklist, codec, curriculum,
+    goal::Goal,
+    history,
+    i18n::{t, t_args},
+    locale, metrics, render_loading,
+    session::SessionSummary,
+    snippets::CodeLanguage,
+    sources,
+    suggestions::Suggestion,
+    tips,
+    units::{SpeedUnit, TimerStart},
+    Msg, Practice,
+};
+
+/// Cards and notices shown around the prompt until dismissed
+pub(crate) struct PracticePage {
+    pub(crate) tip: TipState,
+    /// Recap of the previous session, until dismissed
+    pub(crate) last_session: Option<SessionSummary>,
+    /// Cards shown while idle, empty when not idle
+    pub(crate) suggestions: Vec<Suggestion>,
+    /// Index of the card shown
+    pub(crate) suggestion_shown: usize,
+    /// Stored values that could not be read and were set aside, with why
+    pub(crate) unreadable: Vec<(String, String)>,
+    /// The backup reminder was put off until the next visit
+    pub(crate) backup_postponed: bool,
+    /// The warm-up routine is offered until it is started or skipped
+    pub(crate) warm_up_offered: bool,
+}
+
+/// At most one technique tip is shown per session
+pub(crate) enum TipState {
+    Pending,
+    Showing(&'static tips::TipRule),
+    Dismissed,
+}
+
+impl Practice {
+    fn render_speed(&self) -> Option<String> {
+        Some(self.format_speed(&self.live_speed_sample()?))
+    }
+
+    fn render_source_select(&self, ctx: &Context<Self>) -> Html {
+        let registry = sources::registry(&self.settings);
+        let ids: Vec<&'static str> = registry.iter().map(|s| s.info().id).collect();
+        let onchange = ctx.link().callback(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::SetPromptSource(ids[select.selected_index() as usize].to_string())
+        });
+        let on_url = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetSourceUrl(input.value().trim().to_string())
+        });
+        let on_language = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            // The first option mixes the languages
+            let index = select.selected_index() as usize;
+            Msg::SetSnippetLanguage(index.checked_sub(1).map(|i| CodeLanguage::ALL[i]))
+        });
+        let on_uppercase = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetUppercaseWords(input.checked())
+        });
+        let on_built_in = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetBuiltInBlocklist(input.checked())
+        });
+        let on_blocked = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetBlockedWords(blocklist::parse(&input.value()))
+        });
+        let selected = self.prompt_source().info().id;
+        // Random symbols and code snippets have no words to leave out
+        let has_words = selected != sources::DEFAULT_SOURCE && selected != sources::SNIPPETS_SOURCE;
+        html!(<p>
+            {t("prompt-source")}{" "}
+            <select {onchange}>
+                {for registry.iter().map(|s| {
+                    let info = s.info();
+                    html!(<option selected={info.id == selected}>{t(info.name_key)}</option>)
+                })}
+            </select>
+            {(selected == sources::SNIPPETS_SOURCE).then(|| html!(<>
+                {" "}
+                <select onchange={on_language}>
+                    <option selected={self.settings.snippet_language.is_none()}>{t("code-any")}</option>
+                    {for CodeLanguage::ALL.iter().map(|l| html!(
+                        <option selected={self.settings.snippet_language == Some(*l)}>{t(l.message_key())}</option>
+                    ))}
+                </select>
+            </>))}
+            {(selected == sources::DICTIONARY_SOURCE).then(|| html!(<>
+                {" "}<label>
+                    <input type="checkbox" checked={self.settings.uppercase_words} onchange={on_uppercase} />
+                    {t("uppercase-words")}
+                </label>
+            </>))}
+            {(selected == "url").then(|| html!(<>
+                {" "}<input type="url" value={self.settings.source_url.clone()}
+                    placeholder={t("source-url-placeholder")} onchange={on_url} />
+            </>))}
+            {render_loading(&self.source_loading)}
+            {has_words.then(|| html!(<>
+                <br />
+                <label>
+                    <input type="checkbox" checked={self.settings.built_in_blocklist} onchange={on_built_in} />
+                    {t("blocklist-built-in")}
+                </label>
+                {" "}{t("blocked-words")}{" "}
+                <input type="text" value={self.settings.blocked_words.join(", ")}
+                    placeholder={t("blocked-words-placeholder")} onchange={on_blocked} />
+            </>))}
+        </p>)
+    }
+
+    fn render_timer_start_select(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::SetTimerStart(TimerStart::ALL[select.selected_index() as usize])
+        });
+        html!(
+            <select {onchange} title={t("timer-start-hint")}>
+                {for TimerStart::ALL.iter().map(|s| html!(
+                    <option selected={*s == self.settings.timer_start}>{t(s.message_key())}</option>
+                ))}
+            </select>
+        )
+    }
+
+    fn render_speed_unit_select(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::SetSpeedUnit(SpeedUnit::ALL[select.selected_index() as usize])
+        });
+        html!(
+            <select {onchange}>
+                {for SpeedUnit::ALL.iter().map(|u| html!(
+                    <option selected={*u == self.settings.speed_unit}>{t(u.message_key())}</option>
+                ))}
+            </select>
+        )
+    }
+
+    fn render_last_session(&self, ctx: &Context<Self>) -> Html {
+        let Some(session) = &self.practice_page.last_session else {
+            return html!();
+        };
+        html!(
+            <div class="tip">
+                {t_args("last-session", &[
+                    ("date", &locale::format_date(session.started_at)),
+                    ("duration", &locale::format_duration(session.ended_at - session.started_at)),
+                ])}<br />
+                {render_session_summary(session)}
+                <button onclick={ctx.link().callback(|_| Msg::DismissLastSession)}>{t("tip-dismiss")}</button>
+            </div>
+        )
+    }
+
+    /// Goals to start a session with, the progress towards the current one
+    /// or the summary of the last one
+    fn render_goal(&self, ctx: &Context<Self>) -> Html {
+        let course = self.curriculum.as_ref().filter(|c| c.active);
+        if let Some(goal) = &self.goal {
+            let now = js_sys::Date::now();
+            let course_share = course.map_or(0.0, |c| {
+                c.recent.len() as f64 / curriculum::RECENT_PROMPTS as f64
+            });
+            let label = match goal.goal {
+                Goal::Minutes(minutes) => t_args(
+                    "goal-minutes-progress",
+                    &[
+                        ("elapsed", &locale::format_duration(now - goal.started_at)),
+                        ("minutes", &minutes),
+                    ],
+                ),
+                Goal::Prompts(prompts) => t_args(
+                    "goal-prompts-progress",
+                    &[
+                        ("done", &goal.prompts(self.prompts_done)),
+                        ("prompts", &prompts),
+                    ],
+                ),
+                Goal::CourseStage => {
+                    let key = course
+                        .and_then(|c| c.next_key(self.settings.typed_layout()))
+                        .map_or_else(String::new, String::from);
+                    t_args("goal-stage-progress", &[("key", &key)])
+                }
+            };
+            let progress = goal.progress(now, self.prompts_done, course_share);
+            return html!(<p>
+                {label}{" "}
+                <progress max="1" value={progress.to_string()}></progress>
+                {" "}<button onclick={ctx.link().callback(|_| Msg::StopGoal)}>{t("goal-stop")}</button>
+            </p>);
+        }
+        if let Some((reached, summary)) = &self.goal_summary {
+            return html!(<div class="tip">
+                {t_args(if *reached { "goal-reached" } else { "goal-stopped" }, &[
+                    ("duration", &locale::format_duration(summary.ended_at - summary.started_at)),
+                ])}<br />
+                {render_session_summary(summary)}
+                <button onclick={ctx.link().callback(|_| Msg::DismissGoalSummary)}>{t("tip-dismiss")}</button>
+            </div>);
+        }
+        // A course stage can only be reached while the course is on
+        let goals = Goal::ALL
+            .into_iter()
+            .filter(|g| *g != Goal::CourseStage || course.is_some())
+            .map(|goal| html!(<>
+                {" "}<button onclick={ctx.link().callback(move |_| Msg::StartGoal(goal))}>{goal_label(goal)}</button>
+            </>));
+        html!(<p>{t("goal-start")}{for goals}</p>)
+    }
+
+    fn render_backup_reminder(&self, ctx: &Context<Self>) -> Html {
+        if self.practice_page.backup_postponed {
+            return html!();
+        }
+        let due = backup::backup_due(
+            self.last_export,
+            &self.history,
+            self.settings.backup_reminder_days,
+            js_sys::Date::now(),
+        );
+        let Some(results) = due else {
+            return html!();
+        };
+        html!(
+            <div class="tip">
+                {t_args("backup-due", &[("results", &results)])}{" "}
+                <button onclick={ctx.link().callback(|_| Msg::DownloadStore)}>{t("backup-download")}</button>
+                {" "}<button onclick={ctx.link().callback(|_| Msg::PostponeBackup)}>{t("backup-later")}</button>
+            </div>
+        )
+    }
+
+    /// Warning about stored values that could not be read, until dismissed
+    fn render_unreadable(&self, ctx: &Context<Self>) -> Html {
+        if self.practice_page.unreadable.is_empty() {
+            return html!();
+        }
+        html!(
+            <div class="tip">
+                <span class="flagged">{t_args("unreadable", &[
+                    ("suffix", &codec::UNREADABLE_SUFFIX),
+                ])}</span>
+                <ul>
+                    {for self.practice_page.unreadable.iter().map(|(key, error)| html!(
+                        <li><code>{key}</code>{": "}{error}</li>
+                    ))}
+                </ul>
+                <button onclick={ctx.link().callback(|_| Msg::DismissUnreadable)}>{t("tip-dismiss")}</button>
+            </div>
+        )
+    }
+
+    /// A card of what to practice next, while the app is idle
+    fn render_suggestion(&self, ctx: &Context<Self>) -> Html {
+        let Some(suggestion) = self
+            .practice_page
+            .suggestions
+            .get(self.practice_page.suggestion_shown)
+            .copied()
+        else {
+            return html!();
+        };
+        let (text, action) = match suggestion {
+            Suggestion::SlowerKey { key, percent } => (
+                t_args(
+                    "suggestion-slower-key",
+                    &[
+                        ("key", &key),
+                        ("percent", &locale::format_number(percent, 0)),
+                    ],
+                ),
+                t("suggestion-drill"),
+            ),
+            Suggestion::TimedTest { days: Some(days) } => (
+                t_args("suggestion-timed-test", &[("days", &days)]),
+                t("suggestion-take-timed-test"),
+            ),
+            Suggestion::TimedTest { days: None } => (
+                t("suggestion-first-timed-test"),
+                t("suggestion-take-timed-test"),
+            ),
+            Suggestion::WeakestKey { key } => (
+                t_args("suggestion-weakest-key", &[("key", &key)]),
+                t("suggestion-drill"),
+            ),
+        };
+        html!(
+            <div class="tip">
+                {text}{" "}
+                <button onclick={ctx.link().callback(move |_| Msg::ActOnSuggestion(suggestion))}>{action}</button>
+                {" "}
+                <button onclick={ctx.link().callback(|_| Msg::DismissSuggestions)}>{t("tip-dismiss")}</button>
+            </div>
+        )
+    }
+
+    fn render_tip(&self, ctx: &Context<Self>) -> Html {
+        let TipState::Showing(rule) = self.practice_page.tip else {
+            return html!();
+        };
+        html!(
+            <div class="tip">
+                {t(rule.message_key)}
+                {" "}<button onclick={ctx.link().callback(|_| Msg::DismissTip)}>{t("tip-dismiss")}</button>
+            </div>
+        )
+    }
+
+    /// Rules of the mode opened for the first time, over the page until dismissed
+    fn render_hint(&self, ctx: &Context<Self>) -> Html {
+        let Some(mode) = self.mode().filter(|m| !self.seen_hints.contains(*m)) else {
+            return html!();
+        };
+        html!(
+            <div class="hint" role="dialog">
+                <p>{t(mode.message_key())}</p>
+                <button onclick={ctx.link().callback(move |_| Msg::DismissHint(mode))}>{t("hint-dismiss")}</button>
+            </div>
+        )
+    }
+
+    /// The warm-up offer at the start of a session, or the progress through it
+    fn render_warm_up(&self, ctx: &Context<Self>) -> Html {
+        let skip = html!(<button onclick={ctx.link().callback(|_| Msg::SkipWarmUp)}>{t("warm-up-skip")}</button>);
+        if let Some(warm_up) = &self.warm_up {
+            let (line, lines) = warm_up.position();
+            return html!(<p>
+                {t_args("warm-up-progress", &[
+                    ("section", &t(warm_up.section().message_key())),
+                    ("line", &line),
+                    ("lines", &lines),
+                ])}{" "}
+                {skip}
+            </p>);
+        }
+        let busy = self.drill.is_some()
+            || self.exam.is_some()
+            || self.data_entry.is_some()
+            || self.timed.is_some();
+        if !self.practice_page.warm_up_offered || busy {
+            return html!();
+        }
+        html!(<div class="tip">
+            {t("warm-up-offer")}<br />
+            <button onclick={ctx.link().callback(|_| Msg::StartWarmUp)}>{t("warm-up-start")}</button>
+            {" "}{skip}
+        </div>)
+    }
+
+    /// The prompt and what is going on while typing it
+    pub(crate) fn render_practice_page(&self, ctx: &Context<Self>, prompt: Html) -> Html {
+        html!(<>
+            {self.render_unreadable(ctx)}
+            {self.render_hint(ctx)}
+            {self.render_last_session(ctx)}
+            {self.render_regression(ctx)}
+            {self.render_suggestion(ctx)}
+            {self.render_goal(ctx)}
+            {self.render_tip(ctx)}
+            {self.render_backup_reminder(ctx)}
+            {self.drill.is_some().then(|| self.render_drill(ctx))}
+            {(self.data_entry.is_some() || self.data_entry_result.is_some()).then(|| self.render_data_entry(ctx))}
+            {self.exam.is_some().then(|| self.render_exam(ctx))}
+            {(self.timed.is_some() || self.timed_result.is_some()).then(|| self.render_timed(ctx))}
+            {self.settings.migration.is_some().then(|| self.render_migration(ctx))}
+            {self.render_warm_up(ctx)}
+            {self.unlocked_key.map(|key| html!(<p class="tip">{t_args("curriculum-unlocked", &[("key", &key)])}</p>))}
+            {self.retention_check.then(|| html!(<>{t("retention-check-prompt")}<br /></>))}
+            {self.typing_book().then(|| self.render_book_progress())}
+            {self.render_source_select(ctx)}
+            {t("type-this")}
+            {(self.warm_up.is_none() && self.is_warm_up()).then(|| html!(<span class="flagged">{" "}{t_args("warm-up", &[
+                ("prompt", &(self.prompts_done + 1)),
+                ("prompts", &self.settings.warm_up_prompts),
+            ])}</span>))}
+            {prompt}
+            {t("speed")} {" "}
+            {self.render_speed()}
+            {" "}{self.render_speed_unit_select(ctx)}
+            {" "}{self.render_timer_start_select(ctx)} <br />
+            {(self.keystrokes > 0).then(|| html!(<>
+                {t("accuracy")}{" "}
+                {locale::format_number(history::accuracy(self.keystrokes, self.mistakes_on_this_line), 1)}{"%"}
+                <br />
+            </>))}
+            {self.speed_sample().map(|s| html!(
+                <>{t("time")} {" "} {locale::format_duration(s.millis)} <br /></>
+            ))}
+            {self.flagged.map(|reason| html!(
+                <p class="flagged">{t_args("rejected-input", &[("reason", &t(reason.message_key()))])}</p>
+            ))}
+            {self.caps_lock.then(|| html!(<p class="flagged caps-lock">{
+                if self.settings.caps_lock_guard { t("caps-lock-ignored") } else { t("caps-lock-on") }
+            }</p>))}
+            {t("last-mistakes")}
+            <pre class="mistyped">{
+                self.mistyped
+                .iter()
+                .rev()
+                .map(|(ex, ty, n)| match n {
+                    1 => format!("{ex} -> {ty}\n"),
+                    n => format!("{ex} -> {ty} ×{n}\n"),
+                })
+                .collect::<String>()
+            }</pre>
+        </>)
+    }
+}
+
+fn goal_label(goal: Goal) -> String {
+    match goal {
+        Goal::Minutes(minutes) => t_args("goal-minutes", &[("minutes", &minutes)]),
+        Goal::Prompts(prompts) => t_args("goal-prompts", &[("prompts", &prompts)]),
+        Goal::CourseStage => t("goal-course-stage"),
+    }
+}
+
+/// Prompts, keystrokes and the metrics of a session
+fn render_session_summary(session: &SessionSummary) -> Html {
+    let metrics = session.metrics.iter().filter_map(|(id, value)| {
+        let metric = metrics::find(id)?;
+        Some(html!(<>
+            {t(metric.name_key())}{": "}{metric.unit(session.speed_unit).format(*value)}<br />
+        </>))
+    });
+    html!(<>
+        {t_args("last-session-prompts", &[("prompts", &session.prompts), ("keystrokes", &session.keystrokes)])}<br />
+        {for metrics}
+    </>)
+}
@@ -0,0 +1,618 @@
+//! The settings page: practice and appearance settings, moving and
+//! deleting data, and share codes.
+
+use itertools::Itertools;
+use typing_core::{errors::TypingErrors, DAY_MS};
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use crate::{
+    ambient::Soundscape,
+    analytics::SymbolClass,
+    audio::SoundTheme,
+    aux_input::AuxAction,
+    backup::{MergePreview, StatsFile},
+    charset::CharSet,
+    deletion::{Deletion, DeletionPreview},
+    i18n::{t, t_args},
+    keycaps::Layout,
+    locale,
+    session::{self, Period},
+    settings::Penalties,
+    share_code::{Preset, ShareCodeError},
+    sources::{self, CharWeights},
+    startup,
+    store::Backend,
+    Msg, Practice,
+};
+
+/// Most recent sessions offered for deletion
+const DELETION_SESSIONS_LISTED: usize = 20;
+/// Characters listed in the preview of changed penalties
+const PENALTY_PREVIEW_CHARS: usize = 10;
+
+/// Imports, share codes and deletions waiting to be confirmed, and edits in progress
+pub(crate) struct SettingsPage {
+    /// A read stats file waiting for the user to confirm the merge
+    pub(crate) pending_import: Option<(StatsFile, MergePreview)>,
+    pub(crate) import_error: Option<String>,
+    /// Encrypts downloaded files and decrypts opened ones, never stored
+    pub(crate) file_password: String,
+    /// A share code entered, waiting for the user to apply it
+    pub(crate) shared_preset: Option<Result<Preset, ShareCodeError>>,
+    /// Start and end day of the range of data to delete
+    pub(crate) deletion_range: (Option<f64>, Option<f64>),
+    /// Data chosen for deletion waiting for the user to confirm
+    pub(crate) pending_deletion: Option<(Deletion, DeletionPreview)>,
+    /// Penalties being edited and the stats they would give
+    pub(crate) penalty_preview: Option<(Penalties, TypingErrors)>,
+    /// Action the next auxiliary button press gets bound to
+    pub(crate) binding: Option<AuxAction>,
+}
+
+impl Practice {
+    fn render_practice_settings(&self, ctx: &Context<Self>) -> Html {
+        let on_len = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input
+                .value()
+                .parse()
+                .ok()
+                .filter(|n| *n > 0)
+                .map(Msg::SetPromptLen)
+        });
+        let on_mistakes = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input.value().parse().ok().map(Msg::SetMistakesShown)
+        });
+        let on_persist = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetPersistStats(input.checked())
+        });
+        let on_caps_lock = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetCapsLockGuard(input.checked())
+        });
+        let on_layout = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::SetLayout(Layout::ALL[select.selected_index() as usize])
+        });
+        let on_reminder = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input.value().parse().ok().map(Msg::SetBackupReminderDays)
+        });
+        let on_charset = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::SetCharSet(CharSet::ALL[select.selected_index() as usize])
+        });
+        let symbols = self.settings.charset.chars();
+        // Only classes random symbols can be made of
+        let classes = SymbolClass::ALL
+            .into_iter()
+            .filter(|class| symbols.iter().any(|c| SymbolClass::of(*c) == *class))
+            .map(|class| {
+                let onchange = ctx.link().callback(move |e: Event| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    Msg::SetSymbolClass(class, input.checked())
+                });
+                let checked = !self.settings.disabled_classes.contains(&class);
+                html!(<label>
+                    <input type="checkbox" {checked} {onchange} />
+                    {t(class.message_key())}{" "}
+                </label>)
+            });
+        html!(<details>
+            <summary>{"⚙ "}{t("practice-settings")}</summary>
+            {t("keyboard-layout")}{" "}
+            <select onchange={on_layout}>
+                {for Layout::ALL.iter().map(|l| html!(
+                    <option selected={*l == self.settings.layout}>{t(l.message_key())}</option>
+                ))}
+            </select>
+            <br />
+            {t("prompt-len")}{" "}
+            <input type="number" min="1" onchange={on_len} value={self.settings.prompt_len.to_string()} />
+            <br />
+            {t("charset")}{" "}
+            <select onchange={on_charset}>
+                {for CharSet::ALL.iter().map(|c| html!(
+                    <option selected={*c == self.settings.charset}>{t(c.message_key())}</option>
+                ))}
+            </select>
+            <br />
+            {t("symbol-classes")}{" "}
+            {for classes}
+            <br />
+            {t("mistakes-shown")}{" "}
+            <input type="number" min="0" onchange={on_mistakes} value={self.settings.mistakes_shown.to_string()} />
+            <br />
+            <label>
+                <input type="checkbox" checked={self.settings.persist_stats} onchange={on_persist} />
+                {t("persist-stats")}
+            </label>
+            <br />
+            <label>
+                <input type="checkbox" checked={self.settings.caps_lock_guard} onchange={on_caps_lock} />
+                {t("caps-lock-guard")}
+            </label>
+            <br />
+            <button onclick={ctx.link().callback(|_| Msg::ResetHints)}>{t("hints-reset")}</button>
+            <br />
+            {t("backup-reminder-days")}{" "}
+            <input type="number" min="0" onchange={on_reminder} value={self.settings.backup_reminder_days.to_string()} />
+        </details>)
+    }
+
+    fn render_ambient(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            // The first option is silence
+            let index = select.selected_index() as usize;
+            Msg::SetAmbient(index.checked_sub(1).map(|i| Soundscape::ALL[i]))
+        });
+        let on_volume = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetAmbientVolume(input.value_as_number())
+        });
+        html!(<>
+            {t("ambient")}{" "}
+            <select {onchange}>
+                <option selected={self.settings.ambient.is_none()}>{t("ambient-none")}</option>
+                {for Soundscape::ALL.iter().map(|s| html!(
+                    <option selected={self.settings.ambient == Some(*s)}>{t(s.message_key())}</option>
+                ))}
+            </select>
+            {self.settings.ambient.is_some().then(|| html!(<>
+                {" "}{t("ambient-volume")}{" "}
+                <input type="range" min="0" max="1" step="0.05"
+                    value={self.settings.ambient_volume.to_string()} onchange={on_volume} />
+            </>))}
+            <br />
+        </>)
+    }
+
+    fn render_key_sounds(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetKeySounds(input.checked())
+        });
+        let on_theme = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::SetSoundTheme(SoundTheme::ALL[select.selected_index().max(0) as usize])
+        });
+        let on_errors = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetErrorSounds(input.checked())
+        });
+        let latency = match (self.sound_latency, self.reported_latency()) {
+            (Some(ms), _) => t_args("sound-latency-calibrated", &[("ms", &ms.round())]),
+            (None, Some(ms)) => t_args("sound-latency-reported", &[("ms", &ms.round())]),
+            (None, None) => t("sound-latency-unknown"),
+        };
+        html!(<>
+            <label>
+                <input type="checkbox" checked={self.settings.key_sounds} {onchange} />
+                {t("key-sounds")}
+            </label>
+            {self.settings.key_sounds.then(|| html!(<>
+                {" "}{latency}{" "}{t("sound-theme")}{" "}
+                <select onchange={on_theme}>
+                    {for SoundTheme::ALL.iter().map(|theme| html!(
+                        <option selected={*theme == self.settings.sound_theme}>{t(theme.message_key())}</option>
+                    ))}
+                </select>
+                {" "}
+                <label>
+                    <input type="checkbox" checked={self.settings.error_sounds} onchange={on_errors} />
+                    {t("error-sounds")}
+                </label>
+            </>))}
+            {" "}
+            {match &self.calibration {
+                Some(calibration) => html!(t_args("sound-calibrating", &[("taps", &calibration.taps())])),
+                None => html!(<button onclick={ctx.link().callback(|_| Msg::StartCalibration)}>
+                    {t("sound-calibrate")}
+                </button>),
+            }}
+            <br />
+        </>)
+    }
+
+    fn render_auto_advance(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            // An empty field waits for Enter
+            Msg::SetAutoAdvance(input.value().parse().ok())
+        });
+        let on_warm_up = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input.value().parse().ok().map(Msg::SetWarmUpPrompts)
+        });
+        html!(<p>
+            {t("auto-advance")}{" "}
+            <input type="number" min="0" {onchange} placeholder={t("auto-advance-off")}
+                value={self.settings.auto_advance.map(|n| n.to_string()).unwrap_or_default()} />
+            {" "}{t("auto-advance-seconds")}<br />
+            {t("warm-up-prompts")}{" "}
+            <input type="number" min="0" onchange={on_warm_up}
+                value={self.settings.warm_up_prompts.to_string()} />
+        </p>)
+    }
+
+    fn render_share_code(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::EnterShareCode(input.value())
+        });
+        let preview = self.settings_page.shared_preset.as_ref().map(|preset| match preset {
+            Ok(preset) => {
+                let source = sources::find(&preset.prompt_source, &self.settings);
+                html!(<div class="tip">
+                    {t("share-code-preview")}
+                    <pre>
+                        {t(preset.layout.message_key())}{"\n"}
+                        {t(preset.finger_map.message_key())}{"\n"}
+                        {t(preset.charset.message_key())}{"\n"}
+                        {t(source.info().name_key)}{"\n"}
+                        {t_args("share-code-chars", &[("chars", &preset.extra_chars.iter().collect::<String>())])}
+                    </pre>
+                    <button onclick={ctx.link().callback(|_| Msg::ApplySharedPreset)}>{t("share-code-apply")}</button>
+                </div>)
+            }
+            Err(e) => html!(<p class="flagged">{t(e.message_key())}</p>),
+        });
+        html!(<details>
+            <summary>{t("share-code")}</summary>
+            {t("share-code-yours")}{" "}
+            <input type="text" readonly=true value={Preset::from_settings(&self.settings).encode()} />
+            <br />
+            {t("share-code-enter")}{" "}
+            <input type="text" {onchange} />
+            {preview}
+        </details>)
+    }
+
+    fn render_appearance(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let area: HtmlTextAreaElement = e.target_unchecked_into();
+            Msg::SetCustomCss(area.value())
+        });
+        let on_read_aloud = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetReadAloud(input.checked())
+        });
+        let on_pace_caret = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetPaceCaret(input.checked())
+        });
+        let on_large_text = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetLargeText(input.checked())
+        });
+        let on_show_keyboard = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetShowKeyboard(input.checked())
+        });
+        html!(<details>
+            <summary>{t("appearance")}</summary>
+            <label>
+                <input type="checkbox" checked={self.settings.large_text} onchange={on_large_text} />
+                {t("large-text")}
+            </label>
+            <br />
+            <label>
+                <input type="checkbox" checked={self.settings.show_keyboard} onchange={on_show_keyboard} />
+                {t("show-keyboard")}
+            </label>
+            <br />
+            <label>
+                <input type="checkbox" checked={self.settings.read_aloud} onchange={on_read_aloud} />
+                {t("read-aloud")}
+            </label>
+            <br />
+            <label>
+                <input type="checkbox" checked={self.settings.pace_caret} onchange={on_pace_caret} />
+                {t("pace-caret")}
+            </label>
+            <br />
+            {self.render_ambient(ctx)}
+            {self.render_key_sounds(ctx)}
+            {t("custom-css")}<br />
+            <textarea rows="6" value={self.settings.custom_css.clone()} {onchange} />
+        </details>)
+    }
+
+    fn render_penalties(&self, ctx: &Context<Self>) -> Html {
+        let (draft, preview) = self
+            .settings_page
+            .penalty_preview
+            .as_ref()
+            .map_or((self.settings.penalties, None), |(p, stats)| {
+                (*p, Some(stats))
+            });
+        let input = |key: &'static str, value: usize, set: fn(&mut Penalties, usize)| {
+            let onchange = ctx.link().batch_callback(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                input.value().parse().ok().map(|v| {
+                    let mut penalties = draft;
+                    set(&mut penalties, v);
+                    Msg::PreviewPenalties(penalties)
+                })
+            });
+            html!(<label>
+                {t(key)}{" "}
+                <input type="number" min="0" value={value.to_string()} {onchange} />
+                <br />
+            </label>)
+        };
+        let shares = |stats: &TypingErrors| {
+            let weights = CharWeights::new(stats, &self.expected_chars, js_sys::Date::now());
+            let total: usize = self
+                .expected_chars
+                .iter()
+                .filter_map(|c| weights.get(*c))
+                .sum();
+            move |c: char| 100.0 * weights.get(c).unwrap_or(0) as f64 / total.max(1) as f64
+        };
+        let current = shares(&self.error_stats);
+        let rows = preview.map(|stats| {
+            let previewed = shares(stats);
+            self.expected_chars
+                .iter()
+                .sorted_by(|a, b| previewed(**b).total_cmp(&previewed(**a)).then(a.cmp(b)))
+                .take(PENALTY_PREVIEW_CHARS)
+                .map(|c| {
+                    html!(<tr>
+                    <td>{c}</td>
+                    <td>{format!("{}%", locale::format_number(current(*c), 1))}</td>
+                    <td>{format!("{}%", locale::format_number(previewed(*c), 1))}</td>
+                </tr>)
+                })
+                .collect::<Html>()
+        });
+        html!(<details>
+            <summary>{t("penalties")}</summary>
+            <p>{t("penalties-hint")}</p>
+            {input("penalty-error", draft.error, |p, v| p.error = v)}
+            {input("penalty-pair", draft.pair, |p, v| p.pair = v)}
+            {input("penalty-correct", draft.correct, |p, v| p.correct = v)}
+            {input("penalty-half-life", draft.half_life_days, |p, v| p.half_life_days = v)}
+            {rows.map(|rows| html!(<>
+                <table>
+                    <tr><th>{t("penalty-char")}</th><th>{t("penalty-share-now")}</th><th>{t("penalty-share-preview")}</th></tr>
+                    {rows}
+                </table>
+                <button onclick={ctx.link().callback(|_| Msg::ApplyPenalties)}>{t("penalties-apply")}</button>
+            </>))}
+            {" "}
+            <button onclick={ctx.link().callback(|_| Msg::PreviewPenalties(Penalties::default()))}>
+                {t("penalties-default")}
+            </button>
+        </details>)
+    }
+
+    fn render_aux_input(&self, ctx: &Context<Self>) -> Html {
+        let rows = AuxAction::ALL.iter().map(|&action| {
+            let buttons = self
+                .settings
+                .aux_bindings
+                .iter()
+                .filter(|b| b.action == action)
+                .map(|b| b.button.name())
+                .join(", ");
+            let bind = if self.settings_page.binding == Some(action) {
+                html!({ t("aux-press-button") })
+            } else {
+                html!(<button onclick={ctx.link().callback(move |_| Msg::StartBinding(action))}>
+                    {t("aux-bind")}
+                </button>)
+            };
+            html!(<tr>
+                <td>{t(action.message_key())}</td>
+                <td>{buttons}</td>
+                <td>
+                    {bind}{" "}
+                    <button onclick={ctx.link().callback(move |_| Msg::ClearBindings(action))}>
+                        {t("aux-clear")}
+                    </button>
+                </td>
+            </tr>)
+        });
+        html!(<details>
+            <summary>{t("aux-input")}</summary>
+            {t("aux-input-hint")}
+            <table>{for rows}</table>
+        </details>)
+    }
+
+    fn render_storage_backend(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::SetStorageBackend(Backend::ALL[select.selected_index() as usize])
+        });
+        let on_file = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::StoreFileChosen(input.files().and_then(|files| files.get(0)))
+        });
+        let backend = self.store.backend();
+        html!(<p>
+            {t("storage-backend")}{" "}
+            <select {onchange}>
+                {for Backend::ALL.iter().map(|b| html!(
+                    <option selected={*b == backend}>{t(b.message_key())}</option>
+                ))}
+            </select>
+            {(backend == Backend::File).then(|| html!(<>
+                <br />{t("backend-file-hint")}<br />
+                <button onclick={ctx.link().callback(|_| Msg::DownloadStore)}>{t("backend-file-download")}</button>
+                {" "}{t("backend-file-open")}{" "}
+                <input type="file" accept=".json,application/json" onchange={on_file} />
+            </>))}
+        </p>)
+    }
+
+    fn render_data_deletion(&self, ctx: &Context<Self>) -> Html {
+        let on_date = |to: bool| {
+            ctx.link().callback(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                let day = locale::parse_date(&input.value());
+                if to {
+                    // The end day is included
+                    Msg::SetDeletionTo(day.map(|d| d + DAY_MS))
+                } else {
+                    Msg::SetDeletionFrom(day)
+                }
+            })
+        };
+        let range = match self.settings_page.deletion_range {
+            (Some(from), Some(to)) if from < to => Some(Deletion::Range(Period { from, to })),
+            _ => None,
+        };
+        let sessions = session::sessions(&self.events);
+        let sessions = sessions
+            .iter()
+            .rev()
+            .take(DELETION_SESSIONS_LISTED)
+            .map(|s| {
+                let deletion = Deletion::Range(s.period());
+                html!(<li>
+                {locale::format_date(s.start)}{" "}
+                {t_args("deletion-session", &[
+                    ("duration", &locale::format_duration(s.end - s.start)),
+                    ("prompts", &s.prompts),
+                ])}{" "}
+                <button onclick={ctx.link().callback(move |_| Msg::PreviewDeletion(deletion))}>
+                    {t("deletion-delete")}
+                </button>
+            </li>)
+            });
+        let preview = self.settings_page.pending_deletion.as_ref().map(|(_deletion, preview)| {
+            html!(<div class="tip">
+                {t("deletion-preview")}
+                <pre>
+                    {t_args("deletion-events", &[("count", &preview.events)])}{"\n"}
+                    {t_args("deletion-keystrokes", &[("count", &preview.keystrokes)])}{"\n"}
+                    {t_args("deletion-prompts", &[("count", &preview.prompts)])}{"\n"}
+                    {t_args("deletion-results", &[("count", &preview.results)])}{"\n"}
+                    {t(if preview.keeps_stats { "deletion-stats-kept" } else { "deletion-stats-changed" })}
+                </pre>
+                <p class="flagged">{t("deletion-copies")}</p>
+                <button onclick={ctx.link().callback(|_| Msg::ApplyDeletion)}>{t("deletion-apply")}</button>
+                {" "}<button onclick={ctx.link().callback(|_| Msg::CancelDeletion)}>{t("deletion-cancel")}</button>
+            </div>)
+        });
+        html!(<details>
+            <summary>{t("deletion")}</summary>
+            {preview}
+            {t("deletion-range")}{" "}
+            <input type="date" onchange={on_date(false)} />
+            {" – "}
+            <input type="date" onchange={on_date(true)} />
+            {" "}
+            <button disabled={range.is_none()}
+                onclick={ctx.link().batch_callback(move |_| range.map(Msg::PreviewDeletion))}>
+                {t("deletion-delete")}
+            </button>
+            <br />
+            {t("deletion-sessions")}
+            <ul>{for sessions}</ul>
+            <button onclick={ctx.link().callback(|_| Msg::PreviewDeletion(Deletion::KeystrokeLogs))}>
+                {t("deletion-keystroke-logs")}
+            </button>
+        </details>)
+    }
+
+    fn render_stats_import(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::StatsFileChosen(input.files().and_then(|files| files.get(0)))
+        });
+        let preview = self.settings_page.pending_import.as_ref().map(|(_file, preview)| {
+            html!(<div class="tip">
+                {t("import-preview")}
+                <pre>
+                    {for preview.score_changes.iter().map(|(c, old, new)| format!("{c}: {old} -> {new}\n"))}
+                    {t_args("import-mistake-pairs", &[("count", &preview.mistake_pairs_changed)])}{"\n"}
+                    {t_args("import-retention", &[("count", &preview.retention_checks_added)])}{"\n"}
+                    {t_args("import-results", &[("count", &preview.results_added)])}{"\n"}
+                    {t(if preview.newer_settings { "import-settings-newer" } else { "import-settings-kept" })}
+                </pre>
+                {preview.same_device.then(|| html!(<p class="flagged">{t("import-same-device")}</p>))}
+                <button onclick={ctx.link().callback(|_| Msg::ApplyImport)}>{t("import-apply")}</button>
+                {" "}<button onclick={ctx.link().callback(|_| Msg::CancelImport)}>{t("import-cancel")}</button>
+            </div>)
+        });
+        let on_compact = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetCompactStorage(input.checked())
+        });
+        html!(<details>
+            <summary>{t("import-stats")}</summary>
+            <label>
+                <input type="checkbox" checked={self.settings.compact_storage} onchange={on_compact} />
+                {t("compact-storage")}
+            </label>
+            {self.render_storage_backend(ctx)}
+            <p>
+                {t("file-password")}{" "}
+                <input type="password" autocomplete="new-password" value={self.settings_page.file_password.clone()}
+                    onchange={ctx.link().callback(|e: Event| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        Msg::SetFilePassword(input.value())
+                    })} />
+                <br />{t("file-password-hint")}
+            </p>
+            <button
+                onclick={ctx.link().callback(|_| Msg::StartRecompute)}
+                disabled={self.recompute.is_some()}
+            >
+                {t("recompute-stats")}
+            </button>
+            {" "}{t("recompute-stats-hint")}
+            <p>
+                <button onclick={ctx.link().callback(|_| Msg::ExportStats)}>{t("export-stats")}</button>
+                {" "}{t("export-stats-hint")}
+            </p>
+            {t("import-stats-file")}{" "}
+            <input type="file" accept=".json,application/json" {onchange} />
+            {self.settings_page.import_error.as_ref().map(|e| html!(<p class="flagged">{e}</p>))}
+            {preview}
+        </details>)
+    }
+
+    pub(crate) fn render_settings_page(&self, ctx: &Context<Self>) -> Html {
+        html!(<>
+            {self.render_practice_settings(ctx)}
+            {self.render_auto_advance(ctx)}
+            {self.render_appearance(ctx)}
+            {self.render_aux_input(ctx)}
+            {self.render_penalties(ctx)}
+            {self.render_stats_import(ctx)}
+            {self.render_data_deletion(ctx)}
+            {self.render_share_code(ctx)}
+            {render_startup_report()}
+        </>)
+    }
+}
+
+fn render_startup_report() -> Html {
+    let Some(total) = startup::total() else {
+        return html!();
+    };
+    let class = if total > startup::BUDGET_MS {
+        "flagged"
+    } else {
+        ""
+    };
+    html!(<details>
+        <summary class={class}>
+            {t_args("startup-report", &[
+                ("total", &locale::format_duration(total)),
+                ("budget", &locale::format_duration(startup::BUDGET_MS)),
+            ])}
+        </summary>
+        <pre>{for startup::marks().into_iter().map(|(name, at)| {
+            format!("{} {}\n", t(name), locale::format_duration(at))
+        })}</pre>
+    </details>)
+}
@@ -0,0 +1,796 @@
+//! The statistics page: error scores, speed over time, metrics of the
+//! session and comparisons of periods, filtered by the tags of results.
+
+use std::{cmp::Reverse, collections::BTreeSet, rc::Rc};
+
+use itertools::Itertools;
+use typing_core::{review, DAY_MS};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::{
+    analytics::SessionAnalytics,
+    bigrams::{self, BigramExplorer},
+    charset::{Script, SCRIPT_TAG_PREFIX},
+    comparison,
+    fingers::FingerMap,
+    hesitation,
+    history::{HistoryQuery, WeeklySpeed},
+    i18n::{t, t_args},
+    keyboards, locale, metrics, numpad,
+    session::{self, Period},
+    units::SpeedUnit,
+    Msg, Practice, RECENT_RESULTS,
+};
+
+/// Most recent keystrokes shown with their hesitations
+const REPLAY_KEYSTROKES: usize = 1000;
+/// Most recent sessions offered for comparison
+const COMPARISON_SESSIONS_LISTED: usize = 20;
+/// Words listed as the ones fumbled most often this session
+const FUMBLED_WORDS_LISTED: usize = 20;
+/// Size of the weekly speed chart in its own units
+const SPEED_CHART_WIDTH: f64 = 300.0;
+const SPEED_CHART_HEIGHT: f64 = 80.0;
+/// Keystrokes a character class needs before it can be suggested for practice
+const CLASS_MIN_KEYSTROKES: usize = 20;
+
+/// Periods compared and tags filtered by, kept while other pages are open
+pub(crate) struct StatsPage {
+    /// Start and end of the two periods compared
+    pub(crate) comparison: [(Option<f64>, Option<f64>); 2],
+    /// History views show results having all of these tags
+    pub(crate) tag_filter: BTreeSet<String>,
+}
+
+impl Practice {
+    fn render_error_stats(&self) -> Html {
+        self.error_stats
+            .error_stats
+            .iter()
+            .sorted_by_key(|(_k, v)| Reverse(*v))
+            .map(|(k, v)| {
+                let count = v.div_ceil(self.error_stats.penalties.pair.max(1));
+                let count = locale::format_number(count as f64, 0);
+                format!("{k} ({count})\n")
+            })
+            .collect()
+    }
+
+    fn render_keyboard_breakdown(&self) -> Html {
+        let keyboards = self
+            .settings
+            .keyboards
+            .iter()
+            .map(|k| (Some(k.id), k.name.clone()))
+            .chain([(None, t("keyboard-unspecified"))]);
+        let rows = keyboards.filter_map(|(id, name)| {
+            let stats = keyboards::stats_for(self.history.query(&self.history_query()), id);
+            (stats.results > 0).then(|| {
+                html!(<tr>
+                <td>{name}</td>
+                <td>{stats.results}</td>
+                <td>{self.format_speed(&stats.sample)}</td>
+                <td>{locale::format_number(stats.accuracy * 100.0, 1)}{"%"}</td>
+            </tr>)
+            })
+        });
+        html!(<>
+            {t("keyboard-breakdown")}
+            <table>
+                <tr><th>{t("keyboard")}</th><th>{t("results")}</th><th>{t("speed")}</th><th>{t("accuracy")}</th></tr>
+                {for rows}
+            </table>
+        </>)
+    }
+
+    /// Why characters of the current prompt were picked more often than others
+    fn render_prompt_weights(&self) -> Html {
+        let weights = self.char_weights();
+        let due = review::due_keys(&self.error_stats, js_sys::Date::now());
+        let latency = |c: char| self.error_stats.latency.get(&c).copied();
+        let rows = self
+            .prompt
+            .chars()
+            .unique()
+            .filter_map(|c| Some((c, weights.get(c)?)))
+            // A weight of 1 is what a character without errors gets
+            .filter(|(c, weight)| *weight > 1 || due.contains(c))
+            .sorted_by_key(|(c, weight)| (Reverse(*weight), *c))
+            .map(|(c, weight)| {
+                html!(<tr>
+                    <td>{c}</td>
+                    <td>{format!("×{weight}")}</td>
+                    <td>{self.error_stats.error_score.get(&c).copied().unwrap_or(0)}</td>
+                    <td>{latency(c).map(|l| locale::format_number(l, 0))}</td>
+                    <td>{due.contains(&c).then(|| t("weights-due"))}</td>
+                </tr>)
+            })
+            .collect::<Vec<_>>();
+        html!(<>
+            {t("weights-title")}
+            <p>{t(if self.retention_check { "weights-retention-hint" } else { "weights-hint" })}</p>
+            {if rows.is_empty() {
+                html!(<p>{t("weights-none")}</p>)
+            } else {
+                html!(<table>
+                    <tr>
+                        <th>{t("weights-char")}</th>
+                        <th>{t("weights-weight")}</th>
+                        <th>{t("weights-error-score")}</th>
+                        <th>{t("latency-ms")}</th>
+                        <th>{t("weights-review")}</th>
+                    </tr>
+                    {for rows}
+                </table>)
+            }}
+            {(!weights.ngrams().is_empty()).then(|| {
+                let ngrams = weights.ngrams().iter().map(|(ngram, _)| ngram).join(" ");
+                html!(<p>{t_args("weights-ngrams", &[("ngrams", &ngrams)])}</p>)
+            })}
+        </>)
+    }
+
+    fn render_metrics(&self) -> Html {
+        let input = metrics::MetricInput {
+            log: &self.log,
+            backspaces: self.backspaces,
+            settings: &self.settings,
+        };
+        let rows = metrics::compute_all(&input)
+            .into_iter()
+            .map(|(metric, value)| {
+                html!(<tr key={metric.id()}>
+                <td>{t(metric.name_key())}</td>
+                <td>{value.map(|v| metric.unit(self.settings.speed_unit).format(v))}</td>
+            </tr>)
+            });
+        html!(<>
+            {t("session-metrics")}
+            <table>{for rows}</table>
+        </>)
+    }
+
+    fn render_finger_breakdown(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::SetFingerMap(FingerMap::ALL[select.selected_index() as usize])
+        });
+        let analytics = SessionAnalytics::compute(&self.log, self.backspaces, &self.settings);
+        let rows = analytics.per_finger.iter().map(|(finger, stats)| {
+            html!(<tr>
+            <td>{t(&finger.message_key())}</td>
+            <td>{stats.total}</td>
+            <td>{locale::format_number(100.0 * stats.accuracy(), 1)}{"%"}</td>
+            <td>{stats.mean_latency().map(|l| locale::format_number(l, 0))}</td>
+        </tr>)
+        });
+        html!(<>
+            {t("finger-breakdown")}{" "}
+            <select {onchange}>
+                {for FingerMap::ALL.iter().map(|m| html!(
+                    <option selected={*m == self.settings.finger_map}>{t(m.message_key())}</option>
+                ))}
+            </select>
+            <table>
+                <tr><th>{t("finger")}</th><th>{t("keystrokes")}</th><th>{t("accuracy")}</th><th>{t("latency-ms")}</th></tr>
+                {for rows}
+            </table>
+            {self.render_layer_breakdown(ctx, &analytics)}
+        </>)
+    }
+
+    fn render_layer_breakdown(&self, ctx: &Context<Self>, analytics: &SessionAnalytics) -> Html {
+        let on_chars = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetLayerChars(input.value())
+        });
+        let on_shift = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetHomeRowShift(input.checked())
+        });
+        let layers_used = !self.settings.layer_chars.is_empty() || self.settings.home_row_shift;
+        let rows = analytics
+            .per_layer_transition
+            .iter()
+            .map(|(transition, stats)| {
+                html!(<tr>
+            <td>{t(transition.message_key())}</td>
+            <td>{stats.total}</td>
+            <td>{locale::format_number(100.0 * stats.accuracy(), 1)}{"%"}</td>
+            <td>{stats.mean_latency().map(|l| locale::format_number(l, 0))}</td>
+        </tr>)
+            });
+        html!(<>
+            {t("layer-chars")}{" "}
+            <input type="text" onchange={on_chars}
+                value={self.settings.layer_chars.iter().collect::<String>()} />
+            {" "}
+            <label>
+                <input type="checkbox" checked={self.settings.home_row_shift} onchange={on_shift} />
+                {t("home-row-shift")}
+            </label>
+            {layers_used.then(|| html!(<table>
+                <tr><th>{t("layer-transition")}</th><th>{t("keystrokes")}</th><th>{t("accuracy")}</th><th>{t("latency-ms")}</th></tr>
+                {for rows}
+            </table>))}
+        </>)
+    }
+
+    /// Accuracy per character class as bars of correct and mistyped shares
+    fn render_class_breakdown(&self) -> Html {
+        let analytics = SessionAnalytics::compute(&self.log, self.backspaces, &self.settings);
+        let rows = analytics.per_class.iter().map(|(class, stats)| {
+            let correct = 100.0 * stats.accuracy();
+            html!(<tr>
+            <td>{t(class.message_key())}</td>
+            <td>{stats.total}</td>
+            <td>{locale::format_number(correct, 1)}{"%"}</td>
+            <td class="class-bar">
+                <span class="correct" style={format!("width: {correct}%")} />
+                <span class="incorrect" style={format!("width: {}%", 100.0 - correct)} />
+            </td>
+        </tr>)
+        });
+        let weakest = analytics
+            .per_class
+            .iter()
+            .filter(|(_class, stats)| stats.total >= CLASS_MIN_KEYSTROKES)
+            .min_by(|(_, a), (_, b)| a.accuracy().total_cmp(&b.accuracy()))
+            .filter(|(_class, stats)| stats.mistakes > 0);
+        html!(<>
+            {t("class-breakdown")}
+            <table>
+                <tr><th>{t("class")}</th><th>{t("keystrokes")}</th><th>{t("accuracy")}</th><th /></tr>
+                {for rows}
+            </table>
+            {weakest.map(|(class, _stats)| html!(<p>
+                {t_args("class-weakest", &[("class", &t(class.message_key()))])}
+            </p>))}
+        </>)
+    }
+
+    fn render_comparison_side(
+        &self,
+        ctx: &Context<Self>,
+        side: usize,
+        sessions: &[Period],
+    ) -> Html {
+        let (from, to) = self.stats_page.comparison[side];
+        let on_session = {
+            let sessions = sessions.to_vec();
+            ctx.link().callback(move |e: Event| {
+                let select: HtmlSelectElement = e.target_unchecked_into();
+                // The first option is a range of days
+                let session = (select.selected_index() as usize)
+                    .checked_sub(1)
+                    .and_then(|i| sessions.get(i));
+                Msg::SetComparisonPeriod(
+                    side,
+                    session.map_or((None, None), |p| (Some(p.from), Some(p.to))),
+                )
+            })
+        };
+        let on_from = ctx.link().callback(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetComparisonPeriod(side, (locale::parse_date(&input.value()), to))
+        });
+        let on_to = ctx.link().callback(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            // The end day is included
+            Msg::SetComparisonPeriod(
+                side,
+                (from, locale::parse_date(&input.value()).map(|d| d + DAY_MS)),
+            )
+        });
+        html!(<p>
+            {t(if side == 0 { "comparison-before" } else { "comparison-after" })}{" "}
+            <select onchange={on_session}>
+                <option>{t("comparison-days")}</option>
+                {for sessions.iter().map(|p| html!(
+                    <option selected={(Some(p.from), Some(p.to)) == (from, to)}>{locale::format_date(p.from)}</option>
+                ))}
+            </select>
+            {" "}
+            <input type="date" onchange={on_from} />
+            {" – "}
+            <input type="date" onchange={on_to} />
+        </p>)
+    }
+
+    /// Metrics and per-key accuracy and latency of two periods side by side
+    fn render_comparison(&self, ctx: &Context<Self>) -> Html {
+        let sessions: Vec<Period> = session::sessions(&self.events)
+            .iter()
+            .rev()
+            .take(COMPARISON_SESSIONS_LISTED)
+            .map(|s| s.period())
+            .collect();
+        let summaries = self.stats_page.comparison.map(|period| match period {
+            (Some(from), Some(to)) if from < to => Some(comparison::summarize(
+                &self.events,
+                Period { from, to },
+                &self.settings,
+            )),
+            _ => None,
+        });
+        let delta = |unit: metrics::MetricUnit, a: Option<f64>, b: Option<f64>| {
+            let d = b? - a?;
+            Some(format!(
+                "{}{}",
+                if d >= 0.0 { "+" } else { "" },
+                unit.format(d)
+            ))
+        };
+        let tables = match &summaries {
+            [Some(a), Some(b)] => {
+                let metric_rows =
+                    a.metrics
+                        .iter()
+                        .zip(&b.metrics)
+                        .map(|((metric, va), (_, vb))| {
+                            let unit = metric.unit(self.settings.speed_unit);
+                            html!(<tr>
+                        <td>{t(metric.name_key())}</td>
+                        <td>{va.map(|v| unit.format(v))}</td>
+                        <td>{vb.map(|v| unit.format(v))}</td>
+                        <td>{delta(unit, *va, *vb)}</td>
+                    </tr>)
+                        });
+                let key_rows = a.per_key.iter().filter_map(|(c, ka)| {
+                    let kb = b.per_key.get(c)?;
+                    let (acc_a, acc_b) = (100.0 * ka.accuracy(), 100.0 * kb.accuracy());
+                    let percent = metrics::MetricUnit::Percent;
+                    let millis = metrics::MetricUnit::Millis;
+                    Some(html!(<tr>
+                        <td><code>{c.to_string().replace(' ', "␣")}</code></td>
+                        <td>{percent.format(acc_a)}</td>
+                        <td>{percent.format(acc_b)}</td>
+                        <td>{delta(percent, Some(acc_a), Some(acc_b))}</td>
+                        <td>{ka.mean_latency().map(|l| millis.format(l))}</td>
+                        <td>{kb.mean_latency().map(|l| millis.format(l))}</td>
+                        <td>{delta(millis, ka.mean_latency(), kb.mean_latency())}</td>
+                    </tr>))
+                });
+                html!(<>
+                    <table>
+                        <tr><th /><th>{t("comparison-before")}</th><th>{t("comparison-after")}</th><th>{"Δ"}</th></tr>
+                        <tr><td>{t("keystrokes")}</td><td>{a.keystrokes}</td><td>{b.keystrokes}</td><td /></tr>
+                        {for metric_rows}
+                    </table>
+                    <table>
+                        <tr>
+                            <th>{t("comparison-key")}</th>
+                            <th colspan="3">{t("accuracy")}</th>
+                            <th colspan="3">{t("latency-ms")}</th>
+                        </tr>
+                        {for key_rows}
+                    </table>
+                </>)
+            }
+            _ => html!(),
+        };
+        html!(<details open={summaries.iter().all(Option::is_some)}>
+            <summary>{t("comparison")}</summary>
+            {self.render_comparison_side(ctx, 0, &sessions)}
+            {self.render_comparison_side(ctx, 1, &sessions)}
+            {tables}
+        </details>)
+    }
+
+    fn render_rhythm(&self, ctx: &Context<Self>) -> Html {
+        let button = match self.rhythm {
+            Some(_) => {
+                html!(<button onclick={ctx.link().callback(|_| Msg::StopRhythm)}>{t("rhythm-stop")}</button>)
+            }
+            None => {
+                html!(<button onclick={ctx.link().callback(|_| Msg::PlayRhythm)}>{t("rhythm-play")}</button>)
+            }
+        };
+        html!(<p>{button}{" "}{t("rhythm-hint")}</p>)
+    }
+
+    /// Keystrokes of the session with the long hesitations flagged,
+    /// and the longest of them in context
+    fn render_hesitations(&self, ctx: &Context<Self>) -> Html {
+        let percentile = self.settings.hesitation_percentile;
+        let Some(threshold) = hesitation::threshold(&self.log, percentile) else {
+            return html!();
+        };
+        let on_percentile = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input
+                .value()
+                .parse()
+                .ok()
+                .filter(|p| (0.0..=100.0).contains(p))
+                .map(Msg::SetHesitationPercentile)
+        });
+        let start = self.log.len().saturating_sub(REPLAY_KEYSTROKES);
+        let keystrokes = self.log[start..].iter().enumerate().map(|(i, k)| {
+            let class = classes!(
+                hesitation::is_hesitation(k, threshold).then_some("hesitation"),
+                (k.typed != k.expected).then_some("incorrect"),
+            );
+            let title = k
+                .latency
+                .map(|ms| format!("{} ms", locale::format_number(ms, 0)));
+            // Each prompt on a line of its own
+            html!(<>
+                {(i > 0 && k.latency.is_none()).then(|| html!(<br />))}
+                <span {class} {title}>{k.expected}</span>
+            </>)
+        });
+        let listed = hesitation::hesitations(&self.log, threshold)
+            .into_iter()
+            .map(|h| {
+                html!(<li>
+                <code>{h.before}<span class="hesitation">{h.key}</span>{h.after}</code>
+                {" "}{locale::format_number(h.latency, 0)}{" ms"}
+            </li>)
+            });
+        html!(<details>
+            <summary>{t("hesitations")}</summary>
+            {t("hesitations-percentile")}{" "}
+            <input type="number" min="0" max="100" step="1" onchange={on_percentile}
+                value={percentile.to_string()} />
+            {" "}{t_args("hesitations-threshold", &[("ms", &locale::format_number(threshold, 0))])}
+            <p class="replay">{for keystrokes}</p>
+            <ul>{for listed}</ul>
+        </details>)
+    }
+
+    pub(crate) fn render_stats(&self, ctx: &Context<Self>) -> Html {
+        html!(<>
+            <br />
+            {self.render_regression(ctx)}
+            {self.render_prompt_weights()}
+            {self.render_tag_filter(ctx)}
+            {self.render_personal_best(ctx)}
+            {(!self.history.results.is_empty()).then(|| html!(<>
+                {t("recent-results")}
+                {self.render_recent_results(ctx)}
+                {self.render_speed_chart()}
+                <p>
+                    <button onclick={ctx.link().callback(|_| Msg::ExportReport)}>{t("export-report")}</button>
+                    <br />{t("export-report-hint")}
+                </p>
+            </>))}
+            {self.render_fumbled_words()}
+            {self.render_metrics()}
+            {(!self.settings.keyboards.is_empty()).then(|| self.render_keyboard_breakdown())}
+            {self.render_finger_breakdown(ctx)}
+            {self.render_error_heatmap()}
+            {(!self.numpad_stats.keys.is_empty()).then(|| self.render_numpad_stats())}
+            {(!self.number_row_stats.keys.is_empty()).then(|| self.render_number_row_stats())}
+            {(!self.log.is_empty()).then(|| self.render_class_breakdown())}
+            {(!self.log.is_empty()).then(|| self.render_rhythm(ctx))}
+            {(!self.log.is_empty()).then(|| self.render_hesitations(ctx))}
+            {self.render_comparison(ctx)}
+            <BigramExplorer
+                bigrams={Rc::new(bigrams::collect(&self.events))}
+                on_drill={ctx.link().callback(Msg::StartDrill)}
+            />
+            {t("total-error-score")} {" "}
+            {locale::format_number(self.error_stats.error_score.values().copied().sum::<usize>() as f64, 0)} <br />
+            {t("error-stats")}
+            <pre>{self.render_error_stats()}</pre>
+            {(!self.error_stats.shift_errors.is_empty()).then(|| html!(<>
+                {t_args("shift-errors", &[("count", &self.error_stats.shift_error_count())])}
+                <pre>{self.render_shift_errors()}</pre>
+            </>))}
+
+            {(!self.error_stats.retention.is_empty()).then(|| html!(<>
+                {t("retention-checks")}
+                <pre>{self.render_retention()}</pre>
+            </>))}
+        </>)
+    }
+
+    fn render_shift_errors(&self) -> String {
+        self.error_stats
+            .shift_errors
+            .iter()
+            .sorted_by_key(|(c, n)| (Reverse(**n), **c))
+            .map(|(c, n)| format!("{c} ({n})\n"))
+            .collect()
+    }
+
+    fn render_retention(&self) -> String {
+        self.error_stats
+            .retention
+            .iter()
+            .sorted_by_key(|(c, _r)| **c)
+            .map(|(c, r)| format!("{c} {}/{}\n", r.correct, r.total))
+            .collect()
+    }
+
+    fn render_personal_best(&self, ctx: &Context<Self>) -> Html {
+        let onchange = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            // An empty field counts every prompt
+            Msg::SetMinAccuracy(input.value().parse().ok())
+        });
+        let best = self
+            .history
+            .personal_best(&self.history_query(), self.settings.speed_unit);
+        html!(<p>
+            {best.map(|r| html!(<>
+                {t("personal-best")}{" "}{self.format_speed(&r.speed_sample())}
+                {" ("}{locale::format_date(r.meta.created_at)}{")"}<br />
+            </>))}
+            {t("min-accuracy")}{" "}
+            <input type="number" min="0" max="100" {onchange} placeholder={t("min-accuracy-off")}
+                value={self.settings.min_accuracy.map(|n| n.to_string()).unwrap_or_default()} />
+            {"%"}
+        </p>)
+    }
+
+    fn render_tag_filter(&self, ctx: &Context<Self>) -> Html {
+        let on_session_tags = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::SetSessionTags(input.value())
+        });
+        let tags = self.history.known_tags(&self.settings.keyboards);
+        html!(<p>
+            {t("session-tags")}{" "}
+            <input type="text" placeholder={t("session-tags-placeholder")} onchange={on_session_tags}
+                value={self.session_tags.iter().join(", ")} />
+            {(!tags.is_empty()).then(|| html!(<>
+                <br />{t("tag-filter")}
+                {for tags.into_iter().map(|tag| {
+                    let checked = self.stats_page.tag_filter.contains(&tag);
+                    let toggle = tag.clone();
+                    html!(<label>
+                        {" "}
+                        <input type="checkbox" {checked}
+                            onchange={ctx.link().callback(move |_| Msg::ToggleTagFilter(toggle.clone()))} />
+                        {tag}
+                    </label>)
+                })}
+            </>))}
+        </p>)
+    }
+
+    fn render_recent_results(&self, ctx: &Context<Self>) -> Html {
+        let query = self.history_query();
+        let rows = self.history.query(&query).rev().take(RECENT_RESULTS).map(|r| {
+            let id = r.meta.id;
+            let onchange = ctx.link().callback(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                Msg::SetNote(id, input.value())
+            });
+            html!(<tr>
+                <td>{locale::format_date(r.meta.created_at)}</td>
+                <td>{self.format_speed(&r.speed_sample())}</td>
+                <td>
+                    {r.mistakes}
+                    {r.sloppy.then(|| html!(<>{" "}<span class="flagged">{t("sloppy")}</span></>))}
+                </td>
+                <td><input type="text" value={r.note.clone()} placeholder={t("note-placeholder")} {onchange} /></td>
+            </tr>)
+        });
+        html!(<table>
+            <tr><th>{t("result-date")}</th><th>{t("speed")}</th><th>{t("result-mistakes")}</th><th>{t("result-note")}</th></tr>
+            {for rows}
+        </table>)
+    }
+
+    /// Words of this session that had mistakes most often
+    fn render_fumbled_words(&self) -> Html {
+        let rows = self
+            .word_stats
+            .iter()
+            .filter(|(_word, s)| s.fumbled > 0)
+            .sorted_by_key(|(_word, s)| (Reverse(s.fumbled), Reverse(s.typed)))
+            .take(FUMBLED_WORDS_LISTED)
+            .map(|(word, s)| {
+                let count = t_args(
+                    "word-fumbled-count",
+                    &[("fumbled", &s.fumbled), ("typed", &s.typed)],
+                );
+                html!(<tr><td><code>{word}</code></td><td>{count}</td></tr>)
+            })
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return html!();
+        }
+        html!(<>
+            {t("session-words-fumbled")}
+            <table>{for rows}</table>
+        </>)
+    }
+
+    /// Weekly speed of the filtered results, one series per script when
+    /// they were typed in several and the filter does not pick one
+    pub(crate) fn speed_chart_series(&self, unit: SpeedUnit) -> Vec<(Option<Script>, WeeklySpeed)> {
+        let query = self.history_query();
+        let picked = self
+            .stats_page
+            .tag_filter
+            .iter()
+            .any(|t| t.starts_with(SCRIPT_TAG_PREFIX));
+        let known = self.history.known_tags(&self.settings.keyboards);
+        let scripts: Vec<Script> = Script::ALL
+            .into_iter()
+            .filter(|s| !picked && known.contains(s.tag()))
+            .collect();
+        if scripts.len() < 2 {
+            return vec![(None, self.history.weekly_speed(&query, unit))];
+        }
+        scripts
+            .into_iter()
+            .map(|script| {
+                let mut tags = self.stats_page.tag_filter.clone();
+                tags.insert(script.tag().to_string());
+                let query = HistoryQuery {
+                    tags: &tags,
+                    ..query
+                };
+                (Some(script), self.history.weekly_speed(&query, unit))
+            })
+            .filter(|(_script, weeks)| !weeks.is_empty())
+            .collect()
+    }
+
+    /// Weekly speed as lines, to see whether it goes up
+    fn render_speed_chart(&self) -> Html {
+        let unit = self.settings.speed_unit;
+        let series = self.speed_chart_series(unit);
+        let weeks: Vec<(f64, f64)> = series.iter().flat_map(|(_s, w)| w).copied().collect();
+        if weeks.is_empty() {
+            return html!();
+        }
+        let bounds = |values: &mut dyn Iterator<Item = f64>| {
+            values.fold((f64::MAX, f64::MIN), |(min, max), v| {
+                (min.min(v), max.max(v))
+            })
+        };
+        let (first, last) = bounds(&mut weeks.iter().map(|(at, _)| *at));
+        let (min, max) = bounds(&mut weeks.iter().map(|(_, v)| *v));
+        let (width, height) = (SPEED_CHART_WIDTH, SPEED_CHART_HEIGHT);
+        // A single week or a flat line is drawn across the middle
+        let x = |at: f64| match last - first {
+            span if span > 0.0 => (at - first) / span * width,
+            _ => width / 2.0,
+        };
+        let y = |v: f64| match max - min {
+            span if span > 0.0 => height - (v - min) / span * height,
+            _ => height / 2.0,
+        };
+        let lines = series.iter().enumerate().map(|(i, (script, weeks))| {
+            let points = weeks
+                .iter()
+                .map(|(at, v)| format!("{:.1},{:.1}", x(*at), y(*v)))
+                .join(" ");
+            let name = script.map(|s| format!("{}, ", t(s.message_key())));
+            let dots = weeks.iter().map(|(at, v)| {
+                let title = format!(
+                    "{}{}: {}",
+                    name.clone().unwrap_or_default(),
+                    locale::format_date(*at),
+                    locale::format_number(*v, 1)
+                );
+                html!(<circle cx={x(*at).to_string()} cy={y(*v).to_string()} r="2">
+                    <title>{title}</title>
+                </circle>)
+            });
+            html!(<g class={format!("series-{i}")}>
+                <polyline {points} />
+                {for dots}
+            </g>)
+        });
+        let legend = series.iter().enumerate().filter_map(|(i, (script, _))| {
+            let script = (*script)?;
+            Some(html!(<>
+                <svg class={classes!("speed-chart-key", format!("series-{i}"))} viewBox="0 0 20 4">
+                    <polyline points="0,2 20,2" />
+                </svg>
+                {" "}{t(script.message_key())}{" "}
+            </>))
+        });
+        let week_count = weeks.iter().map(|(at, _)| *at as i64).unique().count();
+        html!(<p>
+            {t_args("speed-chart", &[("unit", &t(unit.message_key()))])}
+            <br />
+            <svg class="speed-chart" viewBox={format!("-4 -4 {} {}", width + 8.0, height + 8.0)}>
+                {for lines}
+            </svg>
+            <br />
+            {for legend}
+            {t_args("speed-chart-range", &[
+                ("min", &locale::format_number(min, 1)),
+                ("max", &locale::format_number(max, 1)),
+                ("weeks", &week_count),
+            ])}
+        </p>)
+    }
+
+    /// Alarm about a recent drop of accuracy, until dismissed for the session
+    pub(crate) fn render_regression(&self, ctx: &Context<Self>) -> Html {
+        let Some(regression) = self.regression.filter(|_| !self.regression_dismissed) else {
+            return html!();
+        };
+        html!(
+            <div class="tip">
+                <span class="flagged">{t_args("regression", &[
+                    ("before", &locale::format_number(regression.before, 1)),
+                    ("after", &locale::format_number(regression.after, 1)),
+                    ("date", &locale::format_date(regression.since)),
+                ])}</span>{" "}
+                <button onclick={ctx.link().callback(|_| Msg::CompareRegression)}>{t("regression-compare")}</button>
+                {" "}
+                <button onclick={ctx.link().callback(|_| Msg::DismissRegression)}>{t("tip-dismiss")}</button>
+            </div>
+        )
+    }
+
+    /// Error heatmap, fingers and speed of the keys of the keypad, for the stats page
+    fn render_numpad_stats(&self) -> Html {
+        let rows = numpad::KEYS.iter().filter_map(|key| {
+            let stats = self.numpad_stats.get(key.char?);
+            let latency = stats
+                .mean_latency()
+                .map_or_else(String::new, |ms| format!("{ms:.0}"));
+            Some(html!(<tr>
+                <td>{key.label}</td>
+                <td>{t(&key.finger.message_key())}</td>
+                <td>{stats.presses}</td>
+                <td>{format!("{:.1}", 100.0 * (1.0 - stats.error_rate()))}</td>
+                <td>{latency}</td>
+            </tr>))
+        });
+        html!(<details>
+            <summary>{t("numpad")}</summary>
+            <p>{t("numpad-hint")}</p>
+            {self.render_numpad_heatmap(None)}
+            <table>
+                <tr>
+                    <th>{t("numpad-col-key")}</th>
+                    <th>{t("numpad-col-finger")}</th>
+                    <th>{t("numpad-col-presses")}</th>
+                    <th>{t("numpad-col-accuracy")}</th>
+                    <th>{t("numpad-col-latency")}</th>
+                </tr>
+                {for rows}
+            </table>
+        </details>)
+    }
+
+    /// Accuracy and speed of the keys of number drills typed on the number row
+    fn render_number_row_stats(&self) -> Html {
+        let rows = self.number_row_stats.keys.iter().map(|(c, stats)| {
+            let latency = stats
+                .mean_latency()
+                .map_or_else(String::new, |ms| format!("{ms:.0}"));
+            html!(<tr>
+                <td>{c}</td>
+                <td>{stats.presses}</td>
+                <td>{format!("{:.1}", 100.0 * (1.0 - stats.error_rate()))}</td>
+                <td>{latency}</td>
+            </tr>)
+        });
+        html!(<details>
+            <summary>{t("numbers-row-stats")}</summary>
+            <p>{t("numbers-row-stats-hint")}</p>
+            <table>
+                <tr>
+                    <th>{t("numpad-col-key")}</th>
+                    <th>{t("numpad-col-presses")}</th>
+                    <th>{t("numpad-col-accuracy")}</th>
+                    <th>{t("numpad-col-latency")}</th>
+                </tr>
+                {for rows}
+            </table>
+        </details>)
+    }
+
+    /// Where on the board the error scores are, for the stats page
+    fn render_error_heatmap(&self) -> Html {
+        html!(<details>
+            <summary>{t("heatmap")}</summary>
+            <p>{t("heatmap-hint")}</p>
+            {self.render_error_keyboard(None)}
+        </details>)
+    }
+}
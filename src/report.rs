@@ -0,0 +1,353 @@
+//! Progress as a standalone HTML page to save or print: the weekly speed and
+//! daily accuracy charts as inline SVG, with tables of the same numbers and
+//! the weakest characters. Nothing is loaded from elsewhere when it is opened.
+
+use std::{cmp::Reverse, collections::HashMap, fmt::Write};
+
+use itertools::Itertools;
+
+use crate::{
+    analytics::DailyAccuracy,
+    charset::Script,
+    history::{self, PromptResult, WeeklySpeed},
+    i18n::{t, t_args},
+    locale,
+    units::SpeedUnit,
+};
+
+pub const REPORT_FILE_NAME: &str = "typing-tutor-report.html";
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 200.0;
+/// Characters with the highest error scores listed
+const WEAKEST_CHARS_LISTED: usize = 20;
+/// Line colors of the speed series, repeated if there are more
+const SERIES_COLORS: [&str; 4] = ["#2a6fdb", "#d9534f", "#3c9a5f", "#8e5cc4"];
+const STYLE: &str =
+    "body { font-family: sans-serif; max-width: 50em; margin: 2em auto; color: #222; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: right; }
+th { background: #f2f2f2; }
+svg { width: 100%; height: auto; border: 1px solid #eee; }
+polyline { fill: none; stroke-width: 2; }
+h2 { page-break-after: avoid; }
+table, svg { page-break-inside: avoid; }";
+
+/// What the report is made of, as shown on the stats page
+pub struct Report<'a> {
+    /// `lang` of the page
+    pub language: &'a str,
+    pub results: &'a [PromptResult],
+    pub unit: SpeedUnit,
+    pub personal_best: Option<&'a PromptResult>,
+    /// One series per script, or a single one with no script
+    pub weekly_speed: Vec<(Option<Script>, WeeklySpeed)>,
+    pub daily_accuracy: Vec<DailyAccuracy>,
+    pub error_scores: &'a HashMap<char, usize>,
+}
+
+/// Smallest and largest coordinates of the points of a chart
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Bounds {
+    first: f64,
+    last: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Bounds {
+    fn of(points: impl Iterator<Item = (f64, f64)>) -> Option<Bounds> {
+        points.fold(None, |bounds, (x, y)| {
+            Some(match bounds {
+                None => Bounds {
+                    first: x,
+                    last: x,
+                    min: y,
+                    max: y,
+                },
+                Some(b) => Bounds {
+                    first: b.first.min(x),
+                    last: b.last.max(x),
+                    min: b.min.min(y),
+                    max: b.max.max(y),
+                },
+            })
+        })
+    }
+
+    /// Position in the chart, y growing down. A single point in time or
+    /// a flat line is drawn across the middle.
+    fn place(&self, x: f64, y: f64) -> (f64, f64) {
+        let x = match self.last - self.first {
+            span if span > 0.0 => (x - self.first) / span * CHART_WIDTH,
+            _ => CHART_WIDTH / 2.0,
+        };
+        let y = match self.max - self.min {
+            span if span > 0.0 => CHART_HEIGHT - (y - self.min) / span * CHART_HEIGHT,
+            _ => CHART_HEIGHT / 2.0,
+        };
+        (x, y)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Lines of `series` with a dot and a tooltip at each point
+fn line_chart(series: &[(String, Vec<(f64, f64)>)], bounds: Bounds) -> String {
+    let mut svg = format!(
+        r#"<svg viewBox="-4 -4 {} {}" xmlns="http://www.w3.org/2000/svg">"#,
+        CHART_WIDTH + 8.0,
+        CHART_HEIGHT + 8.0
+    );
+    for (i, (name, points)) in series.iter().enumerate() {
+        let color = SERIES_COLORS[i % SERIES_COLORS.len()];
+        let placed = points.iter().map(|(x, y)| (bounds.place(*x, *y), *x, *y));
+        let line = placed
+            .clone()
+            .map(|((x, y), ..)| format!("{x:.1},{y:.1}"))
+            .join(" ");
+        let _ = write!(svg, r#"<polyline points="{line}" stroke="{color}" />"#);
+        for ((x, y), at, value) in placed {
+            let _ = write!(
+                svg,
+                r#"<circle cx="{x:.1}" cy="{y:.1}" r="2" fill="{color}"><title>{}{}: {}</title></circle>"#,
+                escape(name),
+                escape(&locale::format_date(at)),
+                locale::format_number(value, 1)
+            );
+        }
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn table(header: &[String], rows: impl Iterator<Item = Vec<String>>) -> String {
+    let cells = |tag: &str, row: &[String]| {
+        row.iter()
+            .map(|cell| format!("<{tag}>{}</{tag}>", escape(cell)))
+            .collect::<String>()
+    };
+    let mut html = String::from("<table>");
+    if !header.is_empty() {
+        let _ = write!(html, "<tr>{}</tr>", cells("th", header));
+    }
+    for row in rows {
+        let _ = write!(html, "<tr>{}</tr>", cells("td", &row));
+    }
+    html.push_str("</table>");
+    html
+}
+
+impl Report<'_> {
+    /// The page as of `now`
+    pub fn render(&self, now: f64) -> String {
+        let title = escape(&t("report-title"));
+        let mut html = format!(
+            r#"<!DOCTYPE html><html lang="{}"><head><meta charset="utf-8"><title>{title}</title><style>{STYLE}</style></head><body><h1>{title}</h1><p>{}</p>"#,
+            self.language,
+            escape(&t_args(
+                "report-generated",
+                &[("date", &locale::format_date(now))]
+            )),
+        );
+        html.push_str(&self.render_summary());
+        html.push_str(&self.render_speed());
+        html.push_str(&self.render_accuracy());
+        html.push_str(&self.render_weakest_chars());
+        html.push_str("</body></html>");
+        html
+    }
+
+    fn format_speed(&self, rate: f64) -> String {
+        format!(
+            "{} {}",
+            locale::format_number(rate, 1),
+            t(self.unit.message_key())
+        )
+    }
+
+    fn render_summary(&self) -> String {
+        let keystrokes: usize = self.results.iter().map(|r| r.keystrokes).sum();
+        let mistakes: usize = self.results.iter().map(|r| r.mistakes).sum();
+        let hours = self.results.iter().map(|r| r.millis).sum::<f64>() / 3_600_000.0;
+        let mut rows = vec![
+            vec![t("report-results"), self.results.len().to_string()],
+            vec![
+                t("report-practice-time"),
+                t_args(
+                    "report-hours",
+                    &[("hours", &locale::format_number(hours, 1))],
+                ),
+            ],
+            vec![t("keystrokes"), keystrokes.to_string()],
+            vec![
+                t("accuracy"),
+                format!(
+                    "{}%",
+                    locale::format_number(history::accuracy(keystrokes, mistakes), 1)
+                ),
+            ],
+        ];
+        if let Some(best) = self.personal_best {
+            rows.push(vec![
+                t("report-personal-best"),
+                format!(
+                    "{} ({})",
+                    self.format_speed(self.unit.rate(&best.speed_sample())),
+                    locale::format_date(best.meta.created_at)
+                ),
+            ]);
+        }
+        format!(
+            "<h2>{}</h2>{}",
+            escape(&t("report-summary")),
+            table(&[], rows.into_iter())
+        )
+    }
+
+    fn render_speed(&self) -> String {
+        let points = self.weekly_speed.iter().flat_map(|(_s, w)| w).copied();
+        let Some(bounds) = Bounds::of(points) else {
+            return String::new();
+        };
+        let series: Vec<(String, Vec<(f64, f64)>)> = self
+            .weekly_speed
+            .iter()
+            .map(|(script, weeks)| {
+                let name = script.map(|s| format!("{}, ", t(s.message_key())));
+                (name.unwrap_or_default(), weeks.clone())
+            })
+            .collect();
+        let legend = series
+            .iter()
+            .enumerate()
+            .filter(|(_i, (name, _))| !name.is_empty())
+            .map(|(i, (name, _))| {
+                format!(
+                    r#"<span style="color: {}">&#9644; {}</span>"#,
+                    SERIES_COLORS[i % SERIES_COLORS.len()],
+                    escape(name.trim_end_matches(", "))
+                )
+            })
+            .join(" ");
+        let rows = series.iter().flat_map(|(name, weeks)| {
+            weeks.iter().map(move |(at, rate)| {
+                vec![
+                    format!("{name}{}", locale::format_date(*at)),
+                    self.format_speed(*rate),
+                ]
+            })
+        });
+        format!(
+            "<h2>{}</h2>{}<p>{legend} {}</p>{}",
+            escape(&t_args(
+                "report-speed",
+                &[("unit", &t(self.unit.message_key()))]
+            )),
+            line_chart(&series, bounds),
+            escape(&t_args(
+                "report-range",
+                &[
+                    ("min", &locale::format_number(bounds.min, 1)),
+                    ("max", &locale::format_number(bounds.max, 1)),
+                ]
+            )),
+            table(&[t("report-week"), t("speed")], rows),
+        )
+    }
+
+    fn render_accuracy(&self) -> String {
+        let points: Vec<(f64, f64)> = self
+            .daily_accuracy
+            .iter()
+            .map(|d| (d.day, history::accuracy(d.keystrokes, d.mistakes)))
+            .collect();
+        let Some(bounds) = Bounds::of(points.iter().copied()) else {
+            return String::new();
+        };
+        // Up to a perfect score, so that small dips do not look like falls
+        let bounds = Bounds {
+            max: 100.0,
+            ..bounds
+        };
+        let rows = self
+            .daily_accuracy
+            .iter()
+            .zip(&points)
+            .map(|(d, (_, pct))| {
+                vec![
+                    locale::format_date(d.day),
+                    d.keystrokes.to_string(),
+                    format!("{}%", locale::format_number(*pct, 1)),
+                ]
+            });
+        format!(
+            "<h2>{}</h2>{}<p>{}</p>{}",
+            escape(&t("report-daily-accuracy")),
+            line_chart(&[(String::new(), points.clone())], bounds),
+            escape(&t_args(
+                "report-range",
+                &[
+                    ("min", &locale::format_number(bounds.min, 1)),
+                    ("max", &locale::format_number(bounds.max, 1)),
+                ]
+            )),
+            table(&[t("report-day"), t("keystrokes"), t("accuracy")], rows),
+        )
+    }
+
+    fn render_weakest_chars(&self) -> String {
+        let rows = self
+            .error_scores
+            .iter()
+            .filter(|(_c, score)| **score > 0)
+            .sorted_by_key(|(c, score)| (Reverse(**score), **c))
+            .take(WEAKEST_CHARS_LISTED)
+            .map(|(c, score)| {
+                let c = if *c == ' ' {
+                    "␣".to_string()
+                } else {
+                    c.to_string()
+                };
+                vec![c, score.to_string()]
+            })
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return String::new();
+        }
+        format!(
+            "<h2>{}</h2>{}",
+            escape(&t("report-weakest")),
+            table(
+                &[t("report-char"), t("report-error-score")],
+                rows.into_iter()
+            )
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_fill_the_chart() {
+        let bounds = Bounds::of([(0.0, 40.0), (2.0, 60.0), (1.0, 50.0)].into_iter()).unwrap();
+        assert_eq!(bounds.place(0.0, 40.0), (0.0, CHART_HEIGHT));
+        assert_eq!(bounds.place(2.0, 60.0), (CHART_WIDTH, 0.0));
+        assert_eq!(
+            bounds.place(1.0, 50.0),
+            (CHART_WIDTH / 2.0, CHART_HEIGHT / 2.0)
+        );
+        let single = Bounds::of([(5.0, 40.0)].into_iter()).unwrap();
+        assert_eq!(
+            single.place(5.0, 40.0),
+            (CHART_WIDTH / 2.0, CHART_HEIGHT / 2.0)
+        );
+        assert_eq!(escape("<b>\"&\""), "&lt;b&gt;&quot;&amp;&quot;");
+    }
+}
@@ -0,0 +1,108 @@
+//! The session played back as notes at the pace it was typed: each key
+//! has its pitch and mistakes sound a dissonant pair, so flow and
+//! hesitation can be heard rather than read from a table.
+
+use web_sys::{AudioContext, OscillatorType};
+
+use crate::analytics::Keystroke;
+
+/// Pentatonic steps in semitones, any sequence of keys sounds tolerable
+const SCALE: [u32; 5] = [0, 2, 4, 7, 9];
+const OCTAVES: u32 = 3;
+const BASE_HZ: f32 = 220.0;
+const NOTE_SECONDS: f64 = 0.25;
+const VOLUME: f32 = 0.2;
+/// Longer hesitations are shortened to this, a break stays a break without the wait
+const MAX_GAP_MS: f64 = 1500.0;
+/// Between prompts, where there is no interval to play
+const PROMPT_GAP_MS: f64 = 600.0;
+/// The semitone above the expected note, played along on a mistake
+const DISSONANCE: f32 = 1.059_463;
+
+fn js_error(e: wasm_bindgen::JsValue) -> String {
+    format!("{e:?}")
+}
+
+struct Note {
+    /// From the start of the playback
+    at_ms: f64,
+    hz: f32,
+    mistake: bool,
+}
+
+fn pitch(c: char) -> f32 {
+    let degree = c as u32 % (SCALE.len() as u32 * OCTAVES);
+    let semitones = degree / SCALE.len() as u32 * 12 + SCALE[degree as usize % SCALE.len()];
+    BASE_HZ * 2_f32.powf(semitones as f32 / 12.0)
+}
+
+fn notes(log: &[Keystroke]) -> Vec<Note> {
+    let mut at_ms = 0.0;
+    log.iter()
+        .enumerate()
+        .map(|(i, k)| {
+            if i > 0 {
+                at_ms += k.latency.map_or(PROMPT_GAP_MS, |l| l.min(MAX_GAP_MS));
+            }
+            Note {
+                at_ms,
+                hz: pitch(k.expected),
+                mistake: k.expected != k.typed,
+            }
+        })
+        .collect()
+}
+
+/// A playing playback, stopped when dropped
+pub struct Rhythm {
+    context: AudioContext,
+    pub duration_ms: f64,
+}
+
+impl Rhythm {
+    pub fn play(log: &[Keystroke]) -> Result<Self, String> {
+        let context = AudioContext::new().map_err(js_error)?;
+        let start = context.current_time();
+        let notes = notes(log);
+        for note in &notes {
+            let at = start + note.at_ms / 1000.0;
+            let pitches: &[(f32, OscillatorType)] = if note.mistake {
+                &[
+                    (note.hz, OscillatorType::Sawtooth),
+                    (note.hz * DISSONANCE, OscillatorType::Sawtooth),
+                ]
+            } else {
+                &[(note.hz, OscillatorType::Sine)]
+            };
+            for (hz, kind) in pitches {
+                let oscillator = context.create_oscillator().map_err(js_error)?;
+                oscillator.set_type(*kind);
+                oscillator.frequency().set_value(*hz);
+                let gain = context.create_gain().map_err(js_error)?;
+                gain.gain()
+                    .set_value_at_time(VOLUME, at)
+                    .and_then(|g| g.exponential_ramp_to_value_at_time(0.001, at + NOTE_SECONDS))
+                    .map_err(js_error)?;
+                oscillator
+                    .connect_with_audio_node(&gain)
+                    .and_then(|_| gain.connect_with_audio_node(&context.destination()))
+                    .map_err(js_error)?;
+                oscillator.start_with_when(at).map_err(js_error)?;
+                oscillator
+                    .stop_with_when(at + NOTE_SECONDS)
+                    .map_err(js_error)?;
+            }
+        }
+        let duration_ms = notes.last().map_or(0.0, |n| n.at_ms) + NOTE_SECONDS * 1000.0;
+        Ok(Rhythm {
+            context,
+            duration_ms,
+        })
+    }
+}
+
+impl Drop for Rhythm {
+    fn drop(&mut self) {
+        let _ = self.context.close();
+    }
+}
@@ -0,0 +1,50 @@
+//! Pages of the app. The page is kept in the URL hash (`#/stats`) by the
+//! router, so reloads and the back button work without the server knowing the paths.
+
+use yew::html::Scope;
+use yew_router::{prelude::RouterScopeExt, Routable};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Routable)]
+pub enum Route {
+    /// Unknown paths are the practice page
+    #[at("/practice")]
+    #[not_found]
+    Practice,
+    #[at("/stats")]
+    Stats,
+    #[at("/lessons")]
+    Lessons,
+    #[at("/settings")]
+    Settings,
+}
+
+impl Route {
+    pub const ALL: [Route; 4] = [
+        Route::Practice,
+        Route::Stats,
+        Route::Lessons,
+        Route::Settings,
+    ];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Route::Practice => "page-practice",
+            Route::Stats => "page-stats",
+            Route::Lessons => "page-lessons",
+            Route::Settings => "page-settings",
+        }
+    }
+
+    /// The page in the address bar
+    pub fn current<C: yew::Component>(link: &Scope<C>) -> Self {
+        link.route().unwrap_or(Route::Practice)
+    }
+}
+
+/// Opens `route`, location listeners of the router report it
+pub fn go<C: yew::Component>(link: &Scope<C>, route: Route) {
+    match link.navigator() {
+        Some(navigator) => navigator.push(&route),
+        None => tracing::warn!("Opening {} outside the router", route.to_path()),
+    }
+}
@@ -0,0 +1,110 @@
+//! Sessions last from opening the page until it is hidden for the last time.
+//! A closing tab leaves no time for more than a quick save, so the summary
+//! is saved whenever the page is hidden and shown as a recap on the next launch.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    codec,
+    events::{EventKind, EventLog},
+    metrics,
+    store::StatsStore,
+    units::SpeedUnit,
+};
+
+pub const SESSION_STORAGE_KEY: &str = "last_session";
+/// Logged events further apart than this belong to different sessions
+const SESSION_GAP_MS: f64 = 30.0 * 60.0 * 1000.0;
+
+/// Unix times in ms from `from` until `to`, `to` excluded
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Period {
+    pub from: f64,
+    pub to: f64,
+}
+
+impl Period {
+    pub fn contains(self, at: f64) -> bool {
+        (self.from..self.to).contains(&at)
+    }
+}
+
+/// Logged events close enough in time to be one sitting
+pub struct Session {
+    /// Unix times in ms of the first and last event
+    pub start: f64,
+    pub end: f64,
+    pub prompts: usize,
+}
+
+impl Session {
+    pub fn period(&self) -> Period {
+        Period {
+            from: self.start,
+            to: self.end + 1.0,
+        }
+    }
+}
+
+/// Sessions of the log, oldest first
+pub fn sessions(log: &EventLog) -> Vec<Session> {
+    let mut sessions: Vec<Session> = vec![];
+    for event in &log.events {
+        let prompts = usize::from(matches!(event.kind, EventKind::PromptStart { .. }));
+        match sessions.last_mut() {
+            Some(session) if event.at - session.end < SESSION_GAP_MS => {
+                session.end = event.at;
+                session.prompts += prompts;
+            }
+            _ => sessions.push(Session {
+                start: event.at,
+                end: event.at,
+                prompts,
+            }),
+        }
+    }
+    sessions
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SessionSummary {
+    /// Unix time in ms
+    pub started_at: f64,
+    pub ended_at: f64,
+    pub prompts: usize,
+    pub keystrokes: usize,
+    /// Ids and values of the metrics that had enough data
+    pub metrics: Vec<(String, f64)>,
+    /// Unit the speed metric was computed in
+    #[serde(default)]
+    pub speed_unit: SpeedUnit,
+}
+
+impl SessionSummary {
+    pub fn new(
+        started_at: f64,
+        ended_at: f64,
+        prompts: usize,
+        input: &metrics::MetricInput,
+    ) -> Self {
+        SessionSummary {
+            started_at,
+            ended_at,
+            prompts,
+            keystrokes: input.log.len(),
+            metrics: metrics::compute_all(input)
+                .into_iter()
+                .filter_map(|(metric, value)| Some((metric.id().to_string(), value?)))
+                .collect(),
+            speed_unit: input.settings.speed_unit,
+        }
+    }
+
+    pub fn load(store: &dyn StatsStore) -> Option<Self> {
+        codec::load(store, SESSION_STORAGE_KEY).ok()
+    }
+
+    pub fn save(&self, store: &dyn StatsStore, compact: bool) -> Result<(), String> {
+        codec::save(store, SESSION_STORAGE_KEY, self, compact)
+    }
+}
@@ -0,0 +1,248 @@
+//! User preferences, persisted in the statistics store.
+
+use std::collections::{BTreeSet, HashSet};
+
+use serde::{Deserialize, Serialize};
+use typing_core::ids::{RecordMeta, Ulid};
+
+pub use typing_core::errors::Penalties;
+
+use crate::{
+    ambient::Soundscape,
+    analytics::SymbolClass,
+    audio::SoundTheme,
+    aux_input::AuxBinding,
+    blocklist::Blocklist,
+    book,
+    charset::CharSet,
+    codec,
+    fingers::FingerMap,
+    i18n::Language,
+    keyboards::Keyboard,
+    keycaps::Layout,
+    layout_switch::Migration,
+    normalize::Normalization,
+    snippets::CodeLanguage,
+    sources,
+    store::{LocalStore, StatsStore},
+    units::{SpeedUnit, TimerStart},
+};
+
+pub const SETTINGS_STORAGE_KEY: &str = "settings";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub speed_unit: SpeedUnit,
+    /// `None` follows the browser language
+    pub language: Option<Language>,
+    /// Characters accepted in addition to printable ASCII
+    pub extra_chars: BTreeSet<char>,
+    /// Clean attempts in a row that complete a repeat-until-clean sentence
+    pub clean_repetitions: usize,
+    /// Store stats in the compact binary format instead of JSON
+    pub compact_storage: bool,
+    pub keyboards: Vec<Keyboard>,
+    pub finger_map: FingerMap,
+    /// Characters typed through a layer key (QMK-style), tracked separately
+    pub layer_chars: BTreeSet<char>,
+    /// Shift is a home-row modifier, so shifted characters count as layer ones
+    pub home_row_shift: bool,
+    /// User stylesheet applied on top of the built-in one, see THEMING.md
+    pub custom_css: String,
+    /// Very large prompt and a thick caret for low-vision users
+    pub large_text: bool,
+    /// On-screen keyboard under the prompt showing the next key
+    pub show_keyboard: bool,
+    /// Speak the upcoming text on a key press
+    pub read_aloud: bool,
+    /// Race a caret moving at the pace of recent results
+    pub pace_caret: bool,
+    /// Background sound, `None` is silence
+    pub ambient: Option<Soundscape>,
+    /// From 0 to 1
+    pub ambient_volume: f64,
+    /// Sound on every key press, off mutes the sounds of typing
+    pub key_sounds: bool,
+    pub sound_theme: SoundTheme,
+    /// Another sound on a mistake, along with the key press
+    pub error_sounds: bool,
+    pub penalties: Penalties,
+    /// Prompts completed below this accuracy in percent are sloppy
+    /// and are no personal bests, `None` counts all of them
+    pub min_accuracy: Option<f64>,
+    /// Seconds after which a completed prompt is followed by the next one,
+    /// `None` waits for Enter
+    pub auto_advance: Option<u32>,
+    pub timer_start: TimerStart,
+    /// Prompts at the start of a session that do not change the statistics
+    pub warm_up_prompts: usize,
+    /// Layout drawn on the on-screen keyboard of the key discovery
+    pub layout: Layout,
+    /// Hesitation before the key being looked for is revealed
+    pub discovery_delay_ms: u32,
+    /// Accuracy in percent the recent prompts of the course need for the next key
+    pub curriculum_accuracy: f64,
+    /// and their speed in WPM
+    pub curriculum_wpm: f64,
+    /// Layout switch in progress
+    pub migration: Option<Migration>,
+    /// Gamepad and mouse buttons bound to actions
+    pub aux_bindings: Vec<AuxBinding>,
+    /// Backspaces allowed in an exam, `None` is unlimited
+    pub exam_backspace_limit: Option<usize>,
+    /// Id of the prompt source, see `sources::registry`
+    pub prompt_source: String,
+    /// Text fetched by the URL prompt source
+    pub source_url: String,
+    /// Language of the code snippets prompt source, `None` mixes them
+    pub snippet_language: Option<CodeLanguage>,
+    /// Half of the words of dictionary prompts start with a capital letter
+    pub uppercase_words: bool,
+    /// Offensive words of the built-in list are left out of word and quote prompts
+    pub built_in_blocklist: bool,
+    /// Words the user leaves out of word and quote prompts, see `Blocklist::new`
+    pub blocked_words: Vec<String>,
+    /// Characters practiced, see `CharSet::chars`
+    pub charset: CharSet,
+    /// Characters in a random symbols prompt
+    pub prompt_len: usize,
+    /// Character classes left out of random symbols prompts
+    pub disabled_classes: BTreeSet<SymbolClass>,
+    /// Keystrokes slower than this percentage of the session's are hesitations
+    pub hesitation_percentile: f64,
+    /// Recent mistakes listed under the prompt
+    pub mistakes_shown: usize,
+    /// Off, practice leaves the stored statistics as they are
+    pub persist_stats: bool,
+    /// Keystrokes are ignored while CapsLock is on, so they do not count as mistakes
+    pub caps_lock_guard: bool,
+    /// Characters of a long text typed at once, up to the end of the sentence
+    pub passage_len: usize,
+    /// How imported texts and books are made typeable
+    pub normalization: Normalization,
+    /// Days between suggestions to download a backup, 0 turns them off
+    pub backup_reminder_days: u32,
+    /// Keyboard new results are tagged with
+    pub active_keyboard: Option<Ulid>,
+    /// Newer settings win when merging devices
+    pub meta: RecordMeta,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            speed_unit: Default::default(),
+            language: None,
+            extra_chars: Default::default(),
+            clean_repetitions: 1,
+            compact_storage: false,
+            keyboards: vec![],
+            finger_map: Default::default(),
+            layer_chars: Default::default(),
+            home_row_shift: false,
+            custom_css: String::new(),
+            large_text: false,
+            show_keyboard: true,
+            read_aloud: false,
+            pace_caret: false,
+            ambient: None,
+            ambient_volume: 0.3,
+            key_sounds: false,
+            sound_theme: SoundTheme::SoftClick,
+            error_sounds: true,
+            penalties: Default::default(),
+            min_accuracy: None,
+            auto_advance: None,
+            timer_start: Default::default(),
+            warm_up_prompts: 0,
+            layout: Default::default(),
+            discovery_delay_ms: 2000,
+            curriculum_accuracy: 95.0,
+            curriculum_wpm: 30.0,
+            migration: None,
+            aux_bindings: vec![],
+            exam_backspace_limit: None,
+            prompt_source: sources::DEFAULT_SOURCE.to_string(),
+            source_url: String::new(),
+            snippet_language: None,
+            uppercase_words: false,
+            built_in_blocklist: true,
+            blocked_words: vec![],
+            charset: CharSet::Ascii,
+            prompt_len: sources::SYMBOLS_LEN,
+            disabled_classes: Default::default(),
+            hesitation_percentile: 95.0,
+            mistakes_shown: 100,
+            persist_stats: true,
+            caps_lock_guard: true,
+            passage_len: book::PASSAGE_LEN,
+            normalization: Default::default(),
+            backup_reminder_days: 7,
+            active_keyboard: None,
+            meta: Default::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Settings saved before they went through the store are in LocalStorage
+    pub fn load(store: &dyn StatsStore) -> Self {
+        codec::load(store, SETTINGS_STORAGE_KEY)
+            .or_else(|_| codec::load(&LocalStore, SETTINGS_STORAGE_KEY))
+            .unwrap_or_else(|_| Settings::new(js_sys::Date::now()))
+    }
+
+    fn new(now: f64) -> Self {
+        Settings {
+            meta: RecordMeta::new(now),
+            ..Default::default()
+        }
+    }
+
+    /// Layout prompts are typed on, the new one while practicing a switch
+    pub fn typed_layout(&self) -> Layout {
+        self.migration
+            .as_ref()
+            .map_or(self.layout, Migration::typed_layout)
+    }
+
+    /// Characters that can be typed: the symbols, the space and the extra characters
+    pub fn expected_chars(&self) -> HashSet<char> {
+        self.charset
+            .chars()
+            .into_iter()
+            .chain([' '])
+            .chain(self.extra_chars.iter().copied())
+            .collect()
+    }
+
+    pub fn blocklist(&self) -> Blocklist {
+        Blocklist::new(self.built_in_blocklist, &self.blocked_words)
+    }
+
+    /// Characters random symbols prompts are made of, all of them
+    /// if every class is disabled
+    pub fn symbol_chars(&self) -> Vec<char> {
+        let chars: Vec<char> = self
+            .charset
+            .chars()
+            .into_iter()
+            .filter(|c| !self.disabled_classes.contains(&SymbolClass::of(*c)))
+            .collect();
+        if chars.is_empty() {
+            self.charset.chars()
+        } else {
+            chars
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language.unwrap_or_else(Language::detect)
+    }
+
+    pub fn save(&mut self, store: &dyn StatsStore) -> Result<(), String> {
+        self.meta.updated_at = js_sys::Date::now();
+        codec::save(store, SETTINGS_STORAGE_KEY, self, self.compact_storage)
+    }
+}
@@ -0,0 +1,209 @@
+//! Short codes that carry a training setup, to be read out or pasted
+//! between friends. A code is the postcard-serialized [`Preset`] with a
+//! checksum, in Crockford base32 grouped by five, e.g. `A1B2C-D3E4F-G5`.
+//! Letters easily confused are read as the digits they look like.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use typing_core::ids::CROCKFORD;
+
+use crate::{charset::CharSet, fingers::FingerMap, keycaps::Layout, settings::Settings};
+
+/// First byte of every code, changes if the preset format does
+const VERSION: u8 = 1;
+const GROUP_LEN: usize = 5;
+
+/// The settings a share code carries
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Preset {
+    pub layout: Layout,
+    pub finger_map: FingerMap,
+    pub charset: CharSet,
+    pub extra_chars: BTreeSet<char>,
+    pub prompt_source: String,
+    /// Only for the URL source
+    pub source_url: String,
+}
+
+impl Preset {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Preset {
+            layout: settings.layout,
+            finger_map: settings.finger_map,
+            charset: settings.charset,
+            extra_chars: settings.extra_chars.clone(),
+            prompt_source: settings.prompt_source.clone(),
+            source_url: if settings.prompt_source == "url" {
+                settings.source_url.clone()
+            } else {
+                String::new()
+            },
+        }
+    }
+
+    pub fn apply(self, settings: &mut Settings) {
+        settings.layout = self.layout;
+        settings.finger_map = self.finger_map;
+        settings.charset = self.charset;
+        settings.extra_chars = self.extra_chars;
+        settings.prompt_source = self.prompt_source;
+        if !self.source_url.is_empty() {
+            settings.source_url = self.source_url;
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let mut bytes = vec![VERSION];
+        bytes.extend(postcard::to_allocvec(self).unwrap());
+        bytes.extend(fletcher16(&bytes).to_be_bytes());
+        let chars: Vec<char> = to_base32(&bytes);
+        chars
+            .chunks(GROUP_LEN)
+            .map(|group| group.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    pub fn decode(code: &str) -> Result<Self, ShareCodeError> {
+        let digits = code
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' '))
+            .map(|c| match c.to_ascii_uppercase() {
+                'O' => Some(0),
+                'I' | 'L' => Some(1),
+                c => CROCKFORD
+                    .iter()
+                    .position(|d| *d as char == c)
+                    .map(|d| d as u8),
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(ShareCodeError::Invalid)?;
+        let bytes = from_base32(&digits);
+        let [version, .., _, _] = bytes[..] else {
+            return Err(ShareCodeError::Invalid);
+        };
+        let (payload, checksum) = bytes.split_at(bytes.len() - 2);
+        if fletcher16(payload).to_be_bytes() != checksum {
+            return Err(ShareCodeError::Checksum);
+        }
+        if version != VERSION {
+            return Err(ShareCodeError::Version);
+        }
+        postcard::from_bytes(&payload[1..]).map_err(|_| ShareCodeError::Invalid)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ShareCodeError {
+    /// Not a share code at all
+    Invalid,
+    /// A character was mistyped
+    Checksum,
+    /// Made by a newer version of the app
+    Version,
+}
+
+impl ShareCodeError {
+    pub fn message_key(&self) -> &'static str {
+        match self {
+            ShareCodeError::Invalid => "share-code-invalid",
+            ShareCodeError::Checksum => "share-code-checksum",
+            ShareCodeError::Version => "share-code-version",
+        }
+    }
+}
+
+fn fletcher16(bytes: &[u8]) -> u16 {
+    let (a, b) = bytes.iter().fold((0u16, 0u16), |(a, b), byte| {
+        let a = (a + *byte as u16) % 255;
+        (a, (b + a) % 255)
+    });
+    b << 8 | a
+}
+
+fn to_base32(bytes: &[u8]) -> Vec<char> {
+    let mut chars = vec![];
+    let (mut buffer, mut bits) = (0u32, 0);
+    for byte in bytes {
+        buffer = buffer << 8 | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            chars.push(CROCKFORD[(buffer >> bits) as usize & 0x1f] as char);
+        }
+    }
+    if bits > 0 {
+        chars.push(CROCKFORD[(buffer << (5 - bits)) as usize & 0x1f] as char);
+    }
+    chars
+}
+
+/// Bytes of base32 digits, the padding bits of the last digit are dropped
+fn from_base32(digits: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![];
+    let (mut buffer, mut bits) = (0u32, 0);
+    for digit in digits {
+        buffer = buffer << 5 | *digit as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset() -> Preset {
+        Preset {
+            layout: Layout::default(),
+            finger_map: FingerMap::default(),
+            charset: CharSet::Cyrillic,
+            extra_chars: BTreeSet::from(['№', '@']),
+            prompt_source: "url".to_string(),
+            source_url: "https://example.com/words.txt".to_string(),
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        let code = preset().encode();
+        assert!(code.split('-').all(|group| group.len() <= GROUP_LEN));
+        assert_eq!(Preset::decode(&code), Ok(preset()));
+        assert_eq!(
+            Preset::decode(&code.replace('-', " ").to_lowercase()),
+            Ok(preset())
+        );
+    }
+
+    #[test]
+    fn mistyped_codes_fail_the_checksum() {
+        let code = preset().encode();
+        let mistyped: String = code
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match (i, c) {
+                (3, 'Z') => 'Y',
+                (3, _) => 'Z',
+                _ => c,
+            })
+            .collect();
+        assert_eq!(Preset::decode(&mistyped), Err(ShareCodeError::Checksum));
+        assert_eq!(Preset::decode("U"), Err(ShareCodeError::Invalid));
+    }
+
+    #[test]
+    fn letters_like_digits_are_read_as_digits() {
+        let code = preset().encode();
+        assert!(code.contains('0') && code.contains('1'));
+        let confused = code
+            .replacen('0', "O", 1)
+            .replacen('1', "I", 1)
+            .replace('1', "l");
+        assert_eq!(Preset::decode(&confused), Ok(preset()));
+    }
+}
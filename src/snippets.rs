@@ -0,0 +1,102 @@
+//! Short snippets of code compiled into the app, for practicing the braces,
+//! operators and indentation programmers type. A snippet keeps its line
+//! breaks: Enter types a line break and Tab the indentation that follows it.
+
+use serde::{Deserialize, Serialize};
+
+const SNIPPETS: &str = include_str!("../assets/snippets.txt");
+/// Starts every snippet in the asset, followed by the language tag
+const HEADER: &str = "=== ";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+}
+
+impl CodeLanguage {
+    pub const ALL: [CodeLanguage; 3] = [
+        CodeLanguage::Rust,
+        CodeLanguage::Python,
+        CodeLanguage::JavaScript,
+    ];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            CodeLanguage::Rust => "code-rust",
+            CodeLanguage::Python => "code-python",
+            CodeLanguage::JavaScript => "code-javascript",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "rust" => Some(CodeLanguage::Rust),
+            "python" => Some(CodeLanguage::Python),
+            "js" => Some(CodeLanguage::JavaScript),
+            _ => None,
+        }
+    }
+}
+
+pub struct Snippet {
+    pub language: CodeLanguage,
+    /// Lines joined by `\n`, without blank lines at the ends
+    pub text: String,
+}
+
+/// Snippets of `text`, each after a `=== <language>` line.
+/// Snippets of unknown languages are skipped.
+fn parse(text: &str) -> Vec<Snippet> {
+    let mut snippets = vec![];
+    let mut current: Option<(Option<CodeLanguage>, Vec<&str>)> = None;
+    for line in text.lines().chain([HEADER]) {
+        let Some(tag) = line.strip_prefix(HEADER) else {
+            if let Some((_, lines)) = &mut current {
+                lines.push(line.trim_end());
+            }
+            continue;
+        };
+        if let Some((Some(language), lines)) = current.take() {
+            let text = lines.join("\n").trim_matches('\n').to_string();
+            if !text.is_empty() {
+                snippets.push(Snippet { language, text });
+            }
+        }
+        current = Some((CodeLanguage::from_tag(tag.trim()), vec![]));
+    }
+    snippets
+}
+
+/// The bundled snippets in `language`, or in all of them
+pub fn snippets(language: Option<CodeLanguage>) -> Vec<Snippet> {
+    parse(SNIPPETS)
+        .into_iter()
+        .filter(|s| language.is_none() || language == Some(s.language))
+        .collect()
+}
+
+/// Spaces expected from the char index `at` of `prompt` on, typed at once by Tab
+pub fn indentation(prompt: &str, at: usize) -> usize {
+    prompt.chars().skip(at).take_while(|c| *c == ' ').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippets_keep_their_indentation() {
+        let parsed = parse("=== rust\nfn f() {\n    g();\n}\n\n=== cobol\nX.\n=== js\nf();\n");
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].language, CodeLanguage::Rust);
+        assert_eq!(parsed[0].text, "fn f() {\n    g();\n}");
+        assert_eq!(parsed[1].text, "f();");
+        assert_eq!(indentation(&parsed[0].text, 9), 4);
+        assert_eq!(indentation(&parsed[0].text, 0), 0);
+        assert!(CodeLanguage::ALL
+            .iter()
+            .all(|l| !snippets(Some(*l)).is_empty()));
+    }
+}
@@ -0,0 +1,356 @@
+//! Where prompts come from. Every source implements [`PromptSource`]
+//! and is listed in [`registry`], the rest of the app only sees the trait.
+
+use std::{borrow::Cow, collections::HashSet, future::Future, pin::Pin, rc::Rc};
+
+use gloo_net::http::Request;
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom, Rng};
+use serde::Deserialize;
+
+pub use typing_core::prompts::{symbols, CharWeights, SYMBOLS_LEN};
+
+use crate::{
+    assets,
+    blocklist::Blocklist,
+    normalize::Normalization,
+    settings::Settings,
+    snippets::{self, CodeLanguage},
+};
+
+/// Words in a dictionary prompt
+const WORDS_PER_PROMPT: usize = 8;
+/// Candidates a text source samples from, to keep weighting cheap
+const CANDIDATES: usize = 20;
+/// Answers with a random quote as `{"quote": ..., "author": ...}`
+const QUOTES_API: &str = "https://dummyjson.com/quotes/random";
+/// Quotes typed when the API cannot be reached, compiled in for offline use
+const OFFLINE_QUOTES: &str = include_str!("../assets/quotes.txt");
+/// Words of the dictionary when the asset cannot be fetched
+const OFFLINE_WORDS: &str = include_str!("../assets/words.txt");
+
+pub const DEFAULT_SOURCE: &str = "random-symbols";
+pub const DICTIONARY_SOURCE: &str = "dictionary";
+pub const SNIPPETS_SOURCE: &str = "snippets";
+
+pub type PromptFuture = Pin<Box<dyn Future<Output = Result<String, String>>>>;
+
+pub struct SourceInfo {
+    /// Stored in the settings
+    pub id: &'static str,
+    pub name_key: &'static str,
+}
+
+pub trait PromptSource {
+    fn info(&self) -> SourceInfo;
+
+    /// The next prompt. The work happens when the future is polled,
+    /// so creating it never delays a paint.
+    fn next_prompt(self: Rc<Self>, weights: CharWeights) -> PromptFuture;
+
+    /// Weighting hook: how well `candidate` practices weak characters.
+    /// Candidates with characters that cannot be typed score zero.
+    fn score(&self, candidate: &str, weights: &CharWeights) -> f64 {
+        let Some(total) = candidate
+            .chars()
+            .map(|c| weights.get(c))
+            .sum::<Option<usize>>()
+        else {
+            return 0.0;
+        };
+        total as f64 / candidate.chars().count().max(1) as f64
+    }
+}
+
+/// Random symbols, weak ones more often
+pub struct RandomSymbols {
+    chars: Vec<char>,
+    len: usize,
+}
+
+impl PromptSource for RandomSymbols {
+    fn info(&self) -> SourceInfo {
+        SourceInfo {
+            id: DEFAULT_SOURCE,
+            name_key: "source-random-symbols",
+        }
+    }
+
+    fn next_prompt(self: Rc<Self>, weights: CharWeights) -> PromptFuture {
+        Box::pin(async move { Ok(symbols(&self.chars, &weights, self.len)) })
+    }
+}
+
+/// Lines of a bundled asset: whole lines, or `per_prompt` of them joined by spaces
+struct AssetLines {
+    id: &'static str,
+    name_key: &'static str,
+    asset: &'static str,
+    /// The asset compiled in, typed when fetching it fails
+    offline: Option<&'static str>,
+    per_prompt: usize,
+    blocklist: Rc<Blocklist>,
+    /// Words start with a capital letter half of the time, when it can be typed
+    capitalize: bool,
+}
+
+impl PromptSource for AssetLines {
+    fn info(&self) -> SourceInfo {
+        SourceInfo {
+            id: self.id,
+            name_key: self.name_key,
+        }
+    }
+
+    fn next_prompt(self: Rc<Self>, weights: CharWeights) -> PromptFuture {
+        Box::pin(async move {
+            let text = match (assets::fetch_text(self.asset).await, self.offline) {
+                (Ok(text), _) => Cow::Owned(text),
+                (Err(e), Some(offline)) => {
+                    tracing::warn!("Typing the bundled {}, fetching failed: {e}", self.asset);
+                    Cow::Borrowed(offline)
+                }
+                (Err(e), None) => return Err(e),
+            };
+            let score = |line: &str| self.score(line, &weights);
+            let picked: Option<Vec<&str>> = (0..self.per_prompt)
+                .map(|_| pick(&text, &self.blocklist, &score))
+                .collect();
+            let mut rng = rand::thread_rng();
+            picked
+                .map(|lines| {
+                    let words = lines.join(" ");
+                    if !self.capitalize {
+                        return words;
+                    }
+                    words
+                        .split(' ')
+                        .map(|w| match capitalized(w) {
+                            Some(w) if rng.gen_bool(0.5) && self.score(&w, &weights) > 0.0 => w,
+                            _ => w.to_string(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .ok_or_else(|| format!("{}: nothing can be typed", self.asset))
+        })
+    }
+}
+
+/// Multi-line snippets of code, in one language or any
+pub struct CodeSnippets {
+    pub language: Option<CodeLanguage>,
+}
+
+impl PromptSource for CodeSnippets {
+    fn info(&self) -> SourceInfo {
+        SourceInfo {
+            id: SNIPPETS_SOURCE,
+            name_key: "source-snippets",
+        }
+    }
+
+    fn next_prompt(self: Rc<Self>, weights: CharWeights) -> PromptFuture {
+        Box::pin(async move {
+            let snippets = snippets::snippets(self.language);
+            let scores = snippets.iter().map(|s| self.score(&s.text, &weights));
+            let index = WeightedIndex::new(scores)
+                .map_err(|_| "snippets: nothing can be typed".to_string())?;
+            Ok(snippets[index.sample(&mut rand::thread_rng())].text.clone())
+        })
+    }
+
+    /// Line breaks are typed with Enter, which is no practiced character
+    fn score(&self, candidate: &str, weights: &CharWeights) -> f64 {
+        let Some(total) = candidate
+            .chars()
+            .filter(|c| *c != '\n')
+            .map(|c| weights.get(c))
+            .sum::<Option<usize>>()
+        else {
+            return 0.0;
+        };
+        total as f64 / candidate.chars().count().max(1) as f64
+    }
+}
+
+/// Lines of a text fetched from a user-provided URL
+pub struct Fetched {
+    pub url: String,
+    pub blocklist: Rc<Blocklist>,
+}
+
+impl PromptSource for Fetched {
+    fn info(&self) -> SourceInfo {
+        SourceInfo {
+            id: "url",
+            name_key: "source-url",
+        }
+    }
+
+    fn next_prompt(self: Rc<Self>, weights: CharWeights) -> PromptFuture {
+        Box::pin(async move {
+            let url = &self.url;
+            if url.is_empty() {
+                return Err("no URL".to_string());
+            }
+            let response = Request::get(url).send().await.map_err(|e| e.to_string())?;
+            if !response.ok() {
+                return Err(format!(
+                    "{url}: {} {}",
+                    response.status(),
+                    response.status_text()
+                ));
+            }
+            let text = response.text().await.map_err(|e| e.to_string())?;
+            pick(&text, &self.blocklist, &|line| self.score(line, &weights))
+                .map(str::to_string)
+                .ok_or_else(|| format!("{url}: nothing can be typed"))
+        })
+    }
+}
+
+/// A random quote of [`QUOTES_API`], or of the bundled ones if it cannot be
+/// reached or the quote cannot be typed or is blocked
+pub struct OnlineQuotes {
+    pub normalization: Normalization,
+    pub expected: HashSet<char>,
+    pub blocklist: Rc<Blocklist>,
+}
+
+#[derive(Deserialize)]
+struct ApiQuote {
+    quote: String,
+}
+
+async fn fetch_quote() -> Result<String, String> {
+    let response = Request::get(QUOTES_API)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.ok() {
+        return Err(format!(
+            "{QUOTES_API}: {} {}",
+            response.status(),
+            response.status_text()
+        ));
+    }
+    let text = response.text().await.map_err(|e| e.to_string())?;
+    let quote: ApiQuote = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+    Ok(quote.quote)
+}
+
+impl PromptSource for OnlineQuotes {
+    fn info(&self) -> SourceInfo {
+        SourceInfo {
+            id: "online-quotes",
+            name_key: "source-online-quotes",
+        }
+    }
+
+    fn next_prompt(self: Rc<Self>, weights: CharWeights) -> PromptFuture {
+        Box::pin(async move {
+            match fetch_quote().await {
+                Ok(quote) => {
+                    let (quote, _) = self.normalization.apply(quote.trim(), &self.expected);
+                    if self.blocklist.allows(&quote) && self.score(&quote, &weights) > 0.0 {
+                        return Ok(quote);
+                    }
+                }
+                Err(e) => tracing::warn!("Typing a bundled quote, the API failed: {e}"),
+            }
+            pick(OFFLINE_QUOTES, &self.blocklist, &|line| {
+                self.score(line, &weights)
+            })
+            .map(str::to_string)
+            .ok_or_else(|| "quotes: nothing can be typed".to_string())
+        })
+    }
+}
+
+/// `word` with its first letter in upper case, if it is a lower case one
+fn capitalized(word: &str) -> Option<String> {
+    let mut chars = word.chars();
+    let first = chars.next().filter(|c| c.is_lowercase())?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+/// A non-empty line of `text` without blocked words, sampled by `score`
+/// among random candidates
+fn pick<'a>(text: &'a str, blocklist: &Blocklist, score: &dyn Fn(&str) -> f64) -> Option<&'a str> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let mut rng = rand::thread_rng();
+    let candidates: Vec<(&str, f64)> = lines
+        .choose_multiple(&mut rng, CANDIDATES)
+        .filter(|l| blocklist.allows(l))
+        .map(|l| (*l, score(l)))
+        .filter(|(_l, s)| *s > 0.0)
+        .collect();
+    let index = WeightedIndex::new(candidates.iter().map(|(_l, s)| *s)).ok()?;
+    Some(candidates[index.sample(&mut rng)].0)
+}
+
+fn random_symbols(settings: &Settings) -> RandomSymbols {
+    RandomSymbols {
+        chars: settings.symbol_chars(),
+        len: settings.prompt_len,
+    }
+}
+
+/// All prompt sources, the first one is the default
+pub fn registry(settings: &Settings) -> Vec<Rc<dyn PromptSource>> {
+    let blocklist = Rc::new(settings.blocklist());
+    vec![
+        Rc::new(random_symbols(settings)),
+        Rc::new(AssetLines {
+            id: DICTIONARY_SOURCE,
+            name_key: "source-dictionary",
+            asset: "words.txt",
+            offline: Some(OFFLINE_WORDS),
+            per_prompt: WORDS_PER_PROMPT,
+            blocklist: blocklist.clone(),
+            capitalize: settings.uppercase_words,
+        }),
+        Rc::new(AssetLines {
+            id: "quotes",
+            name_key: "source-quotes",
+            asset: "quotes.txt",
+            offline: Some(OFFLINE_QUOTES),
+            per_prompt: 1,
+            blocklist: blocklist.clone(),
+            capitalize: false,
+        }),
+        Rc::new(AssetLines {
+            id: "code",
+            name_key: "source-code",
+            asset: "code.txt",
+            offline: None,
+            per_prompt: 1,
+            blocklist: blocklist.clone(),
+            capitalize: false,
+        }),
+        Rc::new(OnlineQuotes {
+            normalization: settings.normalization,
+            expected: settings.expected_chars(),
+            blocklist: blocklist.clone(),
+        }),
+        Rc::new(CodeSnippets {
+            language: settings.snippet_language,
+        }),
+        Rc::new(Fetched {
+            url: settings.source_url.clone(),
+            blocklist,
+        }),
+    ]
+}
+
+/// The source with `id`, falling back to the default one
+pub fn find(id: &str, settings: &Settings) -> Rc<dyn PromptSource> {
+    registry(settings)
+        .into_iter()
+        .find(|s| s.info().id == id)
+        .unwrap_or_else(|| Rc::new(random_symbols(settings)))
+}
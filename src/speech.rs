@@ -0,0 +1,62 @@
+//! Reading the prompt aloud through the browser's speech synthesis,
+//! for users who have trouble parsing dense symbol prompts visually.
+
+use web_sys::SpeechSynthesisUtterance;
+
+use crate::i18n::{t, t_args};
+
+/// Characters spelled out when the upcoming text is not a plain word
+const SPELLED_CHARS: usize = 5;
+
+/// Key that reads the upcoming text when read-aloud is on. Escape leaves
+/// fullscreen, F9 toggles it.
+pub const READ_ALOUD_KEY: &str = "F8";
+
+/// Speaks `text` in the language with BCP 47 tag `lang`, interrupting earlier speech
+pub fn speak(text: &str, lang: &str) {
+    let Ok(synth) = gloo_utils::window().speech_synthesis() else {
+        return;
+    };
+    let Ok(utterance) = SpeechSynthesisUtterance::new_with_text(text) else {
+        return;
+    };
+    utterance.set_lang(lang);
+    synth.cancel();
+    synth.speak(&utterance);
+}
+
+/// What to say for `upcoming`, the rest of the prompt from the cursor:
+/// the current word if it is made of letters, otherwise a few characters by name
+pub fn describe(upcoming: &str) -> String {
+    let word: String = upcoming
+        .chars()
+        .take_while(|c| !c.is_whitespace())
+        .collect();
+    if !word.is_empty() && word.chars().all(char::is_alphabetic) {
+        return word;
+    }
+    upcoming
+        .chars()
+        .take(SPELLED_CHARS)
+        .map(name_of)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn name_of(c: char) -> String {
+    match c {
+        ' ' => t("char-space"),
+        c if c.is_uppercase() => t_args("char-capital", &[("char", &c)]),
+        c if c.is_alphanumeric() => c.to_string(),
+        c => {
+            let key = format!("char-{}", c as u32);
+            let name = t(&key);
+            // Unnamed symbols are left to the synthesizer
+            if name == key {
+                c.to_string()
+            } else {
+                name
+            }
+        }
+    }
+}
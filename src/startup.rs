@@ -0,0 +1,30 @@
+//! Startup timing. The practice screen has to show up within [`BUDGET_MS`]
+//! of navigation, marks taken along the way make up the startup report.
+
+use std::cell::RefCell;
+
+/// Time from navigation start to the first rendered prompt
+pub const BUDGET_MS: f64 = 500.0;
+
+thread_local! {
+    static MARKS: RefCell<Vec<(&'static str, f64)>> = Default::default();
+}
+
+/// Milliseconds since navigation started, the time base of event timestamps
+pub fn now() -> f64 {
+    gloo_utils::window().performance().map_or(0.0, |p| p.now())
+}
+
+/// Records that startup reached the stage with the message key `name`
+pub fn mark(name: &'static str) {
+    MARKS.with(|m| m.borrow_mut().push((name, now())));
+}
+
+pub fn marks() -> Vec<(&'static str, f64)> {
+    MARKS.with(|m| m.borrow().clone())
+}
+
+/// Time of the last mark, startup is over once the prompt is rendered
+pub fn total() -> Option<f64> {
+    MARKS.with(|m| m.borrow().last().map(|(_name, at)| *at))
+}
@@ -0,0 +1,344 @@
+//! Where statistics are persisted. The app talks to a [`StatsStore`]
+//! picked at startup, never to a storage API directly.
+//! Reads are synchronous, so backends with async APIs keep a copy in memory
+//! that is loaded when the store is opened and written through on every change.
+//! IndexedDB also keeps the events of the log one per record, so that saving
+//! writes the new events rather than the whole log. Its writes fail after
+//! they are made, such a failure is returned by the next write.
+
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use gloo_storage::{LocalStorage, Storage};
+use gloo_utils::{document, window};
+use js_sys::{Array, Promise};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+/// The chosen backend, kept in LocalStorage whatever the backend is
+const BACKEND_STORAGE_KEY: &str = "storage_backend";
+const DB_NAME: &str = "typing-tutor";
+const DB_STORE: &str = "stats";
+const DB_EVENTS_STORE: &str = "events";
+/// Version 2 added the events store
+const DB_VERSION: u32 = 2;
+pub const DOWNLOAD_FILE_NAME: &str = "typing-tutor-storage.json";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Backend {
+    #[default]
+    LocalStorage,
+    IndexedDb,
+    /// Nothing outlives the page
+    Memory,
+    /// Kept in memory until downloaded, and opened from the downloaded file
+    File,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 4] = [
+        Backend::LocalStorage,
+        Backend::IndexedDb,
+        Backend::Memory,
+        Backend::File,
+    ];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Backend::LocalStorage => "backend-local-storage",
+            Backend::IndexedDb => "backend-indexed-db",
+            Backend::Memory => "backend-memory",
+            Backend::File => "backend-file",
+        }
+    }
+
+    pub fn load() -> Self {
+        LocalStorage::get(BACKEND_STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(self) {
+        LocalStorage::set(BACKEND_STORAGE_KEY, self).unwrap();
+    }
+}
+
+pub trait StatsStore {
+    fn backend(&self) -> Backend;
+    fn get(&self, key: &str) -> Option<String>;
+    fn set(&self, key: &str, value: String) -> Result<(), String>;
+
+    /// Whether the events of the log are stored one at a time with
+    /// [`StatsStore::put_events`] rather than within the serialized log
+    fn keeps_events(&self) -> bool {
+        false
+    }
+
+    /// Serialized events stored one at a time, in no particular order
+    fn events(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Stores serialized events by their ids, deleting all stored ones first if `replace`
+    fn put_events(&self, _events: Vec<(String, String)>, _replace: bool) -> Result<(), String> {
+        Err("this storage keeps the log whole".to_string())
+    }
+}
+
+/// Opens the store of `backend`, falling back to LocalStorage if it fails
+pub async fn open(backend: Backend) -> Rc<dyn StatsStore> {
+    match backend {
+        Backend::LocalStorage => Rc::new(LocalStore),
+        Backend::Memory => Rc::new(MemoryStore::default()),
+        Backend::File => Rc::new(FileStore::default()),
+        Backend::IndexedDb => match IndexedDbStore::open().await {
+            Ok(store) => Rc::new(store),
+            Err(e) => {
+                tracing::warn!("IndexedDB is unavailable, using LocalStorage: {e}");
+                Rc::new(LocalStore)
+            }
+        },
+    }
+}
+
+pub struct LocalStore;
+
+impl StatsStore for LocalStore {
+    fn backend(&self) -> Backend {
+        Backend::LocalStorage
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        LocalStorage::raw().get_item(key).ok().flatten()
+    }
+
+    fn set(&self, key: &str, value: String) -> Result<(), String> {
+        LocalStorage::raw().set_item(key, &value).map_err(js_error)
+    }
+}
+
+#[derive(Default)]
+pub struct MemoryStore(RefCell<BTreeMap<String, String>>);
+
+impl StatsStore for MemoryStore {
+    fn backend(&self) -> Backend {
+        Backend::Memory
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: String) -> Result<(), String> {
+        self.0.borrow_mut().insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+/// A memory store that is saved as a downloaded file
+#[derive(Default)]
+pub struct FileStore(MemoryStore);
+
+impl StatsStore for FileStore {
+    fn backend(&self) -> Backend {
+        Backend::File
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.get(key)
+    }
+
+    fn set(&self, key: &str, value: String) -> Result<(), String> {
+        self.0.set(key, value)
+    }
+}
+
+/// The values of `keys` as the contents of a file that [`open_file`] reads back
+pub fn export(store: &dyn StatsStore, keys: &[&str]) -> String {
+    let contents: BTreeMap<&str, String> = keys
+        .iter()
+        .filter_map(|k| Some((*k, store.get(k)?)))
+        .collect();
+    serde_json::to_string(&contents).unwrap()
+}
+
+/// Copies the values of `keys` from one store to another
+pub fn copy(from: &dyn StatsStore, to: &dyn StatsStore, keys: &[&str]) -> Result<(), String> {
+    for key in keys {
+        if let Some(value) = from.get(key) {
+            to.set(key, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Downloads `contents` of [`export`], encrypted or not, or another file of `mime_type`
+pub fn download(contents: &str, name: &str, mime_type: &str) {
+    let blob = gloo_file::Blob::new_with_options(contents, Some(mime_type));
+    let url = gloo_file::ObjectUrl::from(blob);
+    let link = document().create_element("a").unwrap();
+    link.set_attribute("href", &url).unwrap();
+    link.set_attribute("download", name).unwrap();
+    link.unchecked_into::<web_sys::HtmlElement>().click();
+    // Revoking the URL right away could cancel the download
+    gloo_timers::callback::Timeout::new(60_000, move || drop(url)).forget();
+}
+
+/// Puts the values of a downloaded file into `store`
+pub fn open_file(store: &dyn StatsStore, file: &str) -> Result<(), String> {
+    let contents: BTreeMap<String, String> =
+        serde_json::from_str(file).map_err(|e| e.to_string())?;
+    for (key, value) in contents {
+        store.set(&key, value)?;
+    }
+    Ok(())
+}
+
+pub struct IndexedDbStore {
+    db: IdbDatabase,
+    cache: MemoryStore,
+    /// Serialized events by id
+    events: RefCell<BTreeMap<String, String>>,
+    /// Error of a write that failed since the last one was made
+    failure: Rc<RefCell<Option<String>>>,
+}
+
+fn js_error(e: JsValue) -> String {
+    format!("{e:?}")
+}
+
+/// Waits for an IndexedDB request to succeed
+async fn request(req: &IdbRequest) -> Result<JsValue, String> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        req.set_onsuccess(Some(&resolve));
+        req.set_onerror(Some(&reject));
+    });
+    JsFuture::from(promise).await.map_err(js_error)?;
+    req.result().map_err(js_error)
+}
+
+/// String records of an object store by key
+async fn read_all(db: &IdbDatabase, name: &str) -> Result<BTreeMap<String, String>, String> {
+    let transaction = db.transaction_with_str(name).map_err(js_error)?;
+    let store = transaction.object_store(name).map_err(js_error)?;
+    // Both come sorted by key
+    let keys: Array = request(&store.get_all_keys().map_err(js_error)?)
+        .await?
+        .unchecked_into();
+    let values: Array = request(&store.get_all().map_err(js_error)?)
+        .await?
+        .unchecked_into();
+    Ok(keys
+        .iter()
+        .zip(values.iter())
+        .filter_map(|(key, value)| Some((key.as_string()?, value.as_string()?)))
+        .collect())
+}
+
+impl IndexedDbStore {
+    async fn open() -> Result<Self, String> {
+        let factory = window()
+            .indexed_db()
+            .map_err(js_error)?
+            .ok_or("IndexedDB is not supported")?;
+        let open = factory
+            .open_with_u32(DB_NAME, DB_VERSION)
+            .map_err(js_error)?;
+        let on_upgrade = Closure::once(move |e: web_sys::Event| {
+            let open: IdbOpenDbRequest = e.target().unwrap().unchecked_into();
+            let db: IdbDatabase = open.result().unwrap().unchecked_into();
+            // Creating a store of an older version fails, it is kept as it is
+            for name in [DB_STORE, DB_EVENTS_STORE] {
+                let _ = db.create_object_store(name);
+            }
+        });
+        open.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+        let db: IdbDatabase = request(&open).await?.unchecked_into();
+        let cache = MemoryStore(RefCell::new(read_all(&db, DB_STORE).await?));
+        let events = RefCell::new(read_all(&db, DB_EVENTS_STORE).await?);
+        Ok(IndexedDbStore {
+            db,
+            cache,
+            events,
+            failure: Default::default(),
+        })
+    }
+
+    /// A read-write transaction on `name` whose failure is kept for the next write
+    fn write(&self, name: &str) -> Result<IdbTransaction, JsValue> {
+        let transaction = self
+            .db
+            .transaction_with_str_and_mode(name, IdbTransactionMode::Readwrite)?;
+        let failure = self.failure.clone();
+        let watched = transaction.clone();
+        let on_failure = Closure::<dyn FnMut()>::new(move || {
+            let error = watched
+                .error()
+                .map_or_else(|| "the write was aborted".to_string(), |e| e.message());
+            tracing::error!("Writing to IndexedDB failed: {error}");
+            // An error is followed by the abort it causes
+            failure.borrow_mut().get_or_insert(error);
+        })
+        .into_js_value();
+        transaction.set_onerror(Some(on_failure.unchecked_ref()));
+        transaction.set_onabort(Some(on_failure.unchecked_ref()));
+        Ok(transaction)
+    }
+
+    /// The failure of an earlier write, reported once
+    fn take_failure(&self) -> Result<(), String> {
+        self.failure.take().map_or(Ok(()), Err)
+    }
+}
+
+impl StatsStore for IndexedDbStore {
+    fn backend(&self) -> Backend {
+        Backend::IndexedDb
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.cache.get(key)
+    }
+
+    fn set(&self, key: &str, value: String) -> Result<(), String> {
+        let write = self
+            .write(DB_STORE)
+            .and_then(|t| t.object_store(DB_STORE))
+            .and_then(|s| s.put_with_key(&JsValue::from_str(&value), &JsValue::from_str(key)));
+        // The page keeps working with the cached value even if the write failed,
+        // values are written whole so the next write of the key makes up for it
+        self.cache.set(key, value)?;
+        write.map_err(js_error)?;
+        self.take_failure()
+    }
+
+    fn keeps_events(&self) -> bool {
+        true
+    }
+
+    fn events(&self) -> Vec<String> {
+        self.events.borrow().values().cloned().collect()
+    }
+
+    fn put_events(&self, events: Vec<(String, String)>, replace: bool) -> Result<(), String> {
+        // Events of a failed write are missing, the caller stores them all anew
+        self.take_failure()?;
+        let store = self
+            .write(DB_EVENTS_STORE)
+            .and_then(|t| t.object_store(DB_EVENTS_STORE));
+        let mut cache = self.events.borrow_mut();
+        if replace {
+            cache.clear();
+        }
+        let write = store.and_then(|store| {
+            if replace {
+                store.clear()?;
+            }
+            for (id, event) in &events {
+                store.put_with_key(&JsValue::from_str(event), &JsValue::from_str(id))?;
+            }
+            Ok(())
+        });
+        cache.extend(events);
+        write.map_err(js_error)
+    }
+}
@@ -0,0 +1,92 @@
+//! Read-ahead of a prompt that keeps growing, as in a timed test. Prompts of
+//! the source are buffered so that, at the live typing speed, the buffer
+//! outlasts the time the source takes to produce the next one.
+
+use std::collections::VecDeque;
+
+/// Assumed until a prompt of the source arrived
+const DEFAULT_FETCH_MS: f64 = 1000.0;
+/// Assumed until there is a live speed or a recent result, 120 WPM
+const DEFAULT_CHARS_PER_MS: f64 = 0.01;
+/// Buffered over what is typed while the next prompt is produced
+const HEADROOM: f64 = 2.0;
+/// Weight of the latest fetch time in the running average
+const FETCH_SMOOTHING: f64 = 0.3;
+
+#[derive(Default)]
+pub struct Buffer {
+    prompts: VecDeque<String>,
+    /// When the prompt being produced was requested
+    requested_at: Option<f64>,
+    /// Running average of the time to produce a prompt
+    fetch_ms: Option<f64>,
+}
+
+impl Buffer {
+    pub fn buffered_chars(&self) -> usize {
+        self.prompts.iter().map(|p| p.chars().count()).sum()
+    }
+
+    /// Characters to have ahead of the caret at `chars_per_ms`
+    pub fn target_chars(&self, chars_per_ms: Option<f64>) -> usize {
+        let pace = chars_per_ms.unwrap_or(DEFAULT_CHARS_PER_MS);
+        (pace * self.fetch_ms.unwrap_or(DEFAULT_FETCH_MS) * HEADROOM).ceil() as usize
+    }
+
+    /// Whether another prompt should be requested, one at a time,
+    /// with `untyped` characters of the prompt left
+    pub fn wants_more(&self, chars_per_ms: Option<f64>, untyped: usize) -> bool {
+        self.requested_at.is_none()
+            && untyped + self.buffered_chars() < self.target_chars(chars_per_ms)
+    }
+
+    pub fn requested(&mut self, now: f64) {
+        self.requested_at = Some(now);
+    }
+
+    /// Buffers a prompt, or only ends the request if the source failed
+    pub fn received(&mut self, prompt: Option<String>, now: f64) {
+        let Some(requested_at) = self.requested_at.take() else {
+            return;
+        };
+        let took = now - requested_at;
+        self.fetch_ms = Some(match self.fetch_ms {
+            Some(average) => average + FETCH_SMOOTHING * (took - average),
+            None => took,
+        });
+        self.prompts.extend(prompt);
+    }
+
+    pub fn pop(&mut self) -> Option<String> {
+        self.prompts.pop_front()
+    }
+
+    /// Drops the buffered prompts, a prompt requested before is ignored
+    pub fn clear(&mut self) {
+        self.prompts.clear();
+        self.requested_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_typists_and_slow_sources_buffer_more() {
+        let mut buffer = Buffer::default();
+        buffer.requested(0.0);
+        buffer.received(Some("a".repeat(50)), 2000.0);
+        let slow = buffer.target_chars(Some(0.005));
+        let fast = buffer.target_chars(Some(0.02));
+        assert_eq!((slow, fast), (20, 80));
+        assert!(!buffer.wants_more(Some(0.005), 0));
+        assert!(buffer.wants_more(Some(0.02), 0));
+        assert!(!buffer.wants_more(Some(0.02), 30));
+        buffer.requested(3000.0);
+        assert!(!buffer.wants_more(Some(0.02), 0));
+        buffer.received(None, 7000.0);
+        // 2000 ms and then 4000 ms
+        assert_eq!(buffer.target_chars(Some(0.01)), 52);
+    }
+}
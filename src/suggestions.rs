@@ -0,0 +1,152 @@
+//! Practice suggested while the app is open but idle: cards made from the
+//! analytics, shown one at a time in turn, each with a button to act on it.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+};
+
+use itertools::Itertools;
+use typing_core::DAY_MS;
+
+use crate::{analytics::KeyGroupStats, bigrams::Bigrams, history::History};
+
+/// Without key presses for this long the suggestions are shown
+pub const IDLE_AFTER_MS: f64 = 60_000.0;
+/// How often idleness is checked and the next card is shown
+pub const ROTATE_MS: u32 = 10_000;
+pub const WEEK_MS: f64 = 7.0 * DAY_MS;
+/// Keystrokes of a key needed in each of the weeks to compare its latency
+const MIN_KEY_SAMPLES: usize = 20;
+/// Rise of the latency of a key over the week before worth a drill, in percent
+const MIN_LATENCY_RISE: f64 = 15.0;
+/// Days without a timed test before one is suggested
+const TIMED_TEST_GAP_DAYS: f64 = 5.0;
+/// Pairs with the key drilled
+const DRILL_PAIRS: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Suggestion {
+    /// The mean latency of `key` this week is `percent` higher than the week before
+    SlowerKey { key: char, percent: f64 },
+    /// No timed test for `days` days, `None` if there was none yet
+    TimedTest { days: Option<usize> },
+    /// The key with the highest error score
+    WeakestKey { key: char },
+}
+
+/// Keys that got slower from `before` to `after`, the most first
+fn slower_keys(
+    before: &BTreeMap<char, KeyGroupStats>,
+    after: &BTreeMap<char, KeyGroupStats>,
+) -> Vec<(char, f64)> {
+    let mean = |stats: &KeyGroupStats| {
+        stats
+            .mean_latency()
+            .filter(|_| stats.total >= MIN_KEY_SAMPLES)
+    };
+    after
+        .iter()
+        .filter(|(key, _)| !key.is_whitespace())
+        .filter_map(|(key, stats)| {
+            let (old, new) = (mean(before.get(key)?)?, mean(stats)?);
+            let percent = 100.0 * (new - old) / old;
+            (percent >= MIN_LATENCY_RISE).then_some((*key, percent))
+        })
+        .sorted_by(|a, b| b.1.total_cmp(&a.1))
+        .collect()
+}
+
+/// Suggestions as of `now`, from the per-key stats of the week before
+/// and of the last week
+pub fn suggest(
+    before: &BTreeMap<char, KeyGroupStats>,
+    after: &BTreeMap<char, KeyGroupStats>,
+    history: &History,
+    error_scores: &HashMap<char, usize>,
+    now: f64,
+) -> Vec<Suggestion> {
+    let mut suggestions: Vec<Suggestion> = slower_keys(before, after)
+        .into_iter()
+        .take(2)
+        .map(|(key, percent)| Suggestion::SlowerKey { key, percent })
+        .collect();
+    let last_timed = history
+        .results
+        .iter()
+        .filter(|r| r.tags.iter().any(|t| t.starts_with("timed:")))
+        .map(|r| r.meta.created_at)
+        .max_by(f64::total_cmp);
+    match last_timed.map(|at| (now - at) / DAY_MS) {
+        Some(days) if days >= TIMED_TEST_GAP_DAYS => suggestions.push(Suggestion::TimedTest {
+            days: Some(days as usize),
+        }),
+        None if !history.results.is_empty() => {
+            suggestions.push(Suggestion::TimedTest { days: None })
+        }
+        _ => {}
+    }
+    let weakest = error_scores
+        .iter()
+        .filter(|(c, score)| **score > 0 && !c.is_whitespace())
+        .max_by_key(|(c, score)| (**score, Reverse(**c)));
+    if let Some((key, _)) = weakest {
+        suggestions.push(Suggestion::WeakestKey { key: *key });
+    }
+    suggestions
+}
+
+/// Pairs with `key` typed most often, to drill it in context
+pub fn drill_pairs(bigrams: &Bigrams, key: char) -> Vec<(char, char)> {
+    let pairs: Vec<(char, char)> = bigrams
+        .iter()
+        .filter(|((a, b), _)| (*a == key || *b == key) && !a.is_whitespace() && !b.is_whitespace())
+        .sorted_by_key(|(_, stats)| Reverse(stats.count))
+        .take(DRILL_PAIRS)
+        .map(|(pair, _)| *pair)
+        .collect();
+    if pairs.is_empty() {
+        vec![(key, key)]
+    } else {
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(latency: f64) -> KeyGroupStats {
+        let mut stats = KeyGroupStats::default();
+        for _ in 0..MIN_KEY_SAMPLES {
+            stats.add(0, Some(latency));
+        }
+        stats
+    }
+
+    #[test]
+    fn keys_that_got_slower_come_first() {
+        let before = BTreeMap::from([
+            (';', stats(200.0)),
+            ('a', stats(150.0)),
+            ('b', stats(100.0)),
+        ]);
+        let after = BTreeMap::from([
+            (';', stats(240.0)),
+            ('a', stats(200.0)),
+            ('b', stats(105.0)),
+        ]);
+        let history = History::default();
+        let scores = HashMap::from([('q', 3), ('z', 5)]);
+        let suggestions = suggest(&before, &after, &history, &scores, 0.0);
+        assert_eq!(suggestions.len(), 3);
+        assert!(matches!(
+            suggestions[0],
+            Suggestion::SlowerKey { key: 'a', .. }
+        ));
+        assert!(
+            matches!(suggestions[1], Suggestion::SlowerKey { key: ';', percent } if (percent - 20.0).abs() < 1e-9)
+        );
+        assert_eq!(suggestions[2], Suggestion::WeakestKey { key: 'z' });
+    }
+}
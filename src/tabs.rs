@@ -0,0 +1,40 @@
+//! Tabs of the app share the storage, so statistics saved by one tab would
+//! overwrite what another one saved. A tab that opens takes over saving and
+//! tells the others over a `BroadcastChannel`, they stop saving until reloaded.
+
+use gloo_utils::window;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{BroadcastChannel, MessageEvent};
+use yew::Callback;
+
+const CHANNEL: &str = "typing-tutor-tabs";
+const CLAIM: &str = "claim";
+
+/// Announces this tab as the one saving statistics,
+/// calls `on_taken_over` when a tab opened later takes over
+pub fn claim(on_taken_over: Callback<()>) {
+    let channel = match BroadcastChannel::new(CHANNEL) {
+        Ok(channel) => channel,
+        Err(e) => {
+            tracing::warn!("Other tabs can't be detected: {e:?}");
+            return;
+        }
+    };
+    let cb: Closure<dyn Fn(MessageEvent)> = Closure::new(move |e: MessageEvent| {
+        if e.data().as_string().as_deref() == Some(CLAIM) {
+            on_taken_over.emit(());
+        }
+    });
+    // A channel with a listener stays open for the life of the page
+    channel.set_onmessage(Some(cb.into_js_value().unchecked_ref()));
+    if let Err(e) = channel.post_message(&JsValue::from_str(CLAIM)) {
+        tracing::warn!("Other tabs were not told to stop saving: {e:?}");
+    }
+}
+
+/// Reloads the page, which loads what the other tab saved and takes over
+pub fn take_over() {
+    if let Err(e) = window().location().reload() {
+        tracing::warn!("Reloading failed: {e:?}");
+    }
+}
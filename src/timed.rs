@@ -0,0 +1,126 @@
+//! Timed test: the prompt keeps growing and typing stops when the countdown
+//! runs out, the result is the speed and accuracy over that window.
+//! A test can be limited to the keys of one hand to compare the hands.
+
+use crate::{
+    fingers::{FingerMap, Hand},
+    history::{History, PromptResult},
+    keycaps::Layout,
+    units::{SpeedSample, SpeedUnit},
+};
+
+/// Lengths of a test to choose from, in seconds
+pub const DURATIONS_S: [u32; 4] = [15, 30, 60, 120];
+/// The countdown is refreshed this often
+pub const TICK_MS: u32 = 1000;
+/// More text is appended once fewer characters than this are left to type
+pub const EXTEND_BELOW: usize = 30;
+/// Tests of both hands, then of each hand alone
+pub const HANDS: [Option<Hand>; 3] = [None, Some(Hand::Left), Some(Hand::Right)];
+
+pub struct TimedTest {
+    pub seconds: u32,
+    /// Only the keys of this hand are typed
+    pub hand: Option<Hand>,
+    /// First keystroke, in event timestamp milliseconds
+    pub started_at: Option<f64>,
+}
+
+impl TimedTest {
+    pub fn new(seconds: u32, hand: Option<Hand>) -> Self {
+        TimedTest {
+            seconds,
+            hand,
+            started_at: None,
+        }
+    }
+
+    pub fn duration_ms(&self) -> f64 {
+        f64::from(self.seconds) * 1000.0
+    }
+
+    /// Milliseconds left at `now`
+    pub fn remaining(&self, now: f64) -> f64 {
+        let elapsed = self.started_at.map_or(0.0, |at| now - at);
+        (self.duration_ms() - elapsed).max(0.0)
+    }
+
+    /// Tags of the result besides the mode
+    pub fn tags(&self) -> impl Iterator<Item = String> {
+        [
+            Some(format!("timed:{}", self.seconds)),
+            self.hand.map(hand_tag),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+fn hand_tag(hand: Hand) -> String {
+    format!("hand:{}", hand.name())
+}
+
+/// What was typed before the time ran out
+pub struct TimedResult {
+    pub seconds: u32,
+    pub hand: Option<Hand>,
+    pub sample: SpeedSample,
+    pub mistakes: usize,
+}
+
+/// Those of `chars` typed by the fingers of `hand`, and the space
+/// to separate them; all of `chars` if the hand types none of them
+pub fn hand_chars(chars: &[char], map: FingerMap, layout: Layout, hand: Hand) -> Vec<char> {
+    let typed: Vec<char> = chars
+        .iter()
+        .copied()
+        .filter(|c| *c != ' ')
+        .filter(|c| map.finger_for(layout, *c).is_some_and(|f| f.hand == hand))
+        .collect();
+    if typed.is_empty() {
+        return chars.to_vec();
+    }
+    typed.into_iter().chain([' ']).collect()
+}
+
+/// The fastest result of a test of `seconds` typed with `hand`, or with both
+/// hands if `None`, that was not sloppy
+pub fn personal_best(
+    history: &History,
+    seconds: u32,
+    hand: Option<Hand>,
+    unit: SpeedUnit,
+) -> Option<&PromptResult> {
+    let test = TimedTest::new(seconds, hand);
+    let tags: Vec<String> = test.tags().collect();
+    history
+        .results
+        .iter()
+        .filter(|r| !r.sloppy && tags.iter().all(|t| r.tags.contains(t)))
+        // Tests of both hands have no hand tag
+        .filter(|r| hand.is_some() || !r.tags.iter().any(|t| t.starts_with("hand:")))
+        .max_by(|a, b| {
+            unit.rate(&a.speed_sample())
+                .total_cmp(&unit.rate(&b.speed_sample()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hands_are_told_apart() {
+        let chars: Vec<char> = "asdfjkl;".chars().collect();
+        let left = hand_chars(&chars, FingerMap::Standard, Layout::Qwerty, Hand::Left);
+        assert_eq!(left.iter().collect::<String>(), "asdf ");
+        let right = hand_chars(&chars, FingerMap::Standard, Layout::Qwerty, Hand::Right);
+        assert_eq!(right.iter().collect::<String>(), "jkl; ");
+        assert_eq!(
+            TimedTest::new(30, Some(Hand::Left))
+                .tags()
+                .collect::<Vec<_>>(),
+            ["timed:30", "hand:left"]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! Technique tips chosen by simple rules over session analytics.
+//! To add a tip, add a rule to [`RULES`] and its message to the language packs.
+
+use crate::analytics::SessionAnalytics;
+
+/// Tips are not shown before this many keystrokes, the numbers are noise until then
+pub const MIN_KEYSTROKES: usize = 200;
+/// Mistakes on a hand, out of its keystrokes, that count as an imbalance
+const HAND_IMBALANCE_RATIO: f64 = 2.0;
+
+pub struct TipRule {
+    pub message_key: &'static str,
+    pub applies: fn(&SessionAnalytics) -> bool,
+}
+
+fn error_rate((mistakes, total): (usize, usize)) -> Option<f64> {
+    (total >= 50).then(|| mistakes as f64 / total as f64)
+}
+
+fn hand_weaker(weak: (usize, usize), strong: (usize, usize)) -> bool {
+    match (error_rate(weak), error_rate(strong)) {
+        (Some(weak), Some(strong)) => weak > 0.02 && weak > strong * HAND_IMBALANCE_RATIO,
+        _ => false,
+    }
+}
+
+/// Rules in order of priority, the first that applies wins
+pub const RULES: &[TipRule] = &[
+    TipRule {
+        message_key: "tip-backspace",
+        applies: |a| a.backspace_rate() > 0.1,
+    },
+    TipRule {
+        message_key: "tip-same-finger",
+        applies: |a| match (a.same_finger_latency, a.other_latency) {
+            (Some(same), Some(other)) => same > other * 1.5,
+            _ => false,
+        },
+    },
+    TipRule {
+        message_key: "tip-left-hand",
+        applies: |a| hand_weaker(a.left_hand, a.right_hand),
+    },
+    TipRule {
+        message_key: "tip-right-hand",
+        applies: |a| hand_weaker(a.right_hand, a.left_hand),
+    },
+    TipRule {
+        message_key: "tip-look-at-screen",
+        applies: |a| a.other_latency.is_some_and(|l| l > 600.0),
+    },
+];
+
+pub fn pick(analytics: &SessionAnalytics) -> Option<&'static TipRule> {
+    if analytics.keystrokes < MIN_KEYSTROKES {
+        return None;
+    }
+    RULES.iter().find(|rule| (rule.applies)(analytics))
+}
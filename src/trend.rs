@@ -0,0 +1,96 @@
+//! How fast the speed improves with practice: a least-squares line through
+//! the speed of each result over the minutes practiced up to it.
+
+use crate::{history::PromptResult, units::SpeedUnit};
+
+/// Fewer results say more about the day's form than about the trend
+const MIN_POINTS: usize = 5;
+
+pub struct Trend {
+    /// Speed gained per minute of practice
+    pub per_minute: f64,
+    /// Speed the line is at after the last result
+    pub current: f64,
+}
+
+/// Minutes practiced up to each of `results` and its speed in `unit`,
+/// the points [`Trend::fit`] takes. Sloppy results are left out.
+pub fn points<'a>(
+    results: impl IntoIterator<Item = &'a PromptResult>,
+    unit: SpeedUnit,
+) -> Vec<(f64, f64)> {
+    let mut practiced = 0.0;
+    results
+        .into_iter()
+        .filter(|r| !r.sloppy)
+        .map(|r| {
+            practiced += r.millis / 60_000.0;
+            (practiced, unit.rate(&r.speed_sample()))
+        })
+        .collect()
+}
+
+impl Trend {
+    /// `points` are minutes practiced so far and the speed of a result, in order
+    pub fn fit(points: &[(f64, f64)]) -> Option<Trend> {
+        if points.len() < MIN_POINTS {
+            return None;
+        }
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let (covariance, variance) = points.iter().fold((0.0, 0.0), |(c, v), (x, y)| {
+            (c + (x - mean_x) * (y - mean_y), v + (x - mean_x).powi(2))
+        });
+        if variance == 0.0 {
+            return None;
+        }
+        let per_minute = covariance / variance;
+        let last = points[points.len() - 1].0;
+        Some(Trend {
+            per_minute,
+            current: mean_y + per_minute * (last - mean_x),
+        })
+    }
+
+    /// Minutes of practice until the speed reaches `target` at this rate,
+    /// `None` if it does not improve
+    pub fn minutes_to(&self, target: f64) -> Option<f64> {
+        if self.current >= target {
+            return Some(0.0);
+        }
+        (self.per_minute > 0.0).then(|| (target - self.current) / self.per_minute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_a_line_through_the_points() {
+        let points: Vec<_> = (1..=5)
+            .map(|m| (f64::from(m), 20.0 + 2.0 * f64::from(m)))
+            .collect();
+        let trend = Trend::fit(&points).unwrap();
+        assert!((trend.per_minute - 2.0).abs() < 1e-9);
+        assert!((trend.current - 30.0).abs() < 1e-9);
+        assert!(Trend::fit(&points[..4]).is_none());
+        assert!(Trend::fit(&[(1.0, 20.0); 5]).is_none());
+    }
+
+    #[test]
+    fn minutes_to_a_target_at_the_rate() {
+        let trend = Trend {
+            per_minute: 0.5,
+            current: 30.0,
+        };
+        assert_eq!(trend.minutes_to(40.0), Some(20.0));
+        assert_eq!(trend.minutes_to(25.0), Some(0.0));
+        let flat = Trend {
+            per_minute: 0.0,
+            ..trend
+        };
+        assert_eq!(flat.minutes_to(40.0), None);
+    }
+}
@@ -0,0 +1,88 @@
+//! Typing speed units. Communities count speed differently,
+//! so every displayed speed goes through [`SpeedUnit`].
+
+use serde::{Deserialize, Serialize};
+use typing_core::CHARS_PER_WORD;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum SpeedUnit {
+    /// Words per minute, a word being 5 characters
+    #[default]
+    Wpm,
+    /// Words per minute, counting actual whitespace-separated words
+    ActualWpm,
+    /// Characters per minute
+    Cpm,
+    /// Keystrokes per minute, including corrections
+    Kpm,
+}
+
+/// When the time of a prompt starts, it always ends on the last keystroke
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum TimerStart {
+    /// Reading the prompt before typing is free
+    #[default]
+    FirstKeystroke,
+    /// Like tests that count the time from showing the text
+    PromptShown,
+}
+
+impl TimerStart {
+    pub const ALL: [TimerStart; 2] = [TimerStart::FirstKeystroke, TimerStart::PromptShown];
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            TimerStart::FirstKeystroke => "timer-first-keystroke",
+            TimerStart::PromptShown => "timer-prompt-shown",
+        }
+    }
+
+    /// Tag of results timed this way
+    pub fn tag(self) -> &'static str {
+        match self {
+            TimerStart::FirstKeystroke => "timer:first-keystroke",
+            TimerStart::PromptShown => "timer:prompt-shown",
+        }
+    }
+}
+
+/// What was typed and how long it took
+pub struct SpeedSample {
+    pub chars: usize,
+    pub words: usize,
+    pub keystrokes: usize,
+    pub millis: f64,
+}
+
+impl SpeedUnit {
+    pub const ALL: [SpeedUnit; 4] = [
+        SpeedUnit::Wpm,
+        SpeedUnit::ActualWpm,
+        SpeedUnit::Cpm,
+        SpeedUnit::Kpm,
+    ];
+
+    /// Message key of the unit's abbreviation
+    pub fn message_key(self) -> &'static str {
+        match self {
+            SpeedUnit::Wpm => "unit-wpm",
+            SpeedUnit::ActualWpm => "unit-actual-wpm",
+            SpeedUnit::Cpm => "unit-cpm",
+            SpeedUnit::Kpm => "unit-kpm",
+        }
+    }
+
+    pub fn rate(self, sample: &SpeedSample) -> f64 {
+        let minutes = sample.millis / 60_000.0;
+        if minutes <= 0.0 {
+            return 0.0;
+        }
+        let amount = match self {
+            SpeedUnit::Wpm => sample.chars as f64 / CHARS_PER_WORD,
+            SpeedUnit::ActualWpm => sample.words as f64,
+            SpeedUnit::Cpm => sample.chars as f64,
+            SpeedUnit::Kpm => sample.keystrokes as f64,
+        };
+        amount / minutes
+    }
+}
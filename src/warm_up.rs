@@ -0,0 +1,207 @@
+//! A short warm-up offered at the start of a session. Unlike drills it does
+//! not follow the statistics: the same patterns of the layout every time,
+//! rolls along the home row, keys alternating between hands, common trigrams.
+
+use crate::keycaps::Layout;
+
+/// Frequent English trigrams, typed the same way on every layout
+const TRIGRAMS: [&str; 12] = [
+    "the", "and", "ing", "her", "hat", "his", "tha", "ere", "for", "ent", "ion", "ter",
+];
+/// Times a pattern is repeated on its line
+const REPEATS: usize = 3;
+/// Keys of a row under the fingers of each hand, index fingers last
+const LEFT_KEYS: std::ops::Range<usize> = 0..4;
+const RIGHT_KEYS: std::ops::Range<usize> = 6..10;
+/// Rows of [`Layout::rows`] the hands alternate on
+const ALTERNATING_ROWS: [usize; 3] = [2, 1, 3];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Section {
+    HomeRowRolls,
+    AlternatingHands,
+    Trigrams,
+}
+
+impl Section {
+    pub fn message_key(self) -> &'static str {
+        match self {
+            Section::HomeRowRolls => "warm-up-rolls",
+            Section::AlternatingHands => "warm-up-alternating",
+            Section::Trigrams => "warm-up-trigrams",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct WarmUpLine {
+    pub section: Section,
+    pub text: String,
+}
+
+fn line(section: Section, pattern: &str) -> WarmUpLine {
+    WarmUpLine {
+        section,
+        text: [pattern; REPEATS].join(" "),
+    }
+}
+
+/// Keys of `row` under the left and the right hand, from the little fingers in
+fn hands(row: &str) -> (Vec<char>, Vec<char>) {
+    let keys: Vec<char> = row.chars().collect();
+    let mut right = keys[RIGHT_KEYS].to_vec();
+    right.reverse();
+    (keys[LEFT_KEYS].to_vec(), right)
+}
+
+/// Lines of the warm-up for `layout`, the same on every call
+pub fn routine(layout: Layout) -> Vec<WarmUpLine> {
+    let rows = layout.rows();
+    let (left, right) = hands(rows[2]);
+    let rolls = [left, right].map(|keys| {
+        let inward: String = keys.iter().collect();
+        let outward: String = keys.iter().rev().collect();
+        line(Section::HomeRowRolls, &format!("{inward} {outward}"))
+    });
+    let alternating = ALTERNATING_ROWS.map(|row| {
+        let (left, right) = hands(rows[row]);
+        let pairs: Vec<String> = left
+            .iter()
+            .zip(&right)
+            .map(|(l, r)| format!("{l}{r}"))
+            .collect();
+        line(Section::AlternatingHands, &pairs.join(" "))
+    });
+    let trigrams = WarmUpLine {
+        section: Section::Trigrams,
+        text: TRIGRAMS.join(" "),
+    };
+    rolls
+        .into_iter()
+        .chain(alternating)
+        .chain([trigrams])
+        .collect()
+}
+
+/// A warm-up being typed
+pub struct WarmUp {
+    lines: Vec<WarmUpLine>,
+    position: usize,
+}
+
+impl WarmUp {
+    pub fn new(layout: Layout) -> Self {
+        WarmUp {
+            lines: routine(layout),
+            position: 0,
+        }
+    }
+
+    pub fn prompt(&self) -> &str {
+        &self.lines[self.position].text
+    }
+
+    pub fn section(&self) -> Section {
+        self.lines[self.position].section
+    }
+
+    /// The current line counting from 1 and the number of lines
+    pub fn position(&self) -> (usize, usize) {
+        (self.position + 1, self.lines.len())
+    }
+
+    /// Moves to the next line, `false` once the warm-up is done
+    pub fn advance(&mut self) -> bool {
+        self.position += 1;
+        self.position < self.lines.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routine_is_deterministic() {
+        for layout in Layout::ALL {
+            assert_eq!(routine(layout), routine(layout));
+        }
+    }
+
+    #[test]
+    fn qwerty_routine() {
+        let texts: Vec<String> = routine(Layout::Qwerty)
+            .into_iter()
+            .map(|l| l.text)
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "asdf fdsa asdf fdsa asdf fdsa",
+                ";lkj jkl; ;lkj jkl; ;lkj jkl;",
+                "a; sl dk fj a; sl dk fj a; sl dk fj",
+                "qp wo ei ru qp wo ei ru qp wo ei ru",
+                "z/ x. c, vm z/ x. c, vm z/ x. c, vm",
+                "the and ing her hat his tha ere for ent ion ter",
+            ]
+        );
+    }
+
+    #[test]
+    fn sections_come_in_order() {
+        for layout in Layout::ALL {
+            let sections: Vec<Section> = routine(layout).iter().map(|l| l.section).collect();
+            assert!(sections.windows(2).all(|w| w[0] as u8 <= w[1] as u8));
+            assert_eq!(sections.first(), Some(&Section::HomeRowRolls));
+            assert_eq!(sections.last(), Some(&Section::Trigrams));
+        }
+    }
+
+    #[test]
+    fn alternating_lines_switch_hands_on_every_key() {
+        for layout in Layout::ALL {
+            let rows = layout.rows();
+            let left: String = ALTERNATING_ROWS
+                .iter()
+                .flat_map(|r| rows[*r].chars().skip(LEFT_KEYS.start).take(LEFT_KEYS.len()))
+                .collect();
+            for line in routine(layout)
+                .iter()
+                .filter(|l| l.section == Section::AlternatingHands)
+            {
+                for pair in line.text.split(' ') {
+                    let sides: Vec<bool> = pair.chars().map(|c| left.contains(c)).collect();
+                    assert_eq!(sides, [true, false], "{pair} on {layout:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lines_are_typed_on_the_layout() {
+        for layout in Layout::ALL {
+            let keys: String = layout.rows().concat();
+            for line in routine(layout) {
+                let typeable = |c: char| c == ' ' || keys.contains(c);
+                assert!(
+                    line.text.chars().all(typeable),
+                    "{} on {layout:?}",
+                    line.text
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn warm_up_advances_through_all_lines() {
+        let mut warm_up = WarmUp::new(Layout::Colemak);
+        let (_, lines) = warm_up.position();
+        assert_eq!(warm_up.prompt(), "arst tsra arst tsra arst tsra");
+        for line in 1..lines {
+            assert_eq!(warm_up.position(), (line, lines));
+            assert!(warm_up.advance());
+        }
+        assert_eq!(warm_up.section(), Section::Trigrams);
+        assert!(!warm_up.advance());
+    }
+}
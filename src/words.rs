@@ -0,0 +1,79 @@
+//! Words of the prompt: runs of characters between spaces and line breaks.
+//! The renderer groups the characters of each word so the current one can be
+//! underlined, and mistakes are added up per word to list the fumbled ones.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+/// Ranges of char indices of the words of `prompt`. A prompt of a single
+/// run of characters, like random symbols, has no words.
+pub fn segment(prompt: &str) -> Vec<Range<usize>> {
+    let chars: Vec<char> = prompt.chars().collect();
+    let mut words = vec![];
+    let mut start = None;
+    for (i, c) in chars.iter().enumerate().chain([(chars.len(), &' ')]) {
+        match (start, *c == ' ' || *c == '\n') {
+            (Some(s), true) => {
+                words.push(s..i);
+                start = None;
+            }
+            (None, false) => start = Some(i),
+            _ => {}
+        }
+    }
+    if words.len() < 2 {
+        vec![]
+    } else {
+        words
+    }
+}
+
+/// Index of the word being typed, or of the next one when the caret is between words
+pub fn current(words: &[Range<usize>], caret: usize) -> Option<usize> {
+    words.iter().position(|w| w.end > caret)
+}
+
+fn text(prompt: &str, word: &Range<usize>) -> String {
+    prompt.chars().skip(word.start).take(word.len()).collect()
+}
+
+fn is_fumbled(word: &Range<usize>, fumbled_at: &BTreeSet<usize>) -> bool {
+    fumbled_at.range(word.clone()).next().is_some()
+}
+
+/// Words of `prompt` with a mistake at any of the `fumbled_at` char indices
+pub fn fumbled(prompt: &str, fumbled_at: &BTreeSet<usize>) -> Vec<String> {
+    segment(prompt)
+        .iter()
+        .filter(|w| is_fumbled(w, fumbled_at))
+        .map(|w| text(prompt, w))
+        .collect()
+}
+
+/// Share of the words of `prompt` typed without a mistake, in percent
+pub fn accuracy(prompt: &str, fumbled_at: &BTreeSet<usize>) -> Option<f64> {
+    let words = segment(prompt);
+    let clean = words.iter().filter(|w| !is_fumbled(w, fumbled_at)).count();
+    (!words.is_empty()).then(|| 100.0 * clean as f64 / words.len() as f64)
+}
+
+#[derive(Default)]
+pub struct WordStats {
+    pub typed: usize,
+    pub fumbled: usize,
+}
+
+/// Adds the words of a completed prompt to `stats`
+pub fn account(
+    stats: &mut BTreeMap<String, WordStats>,
+    prompt: &str,
+    fumbled_at: &BTreeSet<usize>,
+) {
+    for word in segment(prompt) {
+        let entry = stats.entry(text(prompt, &word)).or_default();
+        entry.typed += 1;
+        entry.fumbled += usize::from(is_fumbled(&word, fumbled_at));
+    }
+}
@@ -0,0 +1,52 @@
+//! Drives the practice page through `window.typingTutor` like a browser
+//! test would. Run with `wasm-pack test --headless --firefox`.
+#![cfg(target_arch = "wasm32")]
+
+use gloo_timers::future::TimeoutFuture;
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Waits for the app to set up `window.typingTutor`
+async fn api() -> JsValue {
+    for _ in 0..100 {
+        let api = Reflect::get(&gloo_utils::window(), &"typingTutor".into()).unwrap();
+        if !api.is_undefined() {
+            return api;
+        }
+        TimeoutFuture::new(50).await;
+    }
+    panic!("window.typingTutor was not set up");
+}
+
+async fn call(api: &JsValue, name: &str, args: &[JsValue]) -> JsValue {
+    let function: Function = Reflect::get(api, &name.into()).unwrap().unchecked_into();
+    let args = args.iter().collect::<js_sys::Array>();
+    let promise: Promise = function.apply(api, &args).unwrap().unchecked_into();
+    JsFuture::from(promise).await.unwrap()
+}
+
+fn field(state: &JsValue, name: &str) -> JsValue {
+    Reflect::get(state, &name.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn typing_a_prompt_completes_it() {
+    tytutor::start();
+    let api = api().await;
+    let state = call(&api, "state", &[]).await;
+    let prompt = field(&state, "prompt").as_string().unwrap();
+    assert!(!prompt.is_empty());
+    assert_eq!(field(&state, "prompts_done").as_f64(), Some(0.0));
+
+    let keys = format!("{}{{Enter}}", prompt.replace('{', "{{"));
+    call(&api, "type", &[keys.into(), JsValue::UNDEFINED]).await;
+
+    let state = call(&api, "state", &[]).await;
+    assert_eq!(field(&state, "prompts_done").as_f64(), Some(1.0));
+    assert_eq!(field(&state, "typed").as_f64(), Some(0.0));
+    assert_eq!(field(&state, "mistakes").as_f64(), Some(0.0));
+}
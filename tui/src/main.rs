@@ -0,0 +1,254 @@
+//! Practice in the terminal with the generator and the error statistics
+//! of the web version. Each key is checked as it is typed, like on the page.
+//!
+//! The statistics are kept in a stats file like the ones the web version
+//! imports, so practice moves between the two. Like the page, practice adds
+//! to the event log the statistics are replayed from. Other fields are left
+//! as they are.
+//!
+//! Usage: `tytutor-tui [stats.json]`, Escape or Ctrl+C quits.
+
+mod terminal;
+
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use ratatui::{
+    style::{Style, Stylize},
+    text::{self, Span},
+};
+use serde_json::{json, Map, Value};
+use typing_core::{
+    errors::{TypingErrors, SCORING_VERSION},
+    ids::{RecordMeta, Ulid},
+    prompts::{default_symbols, symbols, CharWeights, SYMBOLS_LEN},
+    CHARS_PER_WORD,
+};
+
+use terminal::Key;
+
+const DEFAULT_STATS_FILE: &str = "typing-stats.json";
+
+fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |d| d.as_millis() as f64)
+}
+
+/// The stats file as read, to write the other fields back unchanged
+struct StatsFile {
+    path: PathBuf,
+    fields: Map<String, Value>,
+    errors: TypingErrors,
+    /// The event log, started from the statistics of a file without one
+    log: Map<String, Value>,
+}
+
+impl StatsFile {
+    fn load(path: &Path) -> Result<Self, String> {
+        let fields = match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| e.to_string())?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Map::new(),
+            Err(e) => return Err(e.to_string()),
+        };
+        let errors = match fields.get("errors") {
+            Some(errors) => serde_json::from_value(errors.clone()).map_err(|e| e.to_string())?,
+            None => TypingErrors {
+                scoring_version: SCORING_VERSION,
+                meta: RecordMeta::new(now()),
+                ..Default::default()
+            },
+        };
+        let log = match fields.get("events") {
+            Some(Value::Object(log)) => log.clone(),
+            _ => {
+                let baseline = serde_json::to_value(&errors).map_err(|e| e.to_string())?;
+                Map::from_iter([
+                    ("baseline".to_string(), baseline),
+                    ("events".to_string(), json!([])),
+                ])
+            }
+        };
+        Ok(StatsFile {
+            path: path.to_owned(),
+            fields,
+            errors,
+            log,
+        })
+    }
+
+    /// Appends an event the way the web version logs it
+    fn log(&mut self, kind: Value, at: f64) {
+        let event = json!({ "id": Ulid::new(at), "at": at, "kind": kind });
+        let events = self.log.entry("events").or_insert_with(|| json!([]));
+        if let Value::Array(events) = events {
+            events.push(event);
+        }
+    }
+
+    fn save(&mut self) -> Result<(), String> {
+        let errors = serde_json::to_value(&self.errors).map_err(|e| e.to_string())?;
+        self.fields.insert("errors".to_string(), errors);
+        self.fields
+            .insert("events".to_string(), Value::Object(self.log.clone()));
+        let text = serde_json::to_string(&self.fields).map_err(|e| e.to_string())?;
+        // Written aside first, so an interrupted save leaves the old file
+        let temp = self.path.with_extension("json.tmp");
+        fs::write(&temp, text).map_err(|e| e.to_string())?;
+        fs::rename(&temp, &self.path).map_err(|e| e.to_string())
+    }
+}
+
+/// The prompt being typed
+struct Line {
+    prompt: Vec<char>,
+    /// Whether each typed key was the expected one
+    correctness: Vec<bool>,
+    mistakes: usize,
+    started: Option<Instant>,
+    /// Time of the previous keystroke, for the latency of the next one
+    previous_at: Option<f64>,
+}
+
+impl Line {
+    fn new(prompt: String, stats: &mut StatsFile) -> Self {
+        let kind = json!({ "PromptStart": { "prompt": prompt, "retention_check": false } });
+        stats.log(kind, now());
+        Line {
+            prompt: prompt.chars().collect(),
+            correctness: vec![],
+            mistakes: 0,
+            started: None,
+            previous_at: None,
+        }
+    }
+
+    /// Accounts a keystroke in the statistics the way the web version does
+    fn type_char(&mut self, typed: char, stats: &mut StatsFile) {
+        let Some(&expected) = self.prompt.get(self.correctness.len()) else {
+            return;
+        };
+        let at = now();
+        stats.log(
+            json!({ "Keystroke": { "expected": expected, "typed": typed } }),
+            at,
+        );
+        let errors = &mut stats.errors;
+        let correct = expected == typed;
+        let latency = self.previous_at.map(|previous| at - previous);
+        errors.account(expected, typed, at);
+        if let (true, Some(ms)) = (correct, latency) {
+            errors.account_latency(expected, ms);
+        }
+        errors.account_ngrams(
+            &self.prompt[..self.correctness.len()],
+            expected,
+            correct,
+            latency,
+        );
+        self.started.get_or_insert_with(Instant::now);
+        self.previous_at = Some(at);
+        self.correctness.push(correct);
+        self.mistakes += usize::from(!correct);
+    }
+
+    fn backspace(&mut self, stats: &mut StatsFile) {
+        if self.correctness.pop().is_some() {
+            stats.log(json!("Backspace"), now());
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.correctness.len() == self.prompt.len()
+    }
+
+    fn wpm(&self) -> f64 {
+        let minutes = self
+            .started
+            .map_or(0.0, |s| s.elapsed().as_secs_f64() / 60.0);
+        self.correctness.len() as f64 / CHARS_PER_WORD / minutes.max(f64::EPSILON)
+    }
+
+    /// Typed keys in green or, if mistyped, the expected ones in red
+    fn render(&self) -> text::Line<'static> {
+        let spans: Vec<Span> = self
+            .prompt
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let style = match self.correctness.get(i) {
+                    Some(true) => Style::new().green(),
+                    Some(false) => Style::new().red().underlined(),
+                    None => Style::new(),
+                };
+                Span::styled(c.to_string(), style)
+            })
+            .collect();
+        spans.into()
+    }
+}
+
+fn main() -> Result<(), String> {
+    let path = std::env::args()
+        .nth(1)
+        .map_or_else(|| PathBuf::from(DEFAULT_STATS_FILE), PathBuf::from);
+    let mut stats = StatsFile::load(&path)?;
+    let mut terminal = terminal::Terminal::enable().map_err(|e| e.to_string())?;
+    practice(&path, &mut stats, &mut terminal)
+}
+
+fn practice(
+    path: &Path,
+    stats: &mut StatsFile,
+    terminal: &mut terminal::Terminal,
+) -> Result<(), String> {
+    let chars = default_symbols();
+    let expected: HashSet<char> = chars.iter().copied().chain([' ']).collect();
+    let next_line = |stats: &mut StatsFile| {
+        let weights = CharWeights::new(&stats.errors, &expected, now());
+        Line::new(symbols(&chars, &weights, SYMBOLS_LEN), stats)
+    };
+    let mut line = next_line(stats);
+    let mut last_result = String::new();
+    loop {
+        let rows = vec![
+            text::Line::from(vec![
+                format!("Statistics: {}", path.display()).bold(),
+                ", Escape quits".dim(),
+            ]),
+            text::Line::default(),
+            line.render(),
+            text::Line::default(),
+            format!("{} mistakes", line.mistakes).into(),
+            last_result.clone().dim().into(),
+        ];
+        let column = u16::try_from(line.correctness.len()).unwrap_or(u16::MAX);
+        terminal
+            .draw(rows, (2, column))
+            .map_err(|e| e.to_string())?;
+        match terminal::read_key().map_err(|e| e.to_string())? {
+            Key::Quit => break,
+            Key::Char(c) => line.type_char(c, stats),
+            Key::Backspace => line.backspace(stats),
+            Key::Enter => {}
+        }
+        if line.is_done() {
+            stats.log(json!({ "PromptEnd": { "flagged": false } }), now());
+            last_result = format!(
+                "Last line: {:.1} WPM, {} mistakes",
+                line.wpm(),
+                line.mistakes
+            );
+            stats.save()?;
+            line = next_line(stats);
+        }
+    }
+    if line.started.is_some() {
+        stats.save()?;
+    }
+    Ok(())
+}
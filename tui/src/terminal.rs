@@ -0,0 +1,75 @@
+//! Key input and drawing on any terminal crossterm supports
+
+use std::io::{self, Stdout};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{self as term, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, text::Line, widgets::Paragraph};
+
+pub enum Key {
+    Char(char),
+    Backspace,
+    Enter,
+    /// Escape or Ctrl+C
+    Quit,
+}
+
+/// The terminal in raw mode on the alternate screen until dropped: keys
+/// arrive one at a time, unechoed, and Ctrl+C is read as a key rather than
+/// ending the process
+pub struct Terminal(ratatui::Terminal<CrosstermBackend<Stdout>>);
+
+impl Terminal {
+    pub fn enable() -> io::Result<Self> {
+        term::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        ratatui::Terminal::new(CrosstermBackend::new(io::stdout())).map(Terminal)
+    }
+
+    /// Rows of the screen from the top, the cursor at (row, column)
+    pub fn draw(&mut self, rows: Vec<Line>, cursor: (u16, u16)) -> io::Result<()> {
+        self.0.draw(|frame| {
+            frame.render_widget(Paragraph::new(rows), frame.size());
+            frame.set_cursor(cursor.1, cursor.0);
+        })?;
+        Ok(())
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = term::disable_raw_mode();
+    }
+}
+
+/// Blocks until a key is pressed. Other keys, such as arrows,
+/// and characters typed with Ctrl or Alt are skipped.
+pub fn read_key() -> io::Result<Key> {
+    loop {
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+        let key = match code {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Key::Quit,
+            KeyCode::Esc => Key::Quit,
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Char(_) if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                continue
+            }
+            KeyCode::Char(c) => Key::Char(c),
+            _ => continue,
+        };
+        return Ok(key);
+    }
+}
@@ -0,0 +1,293 @@
+//! Error statistics: how much each character needs practice.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ids::RecordMeta, review::RetentionStats, DAY_MS};
+
+/// Bump when [`TypingErrors::account`] changes:
+/// stored stats are then recomputed from the event log
+pub const SCORING_VERSION: u32 = 5;
+/// Weight of a new interval in the moving average of [`TypingErrors::latency`]
+const LATENCY_SMOOTHING: f64 = 0.2;
+/// Longer intervals are pauses rather than a slow key
+pub const MAX_LATENCY_MS: f64 = 2000.0;
+/// Longest run of keys whose transitions are tracked
+pub const NGRAM_LEN: usize = 3;
+
+/// How keystrokes change the error scores that weight the prompts
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct Penalties {
+    /// Added to the score of a mistyped character
+    pub error: usize,
+    /// Added to the score of a pair of expected and typed characters
+    pub pair: usize,
+    /// Taken from both scores on a correct keystroke
+    pub correct: usize,
+    /// Days over which the scores of a character halve while it is not typed,
+    /// 0 keeps them until correct keystrokes lower them
+    pub half_life_days: usize,
+}
+
+impl Default for Penalties {
+    fn default() -> Self {
+        Penalties {
+            error: 10,
+            pair: 50,
+            correct: 1,
+            half_life_days: 0,
+        }
+    }
+}
+
+/// How a transition between keys goes
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+#[serde(default)]
+pub struct NgramStats {
+    /// Raised when the last key is mistyped after the others, lowered when it is typed correctly
+    pub score: usize,
+    /// Moving average of the interval (ms) before the last key
+    pub latency: Option<f64>,
+}
+
+fn average_in(average: &mut f64, ms: f64) {
+    *average += (ms - *average) * LATENCY_SMOOTHING;
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct TypingErrors {
+    pub error_score: HashMap<char, usize>,
+    pub error_stats: HashMap<String, usize>,
+    /// Unix time (ms) each expected character was last typed
+    pub last_practiced: HashMap<char, f64>,
+    /// Results of retention checks, kept apart from regular practice
+    pub retention: HashMap<char, RetentionStats>,
+    /// Moving average of the interval (ms) before each expected character was typed correctly
+    pub latency: HashMap<char, f64>,
+    /// Transitions by the keys of two or three in a row
+    pub ngrams: HashMap<String, NgramStats>,
+    /// Letters typed in the wrong case, by the letter expected.
+    /// Counted apart from the scores, the slip is the Shift and not the key.
+    pub shift_errors: HashMap<char, usize>,
+    /// [`SCORING_VERSION`] the stats were computed with
+    pub scoring_version: u32,
+    /// Penalties the stats were computed with
+    pub penalties: Penalties,
+    pub meta: RecordMeta,
+}
+
+impl TypingErrors {
+    /// Scores a keystroke: `typed_char` where `expected_c` was expected
+    pub fn account(&mut self, expected_c: char, typed_char: char, now: f64) {
+        let correct = expected_c == typed_char;
+        let penalties = self.penalties;
+        self.decay(expected_c, now);
+        self.last_practiced.insert(expected_c, now);
+        let score = self.error_score.entry(expected_c).or_default();
+        if correct {
+            *score = score.saturating_sub(penalties.correct);
+            self.error_stats
+                .iter_mut()
+                .filter(|(k, _v)| k.starts_with(expected_c))
+                .for_each(|(_k, v)| *v = v.saturating_sub(penalties.correct));
+        } else {
+            *score += penalties.error;
+            *self.error_score.entry(typed_char).or_default() += 1;
+            let stat_score = self
+                .error_stats
+                .entry(chars_to_key(expected_c, typed_char))
+                .or_default();
+            *stat_score += penalties.pair;
+            if is_case_slip(expected_c, typed_char) {
+                *self.shift_errors.entry(expected_c).or_default() += 1;
+            }
+        }
+        self.meta.updated_at = now;
+    }
+
+    /// Mistakes on letters in the wrong case, in total
+    pub fn shift_error_count(&self) -> usize {
+        self.shift_errors.values().sum()
+    }
+
+    /// What the scores of `c` are multiplied by for the time since it was last
+    /// typed. Shorter breaks than a day are left alone, rounding would undo their decay.
+    pub fn decay_factor(&self, c: char, now: f64) -> f64 {
+        let half_life = self.penalties.half_life_days as f64 * DAY_MS;
+        match self.last_practiced.get(&c) {
+            Some(last) if half_life > 0.0 && now - last >= DAY_MS => {
+                0.5f64.powf((now - last) / half_life)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Shrinks the scores of `c` and of the transitions into it by [`Self::decay_factor`]
+    fn decay(&mut self, c: char, now: f64) {
+        let factor = self.decay_factor(c, now);
+        if factor == 1.0 {
+            return;
+        }
+        let decay = |score: &mut usize| *score = (*score as f64 * factor).round() as usize;
+        if let Some(score) = self.error_score.get_mut(&c) {
+            decay(score);
+        }
+        self.error_stats
+            .iter_mut()
+            .filter(|(k, _v)| k.starts_with(c))
+            .for_each(|(_k, v)| decay(v));
+        self.ngrams
+            .iter_mut()
+            .filter(|(k, _v)| k.ends_with(c))
+            .for_each(|(_k, v)| decay(&mut v.score));
+    }
+
+    /// Records the `ms` since the previous keystroke of the prompt
+    /// after which `expected_c` was typed correctly, pauses are left out
+    pub fn account_latency(&mut self, expected_c: char, ms: f64) {
+        if !(0.0..=MAX_LATENCY_MS).contains(&ms) {
+            return;
+        }
+        average_in(self.latency.entry(expected_c).or_insert(ms), ms);
+    }
+
+    /// Scores the transitions into `expected_c` from the keys expected before it
+    /// in the prompt, the latest last. Runs of three are only kept once mistyped,
+    /// there are too many of them to keep every one typed.
+    pub fn account_ngrams(
+        &mut self,
+        previous: &[char],
+        expected_c: char,
+        correct: bool,
+        latency: Option<f64>,
+    ) {
+        let penalties = self.penalties;
+        let latency = latency.filter(|ms| (0.0..=MAX_LATENCY_MS).contains(ms));
+        let previous = &previous[previous.len().saturating_sub(NGRAM_LEN - 1)..];
+        for start in 0..previous.len() {
+            let ngram: String = previous[start..].iter().chain([&expected_c]).collect();
+            let stats = match self.ngrams.get_mut(&ngram) {
+                Some(stats) => stats,
+                None if correct && ngram.chars().count() > 2 => continue,
+                None => self.ngrams.entry(ngram).or_default(),
+            };
+            if !correct {
+                stats.score += penalties.error;
+                continue;
+            }
+            stats.score = stats.score.saturating_sub(penalties.correct);
+            if let Some(ms) = latency {
+                average_in(stats.latency.get_or_insert(ms), ms);
+            }
+        }
+    }
+}
+
+fn chars_to_key(ex: char, ty: char) -> String {
+    format!("{ex} -> {ty}")
+}
+
+/// Whether `typed` is the letter `expected` with Shift in the other state
+fn is_case_slip(expected: char, typed: char) -> bool {
+    expected != typed
+        && expected.is_alphabetic()
+        && expected.to_lowercase().eq(typed.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mistakes_raise_the_scores() {
+        let mut errors = TypingErrors::default();
+        errors.account('a', 's', 1.0);
+        assert_eq!(errors.error_score[&'a'], 10);
+        assert_eq!(errors.error_score[&'s'], 1);
+        assert_eq!(errors.error_stats["a -> s"], 50);
+        assert_eq!(errors.last_practiced[&'a'], 1.0);
+        assert_eq!(errors.meta.updated_at, 1.0);
+    }
+
+    #[test]
+    fn wrong_case_counts_as_a_shift_error() {
+        let mut errors = TypingErrors::default();
+        errors.account('A', 'a', 1.0);
+        errors.account('b', 'B', 1.0);
+        errors.account('c', 'x', 1.0);
+        errors.account('1', '!', 1.0);
+        assert_eq!(errors.shift_errors[&'A'], 1);
+        assert_eq!(errors.shift_errors[&'b'], 1);
+        assert_eq!(errors.shift_error_count(), 2);
+    }
+
+    #[test]
+    fn correct_keystrokes_lower_the_scores() {
+        let mut errors = TypingErrors::default();
+        errors.account('a', 's', 1.0);
+        errors.account('a', 'a', 2.0);
+        assert_eq!(errors.error_score[&'a'], 9);
+        assert_eq!(errors.error_stats["a -> s"], 49);
+        // The typed character is only lowered when it is expected itself
+        assert_eq!(errors.error_score[&'s'], 1);
+    }
+
+    #[test]
+    fn scores_do_not_go_below_zero() {
+        let mut errors = TypingErrors {
+            penalties: Penalties {
+                correct: 100,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        errors.account('a', 's', 1.0);
+        errors.account('a', 'a', 2.0);
+        assert_eq!(errors.error_score[&'a'], 0);
+        assert_eq!(errors.error_stats["a -> s"], 0);
+    }
+
+    #[test]
+    fn scores_decay_between_sessions() {
+        let mut errors = TypingErrors {
+            penalties: Penalties {
+                half_life_days: 30,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        errors.account('a', 's', 0.0);
+        errors.account('a', 's', 1000.0);
+        assert_eq!(errors.error_score[&'a'], 20);
+        errors.account('a', 'a', 60.0 * DAY_MS);
+        // A quarter left after two half-lives, then lowered by the correct keystroke
+        assert_eq!(errors.error_score[&'a'], 4);
+        assert_eq!(errors.error_stats["a -> s"], 24);
+    }
+
+    #[test]
+    fn latency_is_averaged_without_pauses() {
+        let mut errors = TypingErrors::default();
+        errors.account_latency('a', 100.0);
+        errors.account_latency('a', 200.0);
+        errors.account_latency('a', MAX_LATENCY_MS + 1.0);
+        assert_eq!(errors.latency[&'a'], 120.0);
+    }
+
+    #[test]
+    fn transitions_are_scored() {
+        let mut errors = TypingErrors::default();
+        errors.account_ngrams(&['x', 't'], 'h', true, Some(100.0));
+        assert_eq!(errors.ngrams["th"].latency, Some(100.0));
+        // Only mistyped runs of three are kept
+        assert!(!errors.ngrams.contains_key("xth"));
+        errors.account_ngrams(&['i', 'o'], 'n', false, None);
+        assert_eq!(errors.ngrams["on"].score, 10);
+        assert_eq!(errors.ngrams["ion"].score, 10);
+        errors.account_ngrams(&['i', 'o'], 'n', true, None);
+        assert_eq!(errors.ngrams["ion"].score, 9);
+    }
+}
@@ -0,0 +1,113 @@
+//! Identity and timestamps of persisted records, so data coming
+//! from several devices can be merged and deduplicated reliably.
+//!
+//! Ids are ULIDs: 48 bits of Unix time in ms followed by 80 random bits,
+//! written as 26 Crockford base32 characters, so they sort by creation time.
+
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const ULID_LEN: usize = 26;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Ulid(u128);
+
+impl Ulid {
+    pub fn new(now: f64) -> Self {
+        let time = (now as u128) & ((1 << 48) - 1);
+        let random = rand::random::<u128>() & ((1 << 80) - 1);
+        Ulid(time << 80 | random)
+    }
+
+    /// Records stored before ids were introduced have the nil id
+    pub fn is_nil(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Display for Ulid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s: String = (0..ULID_LEN)
+            .rev()
+            .map(|i| CROCKFORD[(self.0 >> (i * 5)) as usize & 0x1f] as char)
+            .collect();
+        f.write_str(&s)
+    }
+}
+
+impl FromStr for Ulid {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != ULID_LEN {
+            return Err(format!("ULID must be {ULID_LEN} characters long: {s}"));
+        }
+        // 26 characters hold 130 bits, the first one only has 3 of the 128
+        if s.as_bytes()[0] > b'7' {
+            return Err(format!("ULID is out of range: {s}"));
+        }
+        s.bytes()
+            .try_fold(0u128, |acc, b| {
+                let digit = CROCKFORD
+                    .iter()
+                    .position(|c| *c == b.to_ascii_uppercase())
+                    .ok_or_else(|| format!("invalid ULID character in {s}"))?;
+                Ok(acc << 5 | digit as u128)
+            })
+            .map(Ulid)
+    }
+}
+
+impl Serialize for Ulid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ulid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+#[serde(default)]
+pub struct RecordMeta {
+    pub id: Ulid,
+    /// Unix time in ms
+    pub created_at: f64,
+    pub updated_at: f64,
+}
+
+impl RecordMeta {
+    pub fn new(now: f64) -> Self {
+        RecordMeta {
+            id: Ulid::new(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_missing(&self) -> bool {
+        self.id.is_nil()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_round_trip_and_overflow_is_rejected() {
+        let id = Ulid::new(1_700_000_000_000.0);
+        assert_eq!(id.to_string().parse(), Ok(id));
+        let max = Ulid(u128::MAX);
+        assert_eq!(max.to_string(), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        assert_eq!(max.to_string().parse(), Ok(max));
+        assert!("8ZZZZZZZZZZZZZZZZZZZZZZZZZ".parse::<Ulid>().is_err());
+    }
+}
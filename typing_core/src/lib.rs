@@ -0,0 +1,13 @@
+//! What the tutor does without a browser: scoring keystrokes into error
+//! statistics and generating prompts weighted by them. It knows nothing about
+//! the page or the storage, so it is tested natively and any frontend can use it.
+
+/// Timestamps are Unix time in ms
+pub const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+/// Characters that make up a "standard" word of typing speeds
+pub const CHARS_PER_WORD: f64 = 5.0;
+
+pub mod errors;
+pub mod ids;
+pub mod prompts;
+pub mod review;
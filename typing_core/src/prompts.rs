@@ -0,0 +1,204 @@
+//! Prompts weighted by the error statistics.
+
+use std::collections::{HashMap, HashSet};
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+
+use crate::errors::TypingErrors;
+
+/// Default length of generated symbol prompts
+pub const SYMBOLS_LEN: usize = 50;
+/// Weight added for each time a key is slower than the average key
+const SLOWNESS_WEIGHT: f64 = 2.0;
+/// Transitions practiced at once, the worst ones
+const WORST_NGRAMS: usize = 10;
+/// Share of picks that are a whole transition rather than a single character
+const NGRAM_SHARE: f64 = 0.25;
+
+/// Printable ASCII, what the default layout can type
+pub fn default_symbols() -> Vec<char> {
+    (0x21..=0x7e_u8).map(|b| b as char).collect()
+}
+
+/// Weight for being slower than the average, which makes it the same at any speed
+fn slowness(latency: Option<f64>, mean: f64) -> usize {
+    match latency {
+        Some(latency) if mean > 0.0 => {
+            ((latency / mean - 1.0).max(0.0) * SLOWNESS_WEIGHT).round() as usize
+        }
+        _ => 0,
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    sum / f64::from(count.max(1))
+}
+
+/// How much each typeable character needs practice, at least 1,
+/// and the transitions between them that need it most.
+/// Mistakes and slowness both add to it.
+#[derive(Clone)]
+pub struct CharWeights {
+    chars: HashMap<char, usize>,
+    ngrams: Vec<(String, usize)>,
+}
+
+impl CharWeights {
+    /// Scores decay as if each character were typed again at `now` (Unix time in ms)
+    pub fn new(stats: &TypingErrors, expected: &HashSet<char>, now: f64) -> Self {
+        let per_error = stats.penalties.error.max(1);
+        let mean_latency = mean(
+            expected
+                .iter()
+                .filter_map(|c| stats.latency.get(c).copied()),
+        );
+        let chars = expected
+            .iter()
+            .map(|c| {
+                let score = stats.error_score.get(c).copied().unwrap_or_default();
+                let score = (score as f64 * stats.decay_factor(*c, now)).round() as usize;
+                let slowness = slowness(stats.latency.get(c).copied(), mean_latency);
+                (*c, score.div_ceil(per_error) + slowness + 1)
+            })
+            .collect();
+        let mean_ngram_latency = mean(stats.ngrams.values().filter_map(|n| n.latency));
+        let mut ngrams: Vec<(String, usize)> = stats
+            .ngrams
+            .iter()
+            .filter(|(ngram, _)| ngram.chars().all(|c| expected.contains(&c)))
+            .map(|(ngram, n)| {
+                let slowness = slowness(n.latency, mean_ngram_latency);
+                let last = ngram.chars().last().unwrap_or_default();
+                let score = (n.score as f64 * stats.decay_factor(last, now)).round() as usize;
+                (ngram.clone(), score.div_ceil(per_error) + slowness)
+            })
+            .filter(|(_, weight)| *weight > 0)
+            .collect();
+        ngrams.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ngrams.truncate(WORST_NGRAMS);
+        CharWeights { chars, ngrams }
+    }
+
+    /// `None` for characters that cannot be typed
+    pub fn get(&self, c: char) -> Option<usize> {
+        self.chars.get(&c).copied()
+    }
+
+    /// Transitions worked into prompts and their weights, the worst first
+    pub fn ngrams(&self) -> &[(String, usize)] {
+        &self.ngrams
+    }
+}
+
+/// `len` characters out of `chars`, picked by weight, with some of the worst
+/// transitions among them. Characters that cannot be typed are left out
+/// unless none of them can.
+pub fn symbols(chars: &[char], weights: &CharWeights, len: usize) -> String {
+    let index = WeightedIndex::new(chars.iter().map(|c| weights.get(*c).unwrap_or(0)))
+        .or_else(|_| WeightedIndex::new(chars.iter().map(|_| 1)))
+        .unwrap();
+    let ngrams: Vec<&(String, usize)> = weights
+        .ngrams
+        .iter()
+        .filter(|(ngram, _)| ngram.chars().all(|c| chars.contains(&c)))
+        .collect();
+    let ngram_index = WeightedIndex::new(ngrams.iter().map(|(_, weight)| *weight)).ok();
+    let mut rng = rand::thread_rng();
+    let mut prompt = vec![];
+    while prompt.len() < len {
+        match &ngram_index {
+            Some(ngram_index) if rng.gen_bool(NGRAM_SHARE) => {
+                prompt.extend(ngrams[ngram_index.sample(&mut rng)].0.chars());
+            }
+            _ => prompt.push(chars[index.sample(&mut rng)]),
+        }
+    }
+    prompt.into_iter().take(len).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::NgramStats;
+
+    fn weights(scores: &[(char, usize)]) -> CharWeights {
+        let mut stats = TypingErrors::default();
+        stats.error_score.extend(scores.iter().copied());
+        CharWeights::new(&stats, &scores.iter().map(|(c, _)| *c).collect(), 0.0)
+    }
+
+    #[test]
+    fn weights_follow_the_error_scores() {
+        let weights = weights(&[('a', 0), ('b', 10), ('c', 11)]);
+        assert_eq!(weights.get('a'), Some(1));
+        assert_eq!(weights.get('b'), Some(2));
+        assert_eq!(weights.get('c'), Some(3));
+        assert_eq!(weights.get('d'), None);
+    }
+
+    #[test]
+    fn scores_decay_until_the_key_is_typed_again() {
+        let mut stats = TypingErrors::default();
+        stats.penalties.error = 10;
+        stats.penalties.half_life_days = 1;
+        stats.error_score.insert('a', 40);
+        stats.last_practiced.insert('a', 0.0);
+        let ngram = NgramStats {
+            score: 40,
+            latency: None,
+        };
+        stats.ngrams.insert("ba".to_string(), ngram);
+        let expected = ['a', 'b'].into();
+        let weights = CharWeights::new(&stats, &expected, 0.0);
+        assert_eq!(weights.get('a'), Some(5));
+        assert_eq!(weights.ngrams(), [("ba".to_string(), 4)]);
+        let weights = CharWeights::new(&stats, &expected, 2.0 * crate::DAY_MS);
+        assert_eq!(weights.get('a'), Some(2));
+        assert_eq!(weights.ngrams(), [("ba".to_string(), 1)]);
+    }
+
+    #[test]
+    fn slow_keys_weigh_more() {
+        let mut stats = TypingErrors::default();
+        stats
+            .latency
+            .extend([('a', 100.0), ('b', 100.0), ('c', 400.0)]);
+        let weights = CharWeights::new(&stats, &['a', 'b', 'c', 'd'].into(), 0.0);
+        assert_eq!(weights.get('a'), Some(1));
+        // Twice as slow as the average of 200 ms
+        assert_eq!(weights.get('c'), Some(3));
+        assert_eq!(weights.get('d'), Some(1));
+    }
+
+    #[test]
+    fn symbols_include_the_worst_transitions() {
+        let mut stats = TypingErrors::default();
+        stats.penalties.error = 10;
+        let ngram = NgramStats {
+            score: 30,
+            latency: None,
+        };
+        stats
+            .ngrams
+            .extend([("th".to_string(), ngram), ("tz".to_string(), ngram)]);
+        let weights = CharWeights::new(&stats, &['t', 'h', 'x'].into(), 0.0);
+        assert_eq!(weights.ngrams(), [("th".to_string(), 3)]);
+        let prompt = symbols(&['t', 'h', 'x'], &weights, 200);
+        assert_eq!(prompt.chars().count(), 200);
+        assert!(prompt.contains("th"));
+    }
+
+    #[test]
+    fn symbols_are_typeable_characters() {
+        let prompt = symbols(&['a', 'b', 'x'], &weights(&[('a', 0), ('b', 0)]), 200);
+        assert_eq!(prompt.chars().count(), 200);
+        assert!(prompt.chars().all(|c| c == 'a' || c == 'b'));
+    }
+
+    #[test]
+    fn symbols_fall_back_to_all_characters() {
+        let prompt = symbols(&['x', 'y'], &weights(&[('a', 0)]), 50);
+        assert!(prompt.chars().all(|c| c == 'x' || c == 'y'));
+    }
+}
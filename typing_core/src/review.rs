@@ -0,0 +1,38 @@
+//! Retention checks: occasional prompts made of keys that were mastered
+//! but not practiced for a while, to catch skill decay.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{errors::TypingErrors, DAY_MS};
+
+/// A mastered key is due for review after this long without practice
+const REVIEW_AFTER_MS: f64 = 3.0 * DAY_MS;
+/// Every n-th prompt is a retention check if some keys are due
+pub const REVIEW_EVERY: usize = 10;
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+pub struct RetentionStats {
+    pub correct: usize,
+    pub total: usize,
+}
+
+/// Keys without outstanding errors that were last practiced long ago
+pub fn due_keys(stats: &TypingErrors, now: f64) -> Vec<char> {
+    stats
+        .last_practiced
+        .iter()
+        .filter(|(c, last)| {
+            now - **last > REVIEW_AFTER_MS
+                && stats.error_score.get(c).copied().unwrap_or_default() == 0
+        })
+        .map(|(c, _last)| *c)
+        .collect()
+}
+
+pub fn account(retention: &mut HashMap<char, RetentionStats>, expected_c: char, correct: bool) {
+    let stats = retention.entry(expected_c).or_default();
+    stats.total += 1;
+    stats.correct += usize::from(correct);
+}